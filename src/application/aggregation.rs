@@ -0,0 +1,71 @@
+use crate::domain::{
+    models::{error::DomainError, exchange::Exchange},
+    ports::aggregation::AggregationStrategy,
+};
+use serde_json::Value;
+
+/// Concatenates bodies with a delimiter, e.g. re-joining split lines.
+pub struct JoinBodiesStrategy {
+    delimiter: String,
+}
+
+impl JoinBodiesStrategy {
+    pub fn new(delimiter: &str) -> Self {
+        Self {
+            delimiter: delimiter.to_string(),
+        }
+    }
+}
+
+impl AggregationStrategy for JoinBodiesStrategy {
+    fn aggregate(&self, aggregated: Option<Exchange>, next: Exchange) -> Result<Exchange, DomainError> {
+        match aggregated {
+            None => Ok(next),
+            Some(mut aggregated) => {
+                let joined = format!("{}{}{}", aggregated.body.text()?, self.delimiter, next.body.text()?);
+                aggregated.body = joined.into();
+                Ok(aggregated)
+            }
+        }
+    }
+}
+
+/// Collects bodies into a JSON array; bodies that are valid JSON are embedded as-is,
+/// anything else becomes a JSON string.
+pub struct JsonArrayStrategy;
+
+impl AggregationStrategy for JsonArrayStrategy {
+    fn aggregate(&self, aggregated: Option<Exchange>, next: Exchange) -> Result<Exchange, DomainError> {
+        let element = next
+            .body
+            .json()
+            .map(|value| value.into_owned())
+            .unwrap_or_else(|_| Value::String(next.body.to_string()));
+
+        let (mut exchange, mut items) = match aggregated {
+            None => (next, Vec::new()),
+            Some(aggregated) => {
+                let items = match aggregated.body.json().map(|value| value.into_owned()) {
+                    Ok(Value::Array(items)) => items,
+                    _ => {
+                        return Err(DomainError::fatal("Aggregated body is not a JSON array"))
+                    }
+                };
+                (aggregated, items)
+            }
+        };
+
+        items.push(element);
+        exchange.body = Value::Array(items).into();
+        Ok(exchange)
+    }
+}
+
+/// Keeps only the most recent exchange.
+pub struct UseLatestStrategy;
+
+impl AggregationStrategy for UseLatestStrategy {
+    fn aggregate(&self, _aggregated: Option<Exchange>, next: Exchange) -> Result<Exchange, DomainError> {
+        Ok(next)
+    }
+}
@@ -0,0 +1,326 @@
+use crate::application::{registry::ComponentRegistry, route::Route};
+use crate::domain::{
+    models::{
+        endpoint::{EndpointUri, QueueStats},
+        error::DomainError,
+        exchange::Exchange,
+    },
+    ports::{
+        component::Component,
+        endpoint::{Consumer, Endpoint},
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use tracing::info;
+
+/// `Starting`, `Stopping`, `Suspending` and `Resuming` are held while the route's
+/// consumer changes state; no other change is accepted until it settles.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RouteStatus {
+    Stopped,
+    Started,
+    Suspended,
+    Starting,
+    Stopping,
+    Suspending,
+    Resuming,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RouteInfo {
+    pub id: String,
+    pub from_uri: String,
+    pub status: RouteStatus,
+}
+
+struct RouteEntry {
+    route: Arc<Route>,
+    status: RouteStatus,
+    consumer: Option<Box<dyn Consumer>>,
+}
+
+/// The runtime that owns every route and component and drives their lifecycle.
+pub struct CamelContext {
+    name: String,
+    routes: RwLock<HashMap<String, RouteEntry>>,
+    registry: ComponentRegistry,
+}
+
+impl CamelContext {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            routes: RwLock::new(HashMap::new()),
+            registry: ComponentRegistry::new(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn add_component(&self, component: Arc<dyn Component>) -> Result<(), DomainError> {
+        self.registry.register(component)
+    }
+
+    pub fn component(&self, scheme: &str) -> Result<Option<Arc<dyn Component>>, DomainError> {
+        self.registry.component(scheme)
+    }
+
+    pub fn registry(&self) -> &ComponentRegistry {
+        &self.registry
+    }
+
+    /// Queue depths across every registered component, ordered by endpoint.
+    pub fn queue_stats(&self) -> Result<Vec<QueueStats>, DomainError> {
+        let mut stats = Vec::new();
+        for scheme in self.registry.schemes()? {
+            if let Some(component) = self.registry.component(&scheme)? {
+                stats.extend(component.queue_stats());
+            }
+        }
+        stats.sort_by(|a, b| a.endpoint.cmp(&b.endpoint));
+        Ok(stats)
+    }
+
+    /// Resolves a uri such as `seda:orders?size=100` to its (cached) endpoint.
+    pub fn endpoint(&self, uri: &str) -> Result<Arc<dyn Endpoint>, DomainError> {
+        self.registry.endpoint(uri)
+    }
+
+    pub fn add_route(&self, route: Route) -> Result<(), DomainError> {
+        let mut routes = self.routes.write().map_err(lock_error)?;
+        if routes.contains_key(route.id()) {
+            return Err(DomainError::conflict(format!(
+                "Route {} is already registered",
+                route.id()
+            )));
+        }
+        routes.insert(
+            route.id().to_string(),
+            RouteEntry {
+                route: Arc::new(route),
+                status: RouteStatus::Stopped,
+                consumer: None,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn remove_route(&self, id: &str) -> Result<(), DomainError> {
+        let mut routes = self.routes.write().map_err(lock_error)?;
+        match routes.get(id).map(|entry| entry.status) {
+            None => Err(route_not_found(id)),
+            Some(RouteStatus::Stopped) => {
+                routes.remove(id);
+                Ok(())
+            }
+            Some(_) => Err(DomainError::conflict(format!(
+                "Route {} must be stopped before it is removed",
+                id
+            ))),
+        }
+    }
+
+    pub fn route(&self, id: &str) -> Result<Option<Arc<Route>>, DomainError> {
+        let routes = self.routes.read().map_err(lock_error)?;
+        Ok(routes.get(id).map(|entry| entry.route.clone()))
+    }
+
+    pub fn routes(&self) -> Result<Vec<RouteInfo>, DomainError> {
+        let routes = self.routes.read().map_err(lock_error)?;
+        let mut infos: Vec<RouteInfo> = routes
+            .values()
+            .map(|entry| RouteInfo {
+                id: entry.route.id().to_string(),
+                from_uri: entry.route.from_uri().to_string(),
+                status: entry.status,
+            })
+            .collect();
+        infos.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(infos)
+    }
+
+    pub fn route_status(&self, id: &str) -> Result<Option<RouteStatus>, DomainError> {
+        let routes = self.routes.read().map_err(lock_error)?;
+        Ok(routes.get(id).map(|entry| entry.status))
+    }
+
+    /// Starts every stopped route.
+    pub fn start(&self) -> Result<(), DomainError> {
+        for info in self.routes()? {
+            if info.status == RouteStatus::Stopped {
+                self.start_route(&info.id)?;
+            }
+        }
+        info!("Camel context {} started", self.name);
+        Ok(())
+    }
+
+    /// Stops every route regardless of its current state.
+    pub fn stop(&self) -> Result<(), DomainError> {
+        for info in self.routes()? {
+            if info.status != RouteStatus::Stopped {
+                self.stop_route(&info.id)?;
+            }
+        }
+        info!("Camel context {} stopped", self.name);
+        Ok(())
+    }
+
+    /// Binds the route's `to()` steps to producers and, when a component exists for
+    /// its `from` scheme, starts a consumer feeding the route. Routes without a
+    /// consumer component (e.g. `rest:`) are driven through `process`.
+    pub fn start_route(&self, id: &str) -> Result<(), DomainError> {
+        self.transition(
+            id,
+            &[RouteStatus::Stopped],
+            RouteStatus::Starting,
+            RouteStatus::Started,
+            |route, consumer| {
+                let started = self.bind_and_start(route, consumer);
+                if started.is_err() {
+                    // Leave nothing half-started behind a route that stays Stopped
+                    for sender in route.senders() {
+                        sender.unbind()?;
+                    }
+                }
+                started
+            },
+        )
+    }
+
+    fn bind_and_start(&self, route: &Route, consumer: &mut Option<Box<dyn Consumer>>) -> Result<(), DomainError> {
+        for sender in route.senders() {
+            sender.bind(self.registry.endpoint(sender.uri())?.create_producer()?)?;
+        }
+
+        let scheme = EndpointUri::parse(route.from_uri())?.scheme;
+        if self.registry.component(&scheme)?.is_some() {
+            let endpoint = self.registry.endpoint(route.from_uri())?;
+            let mut started = endpoint.create_consumer(route.pipeline())?;
+            started.start()?;
+            *consumer = Some(started);
+        }
+        Ok(())
+    }
+
+    pub fn stop_route(&self, id: &str) -> Result<(), DomainError> {
+        self.transition(
+            id,
+            &[RouteStatus::Started, RouteStatus::Suspended],
+            RouteStatus::Stopping,
+            RouteStatus::Stopped,
+            |route, consumer| {
+                // A consumer that fails to stop is kept, so stopping can be retried
+                if let Some(running) = consumer.as_mut() {
+                    running.stop()?;
+                }
+                *consumer = None;
+                for sender in route.senders() {
+                    sender.unbind()?;
+                }
+                Ok(())
+            },
+        )
+    }
+
+    pub fn suspend_route(&self, id: &str) -> Result<(), DomainError> {
+        self.transition(
+            id,
+            &[RouteStatus::Started],
+            RouteStatus::Suspending,
+            RouteStatus::Suspended,
+            |_, consumer| match consumer.as_mut() {
+                Some(consumer) => consumer.suspend(),
+                None => Ok(()),
+            },
+        )
+    }
+
+    pub fn resume_route(&self, id: &str) -> Result<(), DomainError> {
+        self.transition(
+            id,
+            &[RouteStatus::Suspended],
+            RouteStatus::Resuming,
+            RouteStatus::Started,
+            |_, consumer| match consumer.as_mut() {
+                Some(consumer) => consumer.resume(),
+                None => Ok(()),
+            },
+        )
+    }
+
+    /// Sends an exchange through a route; only started routes accept work.
+    pub async fn process(&self, route_id: &str, exchange: Exchange) -> Result<Exchange, DomainError> {
+        let route = {
+            let routes = self.routes.read().map_err(lock_error)?;
+            let entry = routes.get(route_id).ok_or_else(|| route_not_found(route_id))?;
+            if entry.status != RouteStatus::Started {
+                return Err(DomainError::transient(format!(
+                    "Route {} is {:?} and cannot accept exchanges",
+                    route_id, entry.status
+                )));
+            }
+            entry.route.clone()
+        };
+        route.process(exchange).await
+    }
+
+    /// Moves the route to `pending` under the lock, runs `on_transition` without
+    /// it, so a slow consumer holds up no other route, then settles on `to`, or
+    /// back on the status it started from if `on_transition` failed.
+    fn transition<F>(
+        &self,
+        id: &str,
+        allowed_from: &[RouteStatus],
+        pending: RouteStatus,
+        to: RouteStatus,
+        on_transition: F,
+    ) -> Result<(), DomainError>
+    where
+        F: FnOnce(&Route, &mut Option<Box<dyn Consumer>>) -> Result<(), DomainError>,
+    {
+        let (route, mut consumer, from) = {
+            let mut routes = self.routes.write().map_err(lock_error)?;
+            let entry = routes.get_mut(id).ok_or_else(|| route_not_found(id))?;
+            if !allowed_from.contains(&entry.status) {
+                return Err(DomainError::conflict(format!(
+                    "Route {} cannot move from {:?} to {:?}",
+                    id, entry.status, to
+                )));
+            }
+            let from = entry.status;
+            entry.status = pending;
+            (entry.route.clone(), entry.consumer.take(), from)
+        };
+
+        let result = on_transition(&route, &mut consumer);
+
+        let mut routes = self.routes.write().map_err(lock_error)?;
+        // Only stopped routes can be removed, so the entry is still there
+        let entry = routes.get_mut(id).ok_or_else(|| route_not_found(id))?;
+        entry.consumer = consumer;
+        match result {
+            Ok(()) => {
+                info!("Route {} {:?} -> {:?}", id, from, to);
+                entry.status = to;
+                Ok(())
+            }
+            Err(e) => {
+                entry.status = from;
+                Err(e)
+            }
+        }
+    }
+}
+
+fn route_not_found(id: &str) -> DomainError {
+    DomainError::not_found(format!("Route {} not found", id))
+}
+
+fn lock_error<T>(e: std::sync::PoisonError<T>) -> DomainError {
+    DomainError::fatal(format!("Failed to acquire lock: {}", e))
+}
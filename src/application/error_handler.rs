@@ -0,0 +1,226 @@
+use crate::application::processors::send::SendProcessor;
+use crate::domain::{
+    models::{
+        dead_letter::DeadLetter,
+        error::{DomainError, ErrorKind},
+        exchange::Exchange,
+    },
+    ports::{dead_letter::DeadLetterRepository, processor::Processor},
+};
+use chrono::Utc;
+use rand::Rng;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+type RetryPredicate = Arc<dyn Fn(&DomainError) -> bool + Send + Sync>;
+
+/// How often and how patiently a failed step is retried.
+#[derive(Clone, Debug)]
+pub struct RedeliveryPolicy {
+    pub maximum_redeliveries: u32,
+    pub redelivery_delay: Duration,
+    pub backoff_multiplier: f64,
+    pub maximum_redelivery_delay: Duration,
+    /// Fraction (0.0..=1.0) by which each delay is randomly shortened or lengthened.
+    pub jitter: f64,
+}
+
+impl RedeliveryPolicy {
+    pub fn new(maximum_redeliveries: u32) -> Self {
+        Self {
+            maximum_redeliveries,
+            ..Self::default()
+        }
+    }
+
+    pub fn redelivery_delay(mut self, delay: Duration) -> Self {
+        self.redelivery_delay = delay;
+        self
+    }
+
+    pub fn backoff_multiplier(mut self, multiplier: f64) -> Self {
+        self.backoff_multiplier = multiplier.max(1.0);
+        self
+    }
+
+    pub fn maximum_redelivery_delay(mut self, delay: Duration) -> Self {
+        self.maximum_redelivery_delay = delay;
+        self
+    }
+
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Delay before redelivery number `attempt` (1-based).
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1) as i32;
+        let base = self.redelivery_delay.as_secs_f64() * self.backoff_multiplier.powi(exponent);
+        let capped = base.min(self.maximum_redelivery_delay.as_secs_f64());
+        let factor = if self.jitter > 0.0 {
+            rand::thread_rng().gen_range((1.0 - self.jitter)..=(1.0 + self.jitter))
+        } else {
+            1.0
+        };
+        Duration::from_secs_f64((capped * factor).max(0.0))
+    }
+}
+
+impl Default for RedeliveryPolicy {
+    fn default() -> Self {
+        Self {
+            maximum_redeliveries: 0,
+            redelivery_delay: Duration::from_millis(1000),
+            backoff_multiplier: 2.0,
+            maximum_redelivery_delay: Duration::from_secs(60),
+            jitter: 0.0,
+        }
+    }
+}
+
+enum DeadLetterTarget {
+    None,
+    Endpoint(Arc<SendProcessor>),
+    Repository(Arc<dyn DeadLetterRepository>),
+}
+
+/// Per-route error handling: redelivers failing steps according to a policy and,
+/// once redeliveries are exhausted, hands the exchange to a dead letter channel.
+pub struct ErrorHandler {
+    policy: RedeliveryPolicy,
+    retry_on: RetryPredicate,
+    dead_letter: DeadLetterTarget,
+    route_id: Option<String>,
+}
+
+impl ErrorHandler {
+    /// Redelivers according to `policy` and then propagates the error.
+    pub fn default_error_handler(policy: RedeliveryPolicy) -> Self {
+        Self {
+            policy,
+            retry_on: Arc::new(default_retry_on),
+            dead_letter: DeadLetterTarget::None,
+            route_id: None,
+        }
+    }
+
+    /// Sends exhausted exchanges to the endpoint at `uri`.
+    pub fn dead_letter_channel(uri: &str) -> Self {
+        Self {
+            dead_letter: DeadLetterTarget::Endpoint(Arc::new(SendProcessor::new(uri))),
+            ..Self::default_error_handler(RedeliveryPolicy::default())
+        }
+    }
+
+    /// Stores exhausted exchanges in `repository` so they can be listed and replayed.
+    pub fn dead_letter_repository(repository: Arc<dyn DeadLetterRepository>) -> Self {
+        Self {
+            dead_letter: DeadLetterTarget::Repository(repository),
+            ..Self::default_error_handler(RedeliveryPolicy::default())
+        }
+    }
+
+    pub fn redelivery(mut self, policy: RedeliveryPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Only errors matching `predicate` are redelivered; others go straight to the dead letter target.
+    pub fn retry_on<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&DomainError) -> bool + Send + Sync + 'static,
+    {
+        self.retry_on = Arc::new(predicate);
+        self
+    }
+
+    pub fn policy(&self) -> &RedeliveryPolicy {
+        &self.policy
+    }
+
+    /// The dead letter endpoint's sender, so a context can bind it when the route starts.
+    pub fn dead_letter_sender(&self) -> Option<Arc<SendProcessor>> {
+        match &self.dead_letter {
+            DeadLetterTarget::Endpoint(sender) => Some(sender.clone()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn set_route_id(&mut self, route_id: &str) {
+        self.route_id = Some(route_id.to_string());
+    }
+
+    /// Runs one step, redelivering a copy of the exchange as it was before the step.
+    pub async fn run(&self, processor: &Arc<dyn Processor>, exchange: Exchange) -> Result<Exchange, DomainError> {
+        let mut attempt_input = exchange;
+        let mut redeliveries = 0;
+
+        loop {
+            let error = match processor.process(attempt_input.clone()).await {
+                Ok(processed) => return Ok(processed),
+                Err(e) => e,
+            };
+
+            attempt_input.add_processing_step(
+                "error_handler",
+                0,
+                false,
+                Some(format!("attempt {} failed: {}", redeliveries + 1, error)),
+            );
+
+            // Filtering is a deliberate outcome, not a failure worth dead-lettering
+            if error.kind() == ErrorKind::Filtered {
+                return Err(error);
+            }
+
+            if redeliveries >= self.policy.maximum_redeliveries || !(self.retry_on)(&error) {
+                self.dead_letter(attempt_input, &error, redeliveries + 1).await;
+                return Err(error);
+            }
+
+            redeliveries += 1;
+            attempt_input.metadata.retry_count += 1;
+            let delay = self.policy.delay_for(redeliveries);
+            warn!(
+                "Exchange {} failed ({}); redelivery {}/{} in {:?}",
+                attempt_input.id, error, redeliveries, self.policy.maximum_redeliveries, delay
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    async fn dead_letter(&self, mut exchange: Exchange, error: &DomainError, attempts: u32) {
+        exchange.set_property("dead_letter_cause", error.to_string());
+        if let Some(route_id) = &self.route_id {
+            exchange.set_property("dead_letter_route", route_id);
+        }
+
+        let result = match &self.dead_letter {
+            DeadLetterTarget::None => return,
+            DeadLetterTarget::Endpoint(sender) => sender.process(exchange.clone()).await.map(|_| ()),
+            DeadLetterTarget::Repository(repository) => {
+                repository
+                    .save(&DeadLetter {
+                        exchange: exchange.clone(),
+                        route_id: self.route_id.clone(),
+                        cause: error.to_string(),
+                        attempts,
+                        failed_at: Utc::now(),
+                    })
+                    .await
+            }
+        };
+
+        // A failing dead letter channel must not mask the original error
+        match result {
+            Ok(()) => info!("Exchange {} moved to dead letter channel", exchange.id),
+            Err(e) => warn!("Failed to dead letter exchange {}: {}", exchange.id, e),
+        }
+    }
+}
+
+fn default_retry_on(error: &DomainError) -> bool {
+    error.is_retryable()
+}
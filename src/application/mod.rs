@@ -1,4 +1,9 @@
 // src/application/mod.rs
+pub mod aggregation;
+pub mod error_handler;
 pub mod processors;
 pub mod pipeline;
+pub mod context;
+pub mod registry;
+pub mod route;
 pub mod services;
\ No newline at end of file
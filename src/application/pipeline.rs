@@ -1,29 +1,74 @@
+use crate::application::error_handler::ErrorHandler;
 use crate::domain::{
     models::{error::DomainError, exchange::Exchange},
     ports::processor::Processor,
 };
+use async_trait::async_trait;
 use std::sync::Arc;
 
 pub struct ProcessorPipeline {
     processors: Vec<Arc<dyn Processor>>,
+    error_handler: Option<Arc<ErrorHandler>>,
 }
 
 impl ProcessorPipeline {
     pub fn new() -> Self {
         Self {
             processors: Vec::new(),
+            error_handler: None,
         }
     }
 
+    pub fn with_processors(processors: Vec<Arc<dyn Processor>>) -> Self {
+        Self {
+            processors,
+            error_handler: None,
+        }
+    }
+
+    /// Runs every step through `error_handler`, so failing steps are redelivered
+    /// and exhausted exchanges dead-lettered.
+    pub fn with_error_handler(mut self, error_handler: Arc<ErrorHandler>) -> Self {
+        self.error_handler = Some(error_handler);
+        self
+    }
+
     pub fn add_processor(&mut self, processor: Arc<dyn Processor>) {
         self.processors.push(processor);
     }
 
+    pub fn len(&self) -> usize {
+        self.processors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.processors.is_empty()
+    }
+
     pub async fn process(&self, exchange: Exchange) -> Result<Exchange, DomainError> {
         let mut current_exchange = exchange;
         for processor in &self.processors {
-            current_exchange = processor.process(current_exchange).await?;
+            let exchange_id = current_exchange.id;
+            let result = match &self.error_handler {
+                Some(error_handler) => error_handler.run(processor, current_exchange).await,
+                None => processor.process(current_exchange).await,
+            };
+            current_exchange = result.map_err(|e| e.in_processor(exchange_id, processor.name()))?;
         }
         Ok(current_exchange)
     }
 }
+
+impl Default for ProcessorPipeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Lets a pipeline be nested inside another pipeline or route as a single step
+#[async_trait]
+impl Processor for ProcessorPipeline {
+    async fn process(&self, exchange: Exchange) -> Result<Exchange, DomainError> {
+        ProcessorPipeline::process(self, exchange).await
+    }
+}
@@ -0,0 +1,308 @@
+use crate::domain::{
+    models::{aggregation::AggregationGroup, error::DomainError, exchange::Exchange, header::HeaderValue},
+    ports::{
+        aggregation::{AggregationRepository, AggregationStrategy},
+        processor::Processor,
+    },
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+pub const AGGREGATED_SIZE: &str = "aggregated_size";
+pub const AGGREGATED_COMPLETED_BY: &str = "aggregated_completed_by";
+pub const AGGREGATED_CORRELATION_KEY: &str = "aggregated_correlation_key";
+
+type CorrelationFn = Arc<dyn Fn(&Exchange) -> Option<String> + Send + Sync>;
+type CompletionPredicate = Arc<dyn Fn(&Exchange) -> bool + Send + Sync>;
+
+tokio::task_local! {
+    /// Groups whose aggregate the current task is emitting, by aggregator address.
+    static EMITTING: Vec<(usize, String)>;
+}
+
+/// Correlates on `ExchangeMetadata.correlation_id`.
+pub fn correlation_id() -> impl Fn(&Exchange) -> Option<String> + Send + Sync + 'static {
+    |exchange: &Exchange| exchange.metadata.correlation_id.clone()
+}
+
+/// Correlates on the value of a header.
+pub fn header(name: &str) -> impl Fn(&Exchange) -> Option<String> + Send + Sync + 'static {
+    let name = name.to_string();
+    move |exchange: &Exchange| exchange.headers.get(&name).map(HeaderValue::to_string)
+}
+
+/// Aggregator EIP: merges exchanges sharing a correlation key and hands each
+/// completed group to `output`. Incoming exchanges continue down the route
+/// unchanged; only completed aggregates reach `output`.
+pub struct AggregatorProcessor {
+    correlation: CorrelationFn,
+    strategy: Arc<dyn AggregationStrategy>,
+    repository: Arc<dyn AggregationRepository>,
+    output: Arc<dyn Processor>,
+    completion_size: Option<usize>,
+    completion_predicate: Option<CompletionPredicate>,
+    completion_timeout: Option<Duration>,
+    completion_interval: Option<Duration>,
+    // Serializes read-merge-save so concurrent exchanges for one key are not lost. It
+    // guards the keys whose completed group is being emitted: those stay stored until
+    // the emit succeeds, and new exchanges for them wait instead of holding the lock
+    emitting: tokio::sync::Mutex<HashSet<String>>,
+    emitted: tokio::sync::Notify,
+}
+
+impl AggregatorProcessor {
+    pub fn new<F>(
+        correlation: F,
+        strategy: Arc<dyn AggregationStrategy>,
+        repository: Arc<dyn AggregationRepository>,
+        output: Arc<dyn Processor>,
+    ) -> Self
+    where
+        F: Fn(&Exchange) -> Option<String> + Send + Sync + 'static,
+    {
+        Self {
+            correlation: Arc::new(correlation),
+            strategy,
+            repository,
+            output,
+            completion_size: None,
+            completion_predicate: None,
+            completion_timeout: None,
+            completion_interval: None,
+            emitting: tokio::sync::Mutex::new(HashSet::new()),
+            emitted: tokio::sync::Notify::new(),
+        }
+    }
+
+    pub fn completion_size(mut self, size: usize) -> Self {
+        self.completion_size = Some(size.max(1));
+        self
+    }
+
+    /// Completes a group as soon as its aggregated exchange satisfies `predicate`.
+    pub fn completion_predicate<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&Exchange) -> bool + Send + Sync + 'static,
+    {
+        self.completion_predicate = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Completes a group that has received nothing new for `timeout`.
+    pub fn completion_timeout(mut self, timeout: Duration) -> Self {
+        self.completion_timeout = Some(timeout);
+        self
+    }
+
+    /// Completes every open group each time `interval` elapses.
+    pub fn completion_interval(mut self, interval: Duration) -> Self {
+        self.completion_interval = Some(interval);
+        self
+    }
+
+    /// Spawns the background task driving timeout and interval completion. Groups
+    /// recovered from a persistent repository after a restart are completed by it too.
+    pub fn start(self: &Arc<Self>) -> Option<JoinHandle<()>> {
+        let tick = match (self.completion_timeout, self.completion_interval) {
+            (None, None) => return None,
+            (Some(timeout), None) => (timeout / 2).max(Duration::from_millis(10)),
+            (None, Some(interval)) => interval,
+            (Some(timeout), Some(interval)) => (timeout / 2).max(Duration::from_millis(10)).min(interval),
+        };
+
+        let aggregator = self.clone();
+        Some(tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(tick);
+            let mut last_interval = tokio::time::Instant::now();
+            loop {
+                ticker.tick().await;
+                if let Err(e) = aggregator.complete_timed_out().await {
+                    warn!("Aggregation timeout check failed: {}", e);
+                }
+                if let Some(interval) = aggregator.completion_interval {
+                    if last_interval.elapsed() >= interval {
+                        last_interval = tokio::time::Instant::now();
+                        if let Err(e) = aggregator.complete_all("interval").await {
+                            warn!("Aggregation interval completion failed: {}", e);
+                        }
+                    }
+                }
+            }
+        }))
+    }
+
+    /// Completes every group idle for longer than the completion timeout.
+    pub async fn complete_timed_out(&self) -> Result<usize, DomainError> {
+        let Some(timeout) = self.completion_timeout else {
+            return Ok(0);
+        };
+        let timeout = chrono::Duration::from_std(timeout)
+            .map_err(|e| DomainError::invalid_field("completion_timeout", e.to_string()))?;
+
+        let now = Utc::now();
+        self.complete_groups(|group| now - group.updated_at >= timeout, "timeout")
+            .await
+    }
+
+    /// Completes every open group regardless of its state.
+    pub async fn complete_all(&self, reason: &str) -> Result<usize, DomainError> {
+        self.complete_groups(|_| true, reason).await
+    }
+
+    /// Completes the stored groups matching `filter` that are not already being
+    /// emitted. One failure does not hold back the other groups; the failures are
+    /// reported together.
+    async fn complete_groups(
+        &self,
+        filter: impl Fn(&AggregationGroup) -> bool,
+        reason: &str,
+    ) -> Result<usize, DomainError> {
+        let groups: Vec<AggregationGroup> = {
+            let mut emitting = self.emitting.lock().await;
+            let groups: Vec<AggregationGroup> = self
+                .repository
+                .find_all()
+                .await?
+                .into_iter()
+                .filter(|group| !emitting.contains(&group.correlation_key) && filter(group))
+                .collect();
+            emitting.extend(groups.iter().map(|group| group.correlation_key.clone()));
+            groups
+        };
+
+        let total = groups.len();
+        let mut failures = Vec::new();
+        for group in groups {
+            let key = group.correlation_key.clone();
+            if let Err(e) = self.emit_and_remove(group, reason).await {
+                failures.push(format!("{}: {}", key, e));
+            }
+        }
+
+        if failures.is_empty() {
+            return Ok(total);
+        }
+        Err(DomainError::transient(format!(
+            "{} of {} aggregation groups failed to complete: {}",
+            failures.len(),
+            total,
+            failures.join("; ")
+        )))
+    }
+
+    /// Emits a group marked as emitting and only then removes it, so a group whose
+    /// emit fails stays stored as it was and is retried later. Clears the mark either way.
+    async fn emit_and_remove(&self, group: AggregationGroup, reason: &str) -> Result<(), DomainError> {
+        let key = group.correlation_key.clone();
+        let mut scope = EMITTING.try_with(Clone::clone).unwrap_or_default();
+        scope.push((self.address(), key.clone()));
+        let emitted = EMITTING.scope(scope, self.emit(group, reason)).await;
+
+        let mut emitting = self.emitting.lock().await;
+        let result = match emitted {
+            Ok(()) => self.repository.remove(&key).await,
+            Err(e) => Err(e),
+        };
+        emitting.remove(&key);
+        drop(emitting);
+        self.emitted.notify_waiters();
+        result
+    }
+
+    fn address(&self) -> usize {
+        self as *const Self as usize
+    }
+
+    fn completed_by(&self, group: &AggregationGroup) -> Option<&'static str> {
+        if self.completion_size.is_some_and(|size| group.size >= size) {
+            return Some("size");
+        }
+        if self
+            .completion_predicate
+            .as_ref()
+            .is_some_and(|predicate| predicate(&group.exchange))
+        {
+            return Some("predicate");
+        }
+        None
+    }
+
+    async fn emit(&self, group: AggregationGroup, completed_by: &str) -> Result<(), DomainError> {
+        info!(
+            "Aggregation {} completed by {} with {} exchanges",
+            group.correlation_key, completed_by, group.size
+        );
+        let mut exchange = group.exchange;
+        exchange.set_header(AGGREGATED_SIZE, group.size);
+        exchange.set_header(AGGREGATED_COMPLETED_BY, completed_by);
+        exchange.set_header(AGGREGATED_CORRELATION_KEY, &group.correlation_key);
+        self.output.process(exchange).await.map(|_| ())
+    }
+}
+
+#[async_trait]
+impl Processor for AggregatorProcessor {
+    async fn process(&self, mut exchange: Exchange) -> Result<Exchange, DomainError> {
+        let key = (self.correlation)(&exchange).ok_or_else(|| {
+            DomainError::validation(format!(
+                "Exchange {} has no correlation key for aggregation",
+                exchange.id
+            ))
+        })?;
+
+        // Wait out an emit of this key's group; the stored group is only removed once it succeeds
+        let mut emitting = loop {
+            let emitting = self.emitting.lock().await;
+            if !emitting.contains(&key) {
+                break emitting;
+            }
+            let reentrant = EMITTING
+                .try_with(|scope| scope.iter().any(|(address, emitted)| *address == self.address() && emitted == &key))
+                .unwrap_or(false);
+            if reentrant {
+                return Err(DomainError::validation(format!(
+                    "Aggregate of group {} was routed back into its own aggregator",
+                    key
+                )));
+            }
+            // Registered while the lock is held, so the wake-up cannot be missed
+            let emitted = self.emitted.notified();
+            tokio::pin!(emitted);
+            emitted.as_mut().enable();
+            drop(emitting);
+            emitted.await;
+        };
+
+        let group = match self.repository.get(&key).await? {
+            Some(mut group) => {
+                group.exchange = self.strategy.aggregate(Some(group.exchange), exchange.clone())?;
+                group.size += 1;
+                group.updated_at = Utc::now();
+                group
+            }
+            None => AggregationGroup::new(&key, self.strategy.aggregate(None, exchange.clone())?),
+        };
+
+        let notes = match self.completed_by(&group) {
+            Some(reason) => {
+                // If the emit fails the stored group is left as it was, so a redelivery aggregates again
+                let notes = format!("group={} completed_by={} size={}", key, reason, group.size);
+                emitting.insert(key.clone());
+                drop(emitting);
+                self.emit_and_remove(group, reason).await?;
+                notes
+            }
+            None => {
+                self.repository.save(&group).await?;
+                format!("group={} pending", key)
+            }
+        };
+        exchange.add_processing_step("aggregator", 0, true, Some(notes));
+        Ok(exchange)
+    }
+}
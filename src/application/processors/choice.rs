@@ -0,0 +1,83 @@
+use crate::domain::{
+    models::{error::DomainError, exchange::Exchange},
+    ports::processor::Processor,
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+type Predicate = Arc<dyn Fn(&Exchange) -> bool + Send + Sync>;
+
+struct WhenClause {
+    label: String,
+    predicate: Predicate,
+    processor: Arc<dyn Processor>,
+}
+
+/// Content-based router: the first matching `when` branch handles the exchange,
+/// falling back to `otherwise`. Exchanges matching no branch pass through unchanged.
+pub struct ChoiceProcessor {
+    whens: Vec<WhenClause>,
+    otherwise: Option<Arc<dyn Processor>>,
+}
+
+impl ChoiceProcessor {
+    pub fn new() -> Self {
+        Self {
+            whens: Vec::new(),
+            otherwise: None,
+        }
+    }
+
+    pub fn when<F>(self, predicate: F, processor: Arc<dyn Processor>) -> Self
+    where
+        F: Fn(&Exchange) -> bool + Send + Sync + 'static,
+    {
+        let label = format!("when[{}]", self.whens.len());
+        self.when_named(&label, predicate, processor)
+    }
+
+    pub fn when_named<F>(mut self, label: &str, predicate: F, processor: Arc<dyn Processor>) -> Self
+    where
+        F: Fn(&Exchange) -> bool + Send + Sync + 'static,
+    {
+        self.whens.push(WhenClause {
+            label: label.to_string(),
+            predicate: Arc::new(predicate),
+            processor,
+        });
+        self
+    }
+
+    pub fn otherwise(mut self, processor: Arc<dyn Processor>) -> Self {
+        self.otherwise = Some(processor);
+        self
+    }
+}
+
+impl Default for ChoiceProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Processor for ChoiceProcessor {
+    async fn process(&self, exchange: Exchange) -> Result<Exchange, DomainError> {
+        let started = chrono::Utc::now();
+        let branch = self
+            .whens
+            .iter()
+            .find(|clause| (clause.predicate)(&exchange))
+            .map(|clause| (clause.label.as_str(), &clause.processor))
+            .or_else(|| self.otherwise.as_ref().map(|processor| ("otherwise", processor)));
+
+        let (label, mut exchange) = match branch {
+            Some((label, processor)) => (label.to_string(), processor.process(exchange).await?),
+            None => ("none".to_string(), exchange),
+        };
+
+        let duration_ms = (chrono::Utc::now() - started).num_milliseconds();
+        exchange.add_processing_step("choice", duration_ms, true, Some(format!("branch={}", label)));
+        Ok(exchange)
+    }
+}
@@ -27,6 +27,12 @@ impl EnricherProcessor {
     }
 }
 
+impl Default for EnricherProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[async_trait]
 impl Processor for EnricherProcessor {
     async fn process(&self, mut exchange: Exchange) -> Result<Exchange, DomainError> {
@@ -36,7 +42,7 @@ impl Processor for EnricherProcessor {
         }
 
         // Add processing metadata
-        exchange.set_header("processed_at", chrono::Utc::now().to_rfc3339().as_str());
+        exchange.set_header("processed_at", chrono::Utc::now());
         
         // Add Processing Step
         exchange.add_processing_step("enricher", 0, true, None);
@@ -27,15 +27,22 @@ impl FilterProcessor {
     }
 }
 
+impl Default for FilterProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[async_trait]
 impl Processor for FilterProcessor {
     async fn process(&self, exchange: Exchange) -> Result<Exchange, DomainError> {
         if (self.predicate)(&exchange) {
             Ok(exchange)
         } else {
-            Err(DomainError::ProcessorError(
-                "Message filtered out".to_string(),
-            ))
+            Err(DomainError::filtered(format!(
+                "Message {} filtered out",
+                exchange.id
+            )))
         }
     }
 }
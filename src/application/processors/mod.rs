@@ -2,4 +2,8 @@
 pub mod logging;
 pub mod enricher;
 pub mod transform;
-pub mod filter;
\ No newline at end of file
+pub mod filter;
+pub mod choice;
+pub mod splitter;
+pub mod aggregator;
+pub mod send;
@@ -0,0 +1,74 @@
+use async_trait::async_trait;
+use std::sync::{Arc, RwLock};
+use crate::domain::{
+    models::{exchange::Exchange, error::DomainError},
+    ports::{endpoint::Producer, processor::Processor},
+};
+
+pub struct SendProcessor {
+    uri: String,
+    producer: RwLock<Option<Arc<dyn Producer>>>,
+}
+
+impl SendProcessor {
+    pub fn new(uri: &str) -> Self {
+        Self {
+            uri: uri.to_string(),
+            producer: RwLock::new(None),
+        }
+    }
+
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// Attaches the producer resolved for this uri; called when the owning route starts.
+    pub fn bind(&self, producer: Arc<dyn Producer>) -> Result<(), DomainError> {
+        let mut slot = self
+            .producer
+            .write()
+            .map_err(|e| DomainError::fatal(format!("Failed to acquire lock: {}", e)))?;
+        *slot = Some(producer);
+        Ok(())
+    }
+
+    pub fn unbind(&self) -> Result<(), DomainError> {
+        let mut slot = self
+            .producer
+            .write()
+            .map_err(|e| DomainError::fatal(format!("Failed to acquire lock: {}", e)))?;
+        *slot = None;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Processor for SendProcessor {
+    async fn process(&self, mut exchange: Exchange) -> Result<Exchange, DomainError> {
+        let producer = self
+            .producer
+            .read()
+            .map_err(|e| DomainError::fatal(format!("Failed to acquire lock: {}", e)))?
+            .clone();
+
+        // Unbound routes (not registered with a context) only record the destination
+        let started = chrono::Utc::now();
+        if let Some(producer) = producer {
+            // Only InOut senders wait for a reply; whatever an InOnly producer answers is dropped
+            let out = exchange.out.clone();
+            exchange = producer.send(exchange).await?;
+            if !exchange.is_in_out() {
+                exchange.out = out;
+            }
+        }
+
+        exchange.set_property("to_endpoint", &self.uri);
+        let duration_ms = (chrono::Utc::now() - started).num_milliseconds();
+        exchange.add_processing_step(&format!("to:{}", self.uri), duration_ms, true, None);
+        Ok(exchange)
+    }
+
+    fn name(&self) -> &str {
+        &self.uri
+    }
+}
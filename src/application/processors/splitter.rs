@@ -0,0 +1,289 @@
+use crate::domain::{
+    models::{body::Body, error::DomainError, exchange::Exchange},
+    ports::{aggregation::AggregationStrategy, processor::Processor},
+};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use uuid::Uuid;
+
+pub const SPLIT_INDEX: &str = "split_index";
+pub const SPLIT_SIZE: &str = "split_size";
+pub const SPLIT_COMPLETE: &str = "split_complete";
+
+type SplitFn = Arc<dyn Fn(&str) -> Result<Vec<String>, DomainError> + Send + Sync>;
+
+/// How an exchange body is broken into parts.
+#[derive(Clone)]
+pub enum SplitExpression {
+    /// One part per non-empty line (newline-delimited records).
+    Lines,
+    /// One part per delimited token.
+    Delimiter(String),
+    /// One part per element of a top-level JSON array, each with a JSON body.
+    JsonArray,
+    /// One part per CSV record, so a quoted field may span lines; a header record is
+    /// skipped when `skip_header` is set.
+    Csv { skip_header: bool },
+    Custom(SplitFn),
+}
+
+impl SplitExpression {
+    /// Lazily yields the parts of `body`. Only JSON arrays, custom expressions and
+    /// bodies that are not already text need to materialize the whole body up front.
+    fn split<'a>(
+        &'a self,
+        body: &'a Body,
+    ) -> Result<Box<dyn Iterator<Item = Body> + Send + 'a>, DomainError> {
+        Ok(match (self, body) {
+            (SplitExpression::JsonArray, _) => match body.json()?.into_owned() {
+                Value::Array(items) => Box::new(items.into_iter().map(Body::Json)),
+                _ => return Err(DomainError::invalid_field("body", "not a JSON array")),
+            },
+            (SplitExpression::Custom(split_fn), _) => {
+                Box::new(split_fn(&body.text()?)?.into_iter().map(Body::Text))
+            }
+            (_, Body::Text(text)) => self.split_text(text),
+            _ => {
+                let parts: Vec<Body> = self.split_text(&body.text()?).collect();
+                Box::new(parts.into_iter())
+            }
+        })
+    }
+
+    fn split_text<'a>(&'a self, text: &'a str) -> Box<dyn Iterator<Item = Body> + Send + 'a> {
+        let parts: Box<dyn Iterator<Item = &'a str> + Send + 'a> = match self {
+            SplitExpression::Delimiter(delimiter) => {
+                Box::new(text.split(delimiter.as_str()).filter(|part| !part.is_empty()))
+            }
+            SplitExpression::Csv { skip_header } => Box::new(
+                CsvRecords { rest: text }
+                    .filter(|record| !record.trim().is_empty())
+                    .skip(usize::from(*skip_header)),
+            ),
+            _ => Box::new(text.lines().filter(|line| !line.trim().is_empty())),
+        };
+        Box::new(parts.map(Body::from))
+    }
+}
+
+/// The records of a CSV text, each as written. Line breaks inside quoted fields
+/// belong to their record; an escaped quote (`""`) toggles quoting twice.
+struct CsvRecords<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for CsvRecords<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        let mut quoted = false;
+        let end = self.rest.bytes().position(|byte| {
+            if byte == b'"' {
+                quoted = !quoted;
+            }
+            byte == b'\n' && !quoted
+        });
+        let (record, rest) = match end {
+            Some(end) => (&self.rest[..end], &self.rest[end + 1..]),
+            None => (self.rest, ""),
+        };
+        self.rest = rest;
+        Some(record.strip_suffix('\r').unwrap_or(record))
+    }
+}
+
+/// Splitter EIP: runs every part of the body through a sub-pipeline and, with an
+/// aggregation strategy, folds the results back into the original exchange.
+pub struct SplitterProcessor {
+    expression: SplitExpression,
+    processor: Arc<dyn Processor>,
+    aggregation: Option<Arc<dyn AggregationStrategy>>,
+    parallelism: usize,
+    streaming: bool,
+    stop_on_error: bool,
+}
+
+impl SplitterProcessor {
+    pub fn new(expression: SplitExpression, processor: Arc<dyn Processor>) -> Self {
+        Self {
+            expression,
+            processor,
+            aggregation: None,
+            parallelism: 1,
+            streaming: false,
+            stop_on_error: true,
+        }
+    }
+
+    pub fn aggregate(mut self, strategy: Arc<dyn AggregationStrategy>) -> Self {
+        self.aggregation = Some(strategy);
+        self
+    }
+
+    /// Processes up to `max_concurrency` parts at once; results are still aggregated in order.
+    pub fn parallel(mut self, max_concurrency: usize) -> Self {
+        self.parallelism = max_concurrency.max(1);
+        self
+    }
+
+    /// Processes parts as they are split instead of splitting the whole body first.
+    /// The total size is then only known, and set as a header, on the last part.
+    pub fn streaming(mut self, streaming: bool) -> Self {
+        self.streaming = streaming;
+        self
+    }
+
+    /// When disabled, failed parts are skipped and counted instead of failing the exchange.
+    pub fn stop_on_error(mut self, stop_on_error: bool) -> Self {
+        self.stop_on_error = stop_on_error;
+        self
+    }
+
+    fn part(parent: &Exchange, body: Body, index: usize) -> Exchange {
+        let mut part = parent.clone();
+        part.id = Uuid::new_v4();
+        part.version = 0;
+        part.body = body;
+        part.processing_history.clear();
+        part.set_property("split_parent_id", parent.id.to_string());
+        part.set_header(SPLIT_INDEX, index);
+        part
+    }
+
+    fn mark_size(part: &mut Exchange, size: usize, complete: bool) {
+        part.set_header(SPLIT_SIZE, size);
+        part.set_header(SPLIT_COMPLETE, complete);
+    }
+
+    async fn process_sequential(&self, exchange: &Exchange) -> Result<SplitOutcome, DomainError> {
+        let mut outcome = SplitOutcome::default();
+        let mut parts = self.expression.split(&exchange.body)?.peekable();
+
+        if !self.streaming {
+            let bodies: Vec<Body> = parts.collect();
+            let size = bodies.len();
+            for (index, body) in bodies.into_iter().enumerate() {
+                let mut part = Self::part(exchange, body, index);
+                Self::mark_size(&mut part, size, index + 1 == size);
+                self.handle(self.processor.process(part).await, &mut outcome)?;
+            }
+            return Ok(outcome);
+        }
+
+        let mut index = 0;
+        while let Some(body) = parts.next() {
+            let mut part = Self::part(exchange, body, index);
+            let last = parts.peek().is_none();
+            if last {
+                Self::mark_size(&mut part, index + 1, true);
+            } else {
+                part.set_header(SPLIT_COMPLETE, false);
+            }
+            self.handle(self.processor.process(part).await, &mut outcome)?;
+            index += 1;
+        }
+        Ok(outcome)
+    }
+
+    async fn process_parallel(&self, exchange: &Exchange) -> Result<SplitOutcome, DomainError> {
+        let bodies: Vec<Body> = self.expression.split(&exchange.body)?.collect();
+        let size = bodies.len();
+        let semaphore = Arc::new(Semaphore::new(self.parallelism));
+        let mut tasks = JoinSet::new();
+
+        for (index, body) in bodies.into_iter().enumerate() {
+            let mut part = Self::part(exchange, body, index);
+            Self::mark_size(&mut part, size, index + 1 == size);
+            let processor = self.processor.clone();
+            let semaphore = semaphore.clone();
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                (index, processor.process(part).await)
+            });
+        }
+
+        let mut results = Vec::with_capacity(size);
+        while let Some(joined) = tasks.join_next().await {
+            let (index, result) = match joined {
+                Ok(joined) => joined,
+                // Parts aborted after an earlier failure; that failure is reported instead
+                Err(e) if e.is_cancelled() => continue,
+                Err(e) => {
+                    return Err(DomainError::fatal("Split part task failed").with_source(e))
+                }
+            };
+            if self.stop_on_error && result.is_err() {
+                tasks.abort_all();
+            }
+            results.push((index, result));
+        }
+        results.sort_by_key(|(index, _)| *index);
+
+        let mut outcome = SplitOutcome::default();
+        for (_, result) in results {
+            self.handle(result, &mut outcome)?;
+        }
+        Ok(outcome)
+    }
+
+    fn handle(
+        &self,
+        result: Result<Exchange, DomainError>,
+        outcome: &mut SplitOutcome,
+    ) -> Result<(), DomainError> {
+        match result {
+            Ok(part) => {
+                outcome.processed += 1;
+                if let Some(strategy) = &self.aggregation {
+                    outcome.aggregated = Some(strategy.aggregate(outcome.aggregated.take(), part)?);
+                }
+                Ok(())
+            }
+            Err(e) if self.stop_on_error => Err(e),
+            Err(_) => {
+                outcome.failed += 1;
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct SplitOutcome {
+    processed: usize,
+    failed: usize,
+    aggregated: Option<Exchange>,
+}
+
+#[async_trait]
+impl Processor for SplitterProcessor {
+    async fn process(&self, mut exchange: Exchange) -> Result<Exchange, DomainError> {
+        let started = chrono::Utc::now();
+        exchange.body.buffer().await?;
+        let outcome = if self.parallelism > 1 {
+            self.process_parallel(&exchange).await?
+        } else {
+            self.process_sequential(&exchange).await?
+        };
+
+        if let Some(aggregated) = outcome.aggregated {
+            exchange.body = aggregated.body;
+        }
+        exchange.set_header(SPLIT_SIZE, outcome.processed + outcome.failed);
+
+        let duration_ms = (chrono::Utc::now() - started).num_milliseconds();
+        exchange.add_processing_step(
+            "splitter",
+            duration_ms,
+            outcome.failed == 0,
+            Some(format!("processed={} failed={}", outcome.processed, outcome.failed)),
+        );
+        Ok(exchange)
+    }
+}
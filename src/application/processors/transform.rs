@@ -1,25 +1,33 @@
 use crate::domain::{
-    models::{error::DomainError, exchange::Exchange},
+    models::{body::Body, error::DomainError, exchange::Exchange},
     ports::processor::Processor,
 };
 use async_trait::async_trait;
 use std::sync::Arc;
 
 pub struct TransformProcessor {
-    transform_fn: Arc<dyn Fn(String) -> Result<String, DomainError> + Send + Sync>,
+    transform_fn: Arc<dyn Fn(Body) -> Result<Body, DomainError> + Send + Sync>,
 }
 
 impl TransformProcessor {
     pub fn new() -> Self {
-        // Default transformer just returns the original string
+        // Default transformer just returns the original body
         Self {
-            transform_fn: Arc::new(|s| Ok(s)),
+            transform_fn: Arc::new(Ok),
         }
     }
 
+    /// Transforms the body as text; bodies that are not valid UTF-8 fail the exchange.
     pub fn with_transformer<F>(transform_fn: F) -> Self
     where
         F: Fn(String) -> Result<String, DomainError> + Send + Sync + 'static,
+    {
+        Self::with_body_transformer(move |body| transform_fn(body.into_text()?).map(Body::from))
+    }
+
+    pub fn with_body_transformer<F>(transform_fn: F) -> Self
+    where
+        F: Fn(Body) -> Result<Body, DomainError> + Send + Sync + 'static,
     {
         Self {
             transform_fn: Arc::new(transform_fn),
@@ -27,17 +35,24 @@ impl TransformProcessor {
     }
 }
 
+impl Default for TransformProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[async_trait]
 impl Processor for TransformProcessor {
     async fn process(&self, mut exchange: Exchange) -> Result<Exchange, DomainError> {
         // Transform the message body
+        exchange.body.buffer().await?;
         let transformed_body = (self.transform_fn)(exchange.body)?;
         exchange.body = transformed_body;
 
         // Add transformation metadata
-        exchange.set_header("transformed", "true");
-        exchange.set_header("transformed_at", chrono::Utc::now().to_rfc3339().as_str());
+        exchange.set_header("transformed", true);
+        exchange.set_header("transformed_at", chrono::Utc::now());
 
         Ok(exchange)
     }
-}
\ No newline at end of file
+}
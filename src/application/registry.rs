@@ -0,0 +1,75 @@
+use crate::domain::{
+    models::{endpoint::EndpointUri, error::DomainError},
+    ports::{component::Component, endpoint::Endpoint},
+};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Resolves endpoint uris through the component registered for their scheme.
+/// Endpoints are cached so every route sharing a uri shares one endpoint.
+pub struct ComponentRegistry {
+    components: RwLock<HashMap<String, Arc<dyn Component>>>,
+    endpoints: RwLock<HashMap<String, Arc<dyn Endpoint>>>,
+}
+
+impl ComponentRegistry {
+    pub fn new() -> Self {
+        Self {
+            components: RwLock::new(HashMap::new()),
+            endpoints: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn register(&self, component: Arc<dyn Component>) -> Result<(), DomainError> {
+        let mut components = self.components.write().map_err(lock_error)?;
+        components.insert(component.scheme().to_lowercase(), component);
+        Ok(())
+    }
+
+    pub fn component(&self, scheme: &str) -> Result<Option<Arc<dyn Component>>, DomainError> {
+        let components = self.components.read().map_err(lock_error)?;
+        Ok(components.get(&scheme.to_lowercase()).cloned())
+    }
+
+    pub fn schemes(&self) -> Result<Vec<String>, DomainError> {
+        let components = self.components.read().map_err(lock_error)?;
+        let mut schemes: Vec<String> = components.keys().cloned().collect();
+        schemes.sort();
+        Ok(schemes)
+    }
+
+    pub fn endpoint(&self, uri: &str) -> Result<Arc<dyn Endpoint>, DomainError> {
+        let parsed = EndpointUri::parse(uri)?;
+        let key = parsed.to_string();
+
+        if let Some(endpoint) = self.endpoints.read().map_err(lock_error)?.get(&key) {
+            return Ok(endpoint.clone());
+        }
+
+        let component = self.component(&parsed.scheme)?.ok_or_else(|| {
+            DomainError::validation(format!(
+                "No component registered for scheme '{}' (uri {})",
+                parsed.scheme, uri
+            ))
+        })?;
+
+        let mut endpoints = self.endpoints.write().map_err(lock_error)?;
+        // Another caller may have created the endpoint while we waited for the lock
+        if let Some(endpoint) = endpoints.get(&key) {
+            return Ok(endpoint.clone());
+        }
+        let endpoint = component.create_endpoint(&parsed)?;
+        endpoints.insert(key, endpoint.clone());
+        Ok(endpoint)
+    }
+}
+
+impl Default for ComponentRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn lock_error<T>(e: std::sync::PoisonError<T>) -> DomainError {
+    DomainError::fatal(format!("Failed to acquire lock: {}", e))
+}
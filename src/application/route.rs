@@ -0,0 +1,174 @@
+use crate::application::error_handler::ErrorHandler;
+use crate::application::pipeline::ProcessorPipeline;
+use crate::application::processors::{
+    aggregator::AggregatorProcessor, choice::ChoiceProcessor, enricher::EnricherProcessor, filter::FilterProcessor, logging::LoggingProcessor,
+    send::SendProcessor, splitter::SplitterProcessor, transform::TransformProcessor,
+};
+use crate::domain::{
+    models::{body::Body, endpoint::EndpointUri, error::DomainError, exchange::Exchange},
+    ports::processor::Processor,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Starts a new route definition consuming from `uri`.
+pub fn from(uri: &str) -> RouteBuilder {
+    RouteBuilder::from(uri)
+}
+
+/// A named, compiled flow: where exchanges come from and the pipeline they run through.
+pub struct Route {
+    id: String,
+    from_uri: String,
+    to_uris: Vec<String>,
+    senders: Vec<Arc<SendProcessor>>,
+    pipeline: Arc<ProcessorPipeline>,
+}
+
+impl Route {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn from_uri(&self) -> &str {
+        &self.from_uri
+    }
+
+    pub fn to_uris(&self) -> &[String] {
+        &self.to_uris
+    }
+
+    /// The `to()` steps, so a context can bind them to producers.
+    pub fn senders(&self) -> &[Arc<SendProcessor>] {
+        &self.senders
+    }
+
+    pub fn pipeline(&self) -> Arc<ProcessorPipeline> {
+        self.pipeline.clone()
+    }
+
+    pub async fn process(&self, exchange: Exchange) -> Result<Exchange, DomainError> {
+        self.pipeline.process(exchange).await
+    }
+}
+
+/// Fluent builder mirroring Camel's Java DSL, e.g.
+/// `from("direct:orders").filter(..).transform(..).to("log:out").build()`.
+pub struct RouteBuilder {
+    route_id: Option<String>,
+    from_uri: String,
+    to_uris: Vec<String>,
+    senders: Vec<Arc<SendProcessor>>,
+    processors: Vec<Arc<dyn Processor>>,
+    error_handler: Option<ErrorHandler>,
+}
+
+impl RouteBuilder {
+    pub fn from(uri: &str) -> Self {
+        Self {
+            route_id: None,
+            from_uri: uri.to_string(),
+            to_uris: Vec::new(),
+            senders: Vec::new(),
+            processors: Vec::new(),
+            error_handler: None,
+        }
+    }
+
+    pub fn route_id(mut self, id: &str) -> Self {
+        self.route_id = Some(id.to_string());
+        self
+    }
+
+    pub fn error_handler(mut self, error_handler: ErrorHandler) -> Self {
+        self.error_handler = Some(error_handler);
+        self
+    }
+
+    pub fn process(mut self, processor: Arc<dyn Processor>) -> Self {
+        self.processors.push(processor);
+        self
+    }
+
+    pub fn log(self, prefix: &str) -> Self {
+        self.process(Arc::new(LoggingProcessor::new(prefix.to_string())))
+    }
+
+    pub fn filter<F>(self, predicate: F) -> Self
+    where
+        F: Fn(&Exchange) -> bool + Send + Sync + 'static,
+    {
+        self.process(Arc::new(FilterProcessor::with_predicate(predicate)))
+    }
+
+    pub fn transform<F>(self, transform_fn: F) -> Self
+    where
+        F: Fn(String) -> Result<String, DomainError> + Send + Sync + 'static,
+    {
+        self.process(Arc::new(TransformProcessor::with_transformer(transform_fn)))
+    }
+
+    pub fn transform_body<F>(self, transform_fn: F) -> Self
+    where
+        F: Fn(Body) -> Result<Body, DomainError> + Send + Sync + 'static,
+    {
+        self.process(Arc::new(TransformProcessor::with_body_transformer(transform_fn)))
+    }
+
+    pub fn choice(self, choice: ChoiceProcessor) -> Self {
+        self.process(Arc::new(choice))
+    }
+
+    pub fn split(self, splitter: SplitterProcessor) -> Self {
+        self.process(Arc::new(splitter))
+    }
+
+    pub fn aggregate(self, aggregator: Arc<AggregatorProcessor>) -> Self {
+        self.process(aggregator)
+    }
+
+    pub fn enrich(self, metadata: HashMap<String, String>) -> Self {
+        self.process(Arc::new(EnricherProcessor::with_metadata(metadata)))
+    }
+
+    pub fn to(mut self, uri: &str) -> Self {
+        let sender = Arc::new(SendProcessor::new(uri));
+        self.to_uris.push(uri.to_string());
+        self.senders.push(sender.clone());
+        self.process(sender)
+    }
+
+    pub fn build(mut self) -> Result<Route, DomainError> {
+        if self.from_uri.trim().is_empty() {
+            return Err(DomainError::validation(
+                "Route must consume from a non-empty uri".to_string(),
+            ));
+        }
+        EndpointUri::parse(&self.from_uri)?;
+        for uri in &self.to_uris {
+            EndpointUri::parse(uri)?;
+        }
+
+        let id = self
+            .route_id
+            .unwrap_or_else(|| format!("route-{}", Uuid::new_v4()));
+        let mut pipeline = ProcessorPipeline::with_processors(self.processors);
+        if let Some(mut error_handler) = self.error_handler {
+            if let Some(sender) = error_handler.dead_letter_sender() {
+                EndpointUri::parse(sender.uri())?;
+                self.senders.push(sender);
+            }
+            error_handler.set_route_id(&id);
+            pipeline = pipeline.with_error_handler(Arc::new(error_handler));
+        }
+
+        Ok(Route {
+            id,
+            from_uri: self.from_uri,
+            to_uris: self.to_uris,
+            senders: self.senders,
+            pipeline: Arc::new(pipeline),
+        })
+    }
+}
@@ -0,0 +1,52 @@
+use crate::application::context::CamelContext;
+use crate::domain::{
+    models::{dead_letter::DeadLetter, error::DomainError, exchange::Exchange},
+    ports::dead_letter::DeadLetterRepository,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct DeadLetterService {
+    repository: Arc<dyn DeadLetterRepository>,
+    context: Arc<CamelContext>,
+}
+
+impl DeadLetterService {
+    pub fn new(repository: Arc<dyn DeadLetterRepository>, context: Arc<CamelContext>) -> Self {
+        Self { repository, context }
+    }
+
+    pub async fn list(&self) -> Result<Vec<DeadLetter>, DomainError> {
+        self.repository.find_all().await
+    }
+
+    pub async fn get(&self, id: &Uuid) -> Result<DeadLetter, DomainError> {
+        self.repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| DomainError::not_found(format!("Dead letter {} not found", id)))
+    }
+
+    /// Sends a dead-lettered exchange back through the route it failed in. The entry
+    /// is removed only when the replay succeeds.
+    pub async fn replay(&self, id: &Uuid) -> Result<Exchange, DomainError> {
+        let dead_letter = self.get(id).await?;
+        let route_id = dead_letter.route_id.ok_or_else(|| {
+            DomainError::validation(format!("Dead letter {} has no route to replay through", id))
+        })?;
+
+        let mut exchange = dead_letter.exchange;
+        exchange.properties.remove("dead_letter_cause");
+        exchange.properties.remove("dead_letter_route");
+        exchange.set_property("replayed_from_dead_letter", true);
+
+        let processed = self.context.process(&route_id, exchange).await?;
+        self.repository.delete(id).await?;
+        Ok(processed)
+    }
+
+    pub async fn discard(&self, id: &Uuid) -> Result<(), DomainError> {
+        self.get(id).await?;
+        self.repository.delete(id).await
+    }
+}
@@ -0,0 +1,235 @@
+use crate::application::services::message_service::MessageService;
+use crate::domain::{
+    models::{
+        error::{DomainError, ErrorKind},
+        exchange::Exchange,
+        ingestion::{Ingestion, IngestionStatus},
+    },
+    ports::dead_letter::DeadLetterRepository,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+#[derive(Clone, Debug)]
+pub struct IngestionConfig {
+    pub workers: usize,
+    /// Messages waiting for a worker before submissions are refused.
+    pub queue_capacity: usize,
+}
+
+impl IngestionConfig {
+    pub fn workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
+    pub fn queue_capacity(mut self, capacity: usize) -> Self {
+        self.queue_capacity = capacity.max(1);
+        self
+    }
+}
+
+impl Default for IngestionConfig {
+    fn default() -> Self {
+        Self {
+            workers: 4,
+            queue_capacity: 1000,
+        }
+    }
+}
+
+/// Accepts messages without waiting for the pipeline: each one is stored as
+/// submitted, queued, and processed later by a pool of workers that record its
+/// status as they go.
+pub struct IngestionService {
+    message_service: Arc<MessageService>,
+    dead_letters: Arc<dyn DeadLetterRepository>,
+    config: IngestionConfig,
+    sender: mpsc::Sender<Uuid>,
+    receiver: Arc<tokio::sync::Mutex<mpsc::Receiver<Uuid>>>,
+    statuses: Mutex<HashMap<Uuid, Ingestion>>,
+}
+
+impl IngestionService {
+    /// Finished statuses kept for polling; the oldest are forgotten first.
+    pub const MAX_FINISHED: usize = 10_000;
+
+    pub fn new(
+        message_service: Arc<MessageService>,
+        dead_letters: Arc<dyn DeadLetterRepository>,
+        config: IngestionConfig,
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel(config.queue_capacity.max(1));
+        Self {
+            message_service,
+            dead_letters,
+            config,
+            sender,
+            receiver: Arc::new(tokio::sync::Mutex::new(receiver)),
+            statuses: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Spawns the worker pool; submissions only queue up until this is called.
+    /// Messages a previous run stored but never processed are queued again.
+    pub fn start(self: &Arc<Self>) -> Vec<JoinHandle<()>> {
+        let recovery = {
+            let service = self.clone();
+            tokio::spawn(async move {
+                if let Err(e) = service.recover().await {
+                    warn!("Failed to queue messages left over from the last run: {}", e);
+                }
+            })
+        };
+        (0..self.config.workers.max(1))
+            .map(|_| {
+                let service = self.clone();
+                tokio::spawn(async move {
+                    loop {
+                        let next = service.receiver.lock().await.recv().await;
+                        let Some(id) = next else {
+                            return;
+                        };
+                        if let Err(e) = service.work(id).await {
+                            warn!("Failed to record ingestion status of {}: {}", id, e);
+                        }
+                    }
+                })
+            })
+            .chain(std::iter::once(recovery))
+            .collect()
+    }
+
+    /// Stores and queues the exchange, failing with a rate limit when the queue
+    /// is full so that nothing is stored that no worker will pick up.
+    pub async fn submit(&self, exchange: Exchange) -> Result<Ingestion, DomainError> {
+        let permit = self.sender.try_reserve().map_err(|e| match e {
+            mpsc::error::TrySendError::Full(()) => {
+                DomainError::rate_limited("Ingestion queue is full", Some(Duration::from_secs(1)))
+            }
+            mpsc::error::TrySendError::Closed(()) => DomainError::fatal("Ingestion queue is closed"),
+        })?;
+
+        let stored = self.message_service.enqueue_message(exchange).await?;
+        let ingestion = Ingestion::queued(stored.id);
+        {
+            let mut statuses = self.lock_statuses()?;
+            // Recovery may have found the stored message first and queued it already
+            if let Some(recovered) = statuses.get(&stored.id) {
+                return Ok(recovered.clone());
+            }
+            prune_finished(&mut statuses);
+            statuses.insert(stored.id, ingestion.clone());
+        }
+        permit.send(stored.id);
+        Ok(ingestion)
+    }
+
+    async fn recover(&self) -> Result<(), DomainError> {
+        let mut recovered = 0;
+        for id in self.message_service.queued_messages().await? {
+            {
+                let mut statuses = self.lock_statuses()?;
+                if statuses.contains_key(&id) {
+                    continue;
+                }
+                statuses.insert(id, Ingestion::queued(id));
+            }
+            self.sender
+                .send(id)
+                .await
+                .map_err(|_| DomainError::fatal("Ingestion queue is closed"))?;
+            recovered += 1;
+        }
+        if recovered > 0 {
+            info!("Queued {} messages left unprocessed by the last run", recovered);
+        }
+        Ok(())
+    }
+
+    /// The status of a message submitted since this service started.
+    pub fn status(&self, message_id: &Uuid) -> Result<Ingestion, DomainError> {
+        self.lock_statuses()?
+            .get(message_id)
+            .cloned()
+            .ok_or_else(|| not_submitted(message_id))
+    }
+
+    /// Like `status`, but rebuilds the status of a message submitted before the last
+    /// restart from its stored revisions, since statuses are only kept in memory.
+    pub async fn stored_status(&self, message_id: &Uuid) -> Result<Ingestion, DomainError> {
+        if let Ok(ingestion) = self.status(message_id) {
+            return Ok(ingestion);
+        }
+        let revisions = match self.message_service.revisions(message_id).await {
+            Ok(revisions) => revisions,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Err(not_submitted(message_id)),
+            Err(e) => return Err(e),
+        };
+        let mut ingestion = Ingestion::from_revisions(&revisions).ok_or_else(|| not_submitted(message_id))?;
+        if ingestion.status == IngestionStatus::Failed && self.dead_letters.find_by_id(message_id).await?.is_some() {
+            ingestion.status = IngestionStatus::DeadLettered;
+        }
+        Ok(ingestion)
+    }
+
+    /// Messages waiting for a worker.
+    pub fn queue_depth(&self) -> usize {
+        self.sender.max_capacity() - self.sender.capacity()
+    }
+
+    async fn work(&self, id: Uuid) -> Result<(), DomainError> {
+        self.update(&id, Ingestion::start)?;
+
+        let (status, error) = match self.message_service.process_queued(&id).await {
+            Ok(_) => (IngestionStatus::Completed, None),
+            // The error handler has already stored the exchange when it gave up on it
+            Err(e) => match self.dead_letters.find_by_id(&id).await {
+                Ok(Some(_)) => (IngestionStatus::DeadLettered, Some(e.to_string())),
+                _ => (IngestionStatus::Failed, Some(e.to_string())),
+            },
+        };
+        match &error {
+            None => info!("Queued message {} processed", id),
+            Some(error) => warn!("Queued message {} {:?}: {}", id, status, error),
+        }
+        self.update(&id, |ingestion| ingestion.finish(status, error))
+    }
+
+    fn update(&self, id: &Uuid, change: impl FnOnce(&mut Ingestion)) -> Result<(), DomainError> {
+        if let Some(ingestion) = self.lock_statuses()?.get_mut(id) {
+            change(ingestion);
+        }
+        Ok(())
+    }
+
+    fn lock_statuses(&self) -> Result<MutexGuard<'_, HashMap<Uuid, Ingestion>>, DomainError> {
+        self.statuses
+            .lock()
+            .map_err(|e| DomainError::fatal(format!("Failed to acquire lock: {}", e)))
+    }
+}
+
+fn not_submitted(message_id: &Uuid) -> DomainError {
+    DomainError::not_found(format!("Message {} was not submitted asynchronously", message_id))
+}
+
+fn prune_finished(statuses: &mut HashMap<Uuid, Ingestion>) {
+    let mut finished: Vec<(chrono::DateTime<chrono::Utc>, Uuid)> = statuses
+        .values()
+        .filter_map(|ingestion| ingestion.finished_at.map(|at| (at, ingestion.message_id)))
+        .collect();
+    if finished.len() < IngestionService::MAX_FINISHED {
+        return;
+    }
+    finished.sort();
+    let surplus = finished.len() + 1 - IngestionService::MAX_FINISHED;
+    for (_, id) in finished.into_iter().take(surplus) {
+        statuses.remove(&id);
+    }
+}
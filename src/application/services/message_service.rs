@@ -1,43 +1,145 @@
-use crate::application::pipeline::ProcessorPipeline;
+use crate::application::context::CamelContext;
 use crate::domain::{
-    models::{error::DomainError, exchange::Exchange},
+    models::{
+        error::DomainError,
+        exchange::Exchange,
+        query::{MessageQuery, Page},
+        revision::{ExchangeRevision, RevisionCause},
+    },
     ports::repository::MessageRepository,
 };
 use std::sync::Arc;
+use tracing::warn;
 
+/// Stores messages and runs them through a route of `context`, so the route's
+/// lifecycle applies: a stopped or suspended route rejects new messages.
 pub struct MessageService {
     repository: Arc<dyn MessageRepository>,
-    pipeline: Arc<ProcessorPipeline>,
+    context: Arc<CamelContext>,
+    route_id: String,
 }
 
 impl MessageService {
-    pub fn new(repository: Arc<dyn MessageRepository>, pipeline: Arc<ProcessorPipeline>) -> Self {
+    pub fn new(repository: Arc<dyn MessageRepository>, context: Arc<CamelContext>, route_id: impl Into<String>) -> Self {
         Self {
             repository,
-            pipeline,
+            context,
+            route_id: route_id.into(),
         }
     }
 
+    /// Stores `exchange` as received, so the chain keeps the original, then processes it.
     pub async fn process_message(&self, exchange: Exchange) -> Result<Exchange, DomainError> {
-        // Process the message through the pipeline
-        let processed_exchange = self.pipeline.process(exchange).await?;
+        let received = self.store(exchange, RevisionCause::new("received")).await?;
+        self.process_and_save(received, RevisionCause::new("create")).await
+    }
+
+    /// Stores `exchange` as submitted, without processing it, for `process_queued` to pick up.
+    pub async fn enqueue_message(&self, exchange: Exchange) -> Result<Exchange, DomainError> {
+        self.store(exchange, RevisionCause::new("queued")).await
+    }
+
+    /// Messages stored by `enqueue_message` and never processed, oldest first; e.g.
+    /// those still queued when the service last shut down. Processing, successful
+    /// or not, always stores a newer revision, so a processed message is never listed.
+    pub async fn queued_messages(&self) -> Result<Vec<uuid::Uuid>, DomainError> {
+        // Later submissions are left out so they cannot shift the pages
+        let query = MessageQuery::new()
+            .trigger("queued")
+            .created_between(None, Some(chrono::Utc::now()));
+        let mut ids = Vec::new();
+        loop {
+            let page = self
+                .repository
+                .query(&query.clone().page(ids.len(), MessageQuery::MAX_LIMIT))
+                .await?;
+            ids.extend(page.items.iter().map(|exchange| exchange.id));
+            if page.items.is_empty() || ids.len() >= page.total {
+                break;
+            }
+        }
+        // Pages are newest first
+        ids.reverse();
+        Ok(ids)
+    }
 
-        // Save the processed message
-        self.repository.save(&processed_exchange).await?;
+    async fn store(&self, mut exchange: Exchange, cause: RevisionCause) -> Result<Exchange, DomainError> {
+        exchange.body.buffer().await?;
+        self.repository.save_revision(&exchange, &cause).await?;
+        exchange.version += 1;
+        Ok(exchange)
+    }
+
+    /// Runs a message stored by `enqueue_message` through the pipeline.
+    pub async fn process_queued(&self, id: &uuid::Uuid) -> Result<Exchange, DomainError> {
+        let exchange = self.get_message(id).await?;
+        self.process_and_save(exchange, RevisionCause::new("create")).await
+    }
+
+    async fn process_and_save(&self, exchange: Exchange, cause: RevisionCause) -> Result<Exchange, DomainError> {
+        // Process the message through the route, keeping the input to record a failure against
+        let input = exchange.clone();
+        let mut processed_exchange = match self.context.process(&self.route_id, exchange).await {
+            Ok(processed) => processed,
+            Err(e) => {
+                record_failure(self.repository.as_ref(), input, &self.route_id, &cause, &e).await;
+                return Err(e);
+            }
+        };
+        // Stores keep the content, so a body still streaming is read in full first
+        processed_exchange.buffer().await?;
+
+        // Save the processed message as a new revision; a concurrent update of the same message conflicts
+        self.repository.save_revision(&processed_exchange, &cause).await?;
+        processed_exchange.version += 1;
 
         Ok(processed_exchange)
     }
 
+    pub async fn get_message(&self, id: &uuid::Uuid) -> Result<Exchange, DomainError> {
+        self.repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| DomainError::not_found(format!("Message {} not found", id)))
+    }
+
+    /// The message's revision chain, oldest first.
+    pub async fn revisions(&self, id: &uuid::Uuid) -> Result<Vec<ExchangeRevision>, DomainError> {
+        let revisions = self.repository.revisions(id).await?;
+        if revisions.is_empty() {
+            return Err(DomainError::not_found(format!("Message {} not found", id)));
+        }
+        Ok(revisions)
+    }
+
+    pub async fn list_messages(&self, query: &MessageQuery) -> Result<Page<Exchange>, DomainError> {
+        self.repository.query(query).await
+    }
+
+    /// Deletes a message, optionally only while it is still at `expected_version`.
+    pub async fn delete_message(
+        &self,
+        id: &uuid::Uuid,
+        expected_version: Option<u64>,
+    ) -> Result<(), DomainError> {
+        let exchange = self.get_message(id).await?;
+        check_expected_version(&exchange, expected_version)?;
+        self.repository.delete(id).await
+    }
+
     pub async fn get_and_process_message(
         &self,
         id: &uuid::Uuid,
         additional_data: Option<String>,
+        expected_version: Option<u64>,
+        triggered_by: Option<String>,
     ) -> Result<Option<Exchange>, DomainError> {
         // Retrieve the message from the repository
         let mut exchange = match self.repository.find_by_id(id).await? {
             Some(exchange) => exchange,
-            None => return Err(DomainError::ProcessorError("Message not found".to_string())),
+            None => return Err(DomainError::not_found(format!("Message {} not found", id))),
         };
+        check_expected_version(&exchange, expected_version)?;
 
         // Add additional data if provided
         if let Some(data) = additional_data {
@@ -45,6 +147,35 @@ impl MessageService {
         }
 
         // Always process through pipeline
-        self.process_message(exchange).await.map(Some)
+        let cause = RevisionCause::new("reprocess").triggered_by(triggered_by);
+        self.process_and_save(exchange, cause).await.map(Some)
+    }
+}
+
+/// Stores `exchange` as it was before the run through `route_id` that failed with
+/// `error`, with the failure added to its history. The "failed" revision keeps the
+/// outcome across restarts: the message is no longer "queued" and `success=false`
+/// finds it. Failing to store it is only logged, since `error` is what the caller reports.
+pub(crate) async fn record_failure(
+    repository: &dyn MessageRepository,
+    mut exchange: Exchange,
+    route_id: &str,
+    cause: &RevisionCause,
+    error: &DomainError,
+) {
+    exchange.add_processing_step(&format!("route:{}", route_id), 0, false, Some(error.to_string()));
+    let failed = RevisionCause::new("failed").triggered_by(cause.triggered_by.clone());
+    if let Err(e) = repository.save_revision(&exchange, &failed).await {
+        warn!("Failed to record the failure of message {}: {}", exchange.id, e);
+    }
+}
+
+fn check_expected_version(exchange: &Exchange, expected_version: Option<u64>) -> Result<(), DomainError> {
+    match expected_version {
+        Some(expected) if expected != exchange.version => Err(DomainError::conflict(format!(
+            "Message {} is at version {}, not {}",
+            exchange.id, exchange.version, expected
+        ))),
+        _ => Ok(()),
     }
 }
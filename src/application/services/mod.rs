@@ -1 +1,5 @@
-pub mod message_service;
\ No newline at end of file
+pub mod dead_letter_service;
+pub mod ingestion_service;
+pub mod message_service;
+pub mod replay_service;
+pub mod retention_service;
\ No newline at end of file
@@ -0,0 +1,202 @@
+use crate::application::{context::CamelContext, services::message_service::record_failure};
+use crate::domain::{
+    models::{
+        error::DomainError,
+        query::MessageQuery,
+        replay::{ReplayJob, ReplayRequest, ReplaySelection, ReplayStatus},
+        revision::RevisionCause,
+    },
+    ports::repository::MessageRepository,
+};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Runs replay jobs: stored exchanges are sent back through a route in the
+/// background and each result is saved as a new "replay" revision.
+pub struct ReplayService {
+    repository: Arc<dyn MessageRepository>,
+    context: Arc<CamelContext>,
+    jobs: Mutex<HashMap<Uuid, ReplayJob>>,
+}
+
+impl ReplayService {
+    /// Finished jobs kept for progress queries; the oldest are forgotten first.
+    pub const MAX_FINISHED_JOBS: usize = 100;
+
+    pub fn new(repository: Arc<dyn MessageRepository>, context: Arc<CamelContext>) -> Self {
+        Self {
+            repository,
+            context,
+            jobs: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Resolves the selection and spawns the job. The returned snapshot already
+    /// knows how many exchanges will be replayed.
+    pub async fn start(self: &Arc<Self>, request: ReplayRequest) -> Result<ReplayJob, DomainError> {
+        if request.rate_limit.is_some_and(|rate| !rate.is_finite() || rate <= 0.0) {
+            return Err(DomainError::invalid_field("rate_limit", "must be a positive number"));
+        }
+        if self.context.route(&request.route_id)?.is_none() {
+            return Err(DomainError::not_found(format!("Route {} not found", request.route_id)));
+        }
+
+        let ids = self.resolve(&request.selection).await?;
+        let job = ReplayJob::new(&request, ids.len());
+        {
+            let mut jobs = self.lock_jobs()?;
+            prune_finished(&mut jobs);
+            jobs.insert(job.id, job.clone());
+        }
+        info!("Replay {} started: {} exchanges through route {}", job.id, ids.len(), request.route_id);
+
+        let service = self.clone();
+        let job_id = job.id;
+        tokio::spawn(async move {
+            if let Err(e) = service.run(job_id, ids, request).await {
+                warn!("Replay {} aborted: {}", job_id, e);
+            }
+        });
+        Ok(job)
+    }
+
+    pub fn job(&self, id: &Uuid) -> Result<ReplayJob, DomainError> {
+        self.lock_jobs()?
+            .get(id)
+            .cloned()
+            .ok_or_else(|| DomainError::not_found(format!("Replay {} not found", id)))
+    }
+
+    /// Every known job, most recently started first.
+    pub fn jobs(&self) -> Result<Vec<ReplayJob>, DomainError> {
+        let mut jobs: Vec<ReplayJob> = self.lock_jobs()?.values().cloned().collect();
+        jobs.sort_by_key(|job| std::cmp::Reverse(job.started_at));
+        Ok(jobs)
+    }
+
+    /// Stops a running job before its next exchange; an exchange already in
+    /// flight still completes and is counted.
+    pub fn cancel(&self, id: &Uuid) -> Result<ReplayJob, DomainError> {
+        let mut jobs = self.lock_jobs()?;
+        let job = jobs
+            .get_mut(id)
+            .ok_or_else(|| DomainError::not_found(format!("Replay {} not found", id)))?;
+        if job.status.is_finished() {
+            return Err(DomainError::conflict(format!(
+                "Replay {} is already {:?}",
+                id, job.status
+            )));
+        }
+        job.finish(ReplayStatus::Cancelled);
+        info!("Replay {} cancelled after {} of {} exchanges", id, job.processed, job.total);
+        Ok(job.clone())
+    }
+
+    async fn resolve(&self, selection: &ReplaySelection) -> Result<Vec<Uuid>, DomainError> {
+        match selection {
+            ReplaySelection::Ids(ids) => {
+                let mut seen = HashSet::new();
+                Ok(ids.iter().copied().filter(|id| seen.insert(*id)).collect())
+            }
+            ReplaySelection::Query(query) => {
+                // Snapshot the matches up front so revisions written by the replay cannot shift pages
+                let mut ids = Vec::new();
+                loop {
+                    let page = self
+                        .repository
+                        .query(&query.clone().page(ids.len(), MessageQuery::MAX_LIMIT))
+                        .await?;
+                    ids.extend(page.items.iter().map(|exchange| exchange.id));
+                    if page.items.is_empty() || ids.len() >= page.total {
+                        return Ok(ids);
+                    }
+                }
+            }
+        }
+    }
+
+    async fn run(&self, job_id: Uuid, ids: Vec<Uuid>, request: ReplayRequest) -> Result<(), DomainError> {
+        let mut ticker = request.rate_limit.map(|rate| {
+            let mut ticker = tokio::time::interval(Duration::from_secs_f64(1.0 / rate));
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            ticker
+        });
+        let cause = RevisionCause::new("replay").triggered_by(request.triggered_by.clone());
+
+        for id in ids {
+            if let Some(ticker) = ticker.as_mut() {
+                ticker.tick().await;
+            }
+            if self.job(&job_id)?.status.is_finished() {
+                return Ok(());
+            }
+
+            let result = self.replay_one(&id, &request.route_id, &cause).await;
+            let mut jobs = self.lock_jobs()?;
+            let Some(job) = jobs.get_mut(&job_id) else {
+                return Ok(());
+            };
+            match result {
+                Ok(()) => job.record_success(),
+                Err(e) => job.record_failure(id, e.to_string()),
+            }
+        }
+
+        let mut jobs = self.lock_jobs()?;
+        if let Some(job) = jobs.get_mut(&job_id).filter(|job| !job.status.is_finished()) {
+            job.finish(ReplayStatus::Completed);
+            info!(
+                "Replay {} completed: {} succeeded, {} failed",
+                job_id, job.succeeded, job.failed
+            );
+        }
+        Ok(())
+    }
+
+    async fn replay_one(&self, id: &Uuid, route_id: &str, cause: &RevisionCause) -> Result<(), DomainError> {
+        let exchange = self
+            .repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| DomainError::not_found(format!("Message {} not found", id)))?;
+        // A failed run is stored too, so the message can be picked up by a later replay
+        let input = exchange.clone();
+        let mut processed = match self.context.process(route_id, exchange).await {
+            Ok(processed) => processed,
+            Err(e) => {
+                record_failure(self.repository.as_ref(), input, route_id, cause, &e).await;
+                return Err(e);
+            }
+        };
+        // Stores keep the content, so a body still streaming is read in full first
+        processed.buffer().await?;
+        self.repository.save_revision(&processed, cause).await?;
+        Ok(())
+    }
+
+    fn lock_jobs(&self) -> Result<MutexGuard<'_, HashMap<Uuid, ReplayJob>>, DomainError> {
+        self.jobs
+            .lock()
+            .map_err(|e| DomainError::fatal(format!("Failed to acquire lock: {}", e)))
+    }
+}
+
+fn prune_finished(jobs: &mut HashMap<Uuid, ReplayJob>) {
+    let mut finished: Vec<(chrono::DateTime<chrono::Utc>, Uuid)> = jobs
+        .values()
+        .filter(|job| job.status.is_finished())
+        .map(|job| (job.started_at, job.id))
+        .collect();
+    if finished.len() < ReplayService::MAX_FINISHED_JOBS {
+        return;
+    }
+    finished.sort();
+    let surplus = finished.len() + 1 - ReplayService::MAX_FINISHED_JOBS;
+    for (_, id) in finished.into_iter().take(surplus) {
+        jobs.remove(&id);
+    }
+}
@@ -0,0 +1,145 @@
+use crate::domain::{
+    models::{error::DomainError, query::MessageQuery, retention::RetentionPolicy},
+    ports::repository::MessageRepository,
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+/// Counters describing what the sweeper has expired since startup.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct RetentionMetrics {
+    pub sweeps: u64,
+    pub failed_sweeps: u64,
+    pub expired_total: u64,
+    pub expired_by_age: u64,
+    pub expired_by_count: u64,
+    pub last_sweep_at: Option<DateTime<Utc>>,
+    pub last_sweep_expired: u64,
+}
+
+/// Exchanges removed by one sweep.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SweepReport {
+    pub expired_by_age: usize,
+    pub expired_by_count: usize,
+}
+
+impl SweepReport {
+    pub fn total(&self) -> usize {
+        self.expired_by_age + self.expired_by_count
+    }
+}
+
+/// Enforces a `RetentionPolicy` through the repository's query and delete
+/// operations, so it works the same for every `MessageRepository`.
+pub struct RetentionService {
+    repository: Arc<dyn MessageRepository>,
+    policy: RetentionPolicy,
+    metrics: Mutex<RetentionMetrics>,
+}
+
+impl RetentionService {
+    pub fn new(repository: Arc<dyn MessageRepository>, policy: RetentionPolicy) -> Self {
+        Self {
+            repository,
+            policy,
+            metrics: Mutex::new(RetentionMetrics::default()),
+        }
+    }
+
+    pub fn policy(&self) -> &RetentionPolicy {
+        &self.policy
+    }
+
+    pub fn metrics(&self) -> Result<RetentionMetrics, DomainError> {
+        self.metrics
+            .lock()
+            .map(|metrics| metrics.clone())
+            .map_err(|e| DomainError::fatal(format!("Failed to acquire lock: {}", e)))
+    }
+
+    /// Spawns the background sweeper; returns `None` when the policy limits nothing.
+    pub fn start(self: &Arc<Self>, interval: Duration) -> Option<JoinHandle<()>> {
+        if self.policy.is_empty() {
+            return None;
+        }
+
+        let service = self.clone();
+        Some(tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = service.sweep().await {
+                    warn!("Retention sweep failed: {}", e);
+                }
+            }
+        }))
+    }
+
+    /// Applies every rule once and records the outcome in the metrics.
+    pub async fn sweep(&self) -> Result<SweepReport, DomainError> {
+        let result = self.apply_rules().await;
+
+        let mut metrics = self
+            .metrics
+            .lock()
+            .map_err(|e| DomainError::fatal(format!("Failed to acquire lock: {}", e)))?;
+        metrics.sweeps += 1;
+        metrics.last_sweep_at = Some(Utc::now());
+        match &result {
+            Ok(report) => {
+                metrics.expired_by_age += report.expired_by_age as u64;
+                metrics.expired_by_count += report.expired_by_count as u64;
+                metrics.expired_total += report.total() as u64;
+                metrics.last_sweep_expired = report.total() as u64;
+                if report.total() > 0 {
+                    info!(
+                        "Retention sweep expired {} exchanges ({} by age, {} by count)",
+                        report.total(),
+                        report.expired_by_age,
+                        report.expired_by_count
+                    );
+                }
+            }
+            Err(_) => metrics.failed_sweeps += 1,
+        }
+        result
+    }
+
+    async fn apply_rules(&self) -> Result<SweepReport, DomainError> {
+        let mut report = SweepReport::default();
+        for rule in &self.policy.rules {
+            if let Some(max_age) = rule.max_age {
+                let max_age = chrono::Duration::from_std(max_age)
+                    .map_err(|e| DomainError::invalid_field("max_age", e.to_string()))?;
+                let query = rule.query().created_between(None, Some(Utc::now() - max_age));
+                report.expired_by_age += self.delete_matching(query.page(0, MessageQuery::MAX_LIMIT)).await?;
+            }
+            if let Some(max_count) = rule.max_count {
+                // Results are newest first, so everything past `max_count` is surplus
+                let query = rule.query().page(max_count, MessageQuery::MAX_LIMIT);
+                report.expired_by_count += self.delete_matching(query).await?;
+            }
+        }
+        Ok(report)
+    }
+
+    /// Deletes the page selected by `query` until it comes back empty.
+    async fn delete_matching(&self, query: MessageQuery) -> Result<usize, DomainError> {
+        let mut deleted = 0;
+        loop {
+            let page = self.repository.query(&query).await?;
+            if page.items.is_empty() {
+                return Ok(deleted);
+            }
+            for exchange in &page.items {
+                self.repository.delete(&exchange.id).await?;
+            }
+            deleted += page.items.len();
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::models::exchange::Exchange;
+
+/// An in-flight aggregation: everything merged so far for one correlation key.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AggregationGroup {
+    pub correlation_key: String,
+    pub exchange: Exchange,
+    pub size: usize,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl AggregationGroup {
+    pub fn new(correlation_key: &str, exchange: Exchange) -> Self {
+        let now = Utc::now();
+        Self {
+            correlation_key: correlation_key.to_string(),
+            exchange,
+            size: 1,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
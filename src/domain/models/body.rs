@@ -0,0 +1,308 @@
+use crate::domain::models::error::DomainError;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use bytes::{Bytes, BytesMut};
+use futures::stream::{BoxStream, Stream, StreamExt};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// Header carrying the MIME type of a body that arrived as raw content.
+pub const CONTENT_TYPE: &str = "content_type";
+
+/// The payload of an exchange. Conversions between the representations happen
+/// on access, so a body is only parsed or re-encoded when a processor asks for it.
+#[derive(Clone, Debug)]
+pub enum Body {
+    Text(String),
+    Bytes(Bytes),
+    Json(Value),
+    /// Content that has not been read yet; see `Body::buffer`.
+    Stream(BodyStream),
+}
+
+/// How a body is represented on the wire and in stores.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BodyEncoding {
+    Text,
+    Base64,
+    Json,
+}
+
+impl BodyEncoding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BodyEncoding::Text => "text",
+            BodyEncoding::Base64 => "base64",
+            BodyEncoding::Json => "json",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "text" => Some(BodyEncoding::Text),
+            "base64" => Some(BodyEncoding::Base64),
+            "json" => Some(BodyEncoding::Json),
+            _ => None,
+        }
+    }
+}
+
+impl Body {
+    pub fn empty() -> Self {
+        Body::Text(String::new())
+    }
+
+    pub fn from_stream<S>(stream: S) -> Self
+    where
+        S: Stream<Item = std::io::Result<Bytes>> + Send + 'static,
+    {
+        Body::Stream(BodyStream(Arc::new(Mutex::new(Some(stream.boxed())))))
+    }
+
+    /// Decodes `content` written with `encoding`, e.g. a base64 string from a JSON request.
+    pub fn decode(content: &str, encoding: BodyEncoding) -> Result<Self, DomainError> {
+        match encoding {
+            BodyEncoding::Text => Ok(Body::Text(content.to_string())),
+            BodyEncoding::Base64 => STANDARD
+                .decode(content)
+                .map(|bytes| Body::Bytes(bytes.into()))
+                .map_err(|_| DomainError::invalid_field("body", "not valid base64")),
+            BodyEncoding::Json => serde_json::from_str(content)
+                .map(Body::Json)
+                .map_err(|e| DomainError::invalid_field("body", "not valid JSON").with_source(e)),
+        }
+    }
+
+    /// The encoding `encode` writes this body with; streams must be buffered first.
+    pub fn encoding(&self) -> Result<BodyEncoding, DomainError> {
+        match self {
+            Body::Text(_) => Ok(BodyEncoding::Text),
+            Body::Bytes(_) => Ok(BodyEncoding::Base64),
+            Body::Json(_) => Ok(BodyEncoding::Json),
+            Body::Stream(_) => Err(unbuffered()),
+        }
+    }
+
+    /// The body as a string in its `encoding`; the inverse of `decode`.
+    pub fn encode(&self) -> Result<String, DomainError> {
+        match self {
+            Body::Text(text) => Ok(text.clone()),
+            Body::Bytes(bytes) => Ok(STANDARD.encode(bytes)),
+            Body::Json(value) => Ok(value.to_string()),
+            Body::Stream(_) => Err(unbuffered()),
+        }
+    }
+
+    pub fn is_stream(&self) -> bool {
+        matches!(self, Body::Stream(_))
+    }
+
+    /// The body as UTF-8 text; JSON is serialized and bytes must be valid UTF-8.
+    pub fn text(&self) -> Result<Cow<'_, str>, DomainError> {
+        match self {
+            Body::Text(text) => Ok(Cow::Borrowed(text)),
+            Body::Bytes(bytes) => std::str::from_utf8(bytes)
+                .map(Cow::Borrowed)
+                .map_err(|_| DomainError::invalid_field("body", "binary content is not valid UTF-8")),
+            Body::Json(value) => Ok(Cow::Owned(value.to_string())),
+            Body::Stream(_) => Err(unbuffered()),
+        }
+    }
+
+    pub fn bytes(&self) -> Result<Cow<'_, [u8]>, DomainError> {
+        match self {
+            Body::Text(text) => Ok(Cow::Borrowed(text.as_bytes())),
+            Body::Bytes(bytes) => Ok(Cow::Borrowed(bytes)),
+            Body::Json(value) => Ok(Cow::Owned(value.to_string().into_bytes())),
+            Body::Stream(_) => Err(unbuffered()),
+        }
+    }
+
+    /// The body as a JSON value, parsing text and bytes on demand.
+    pub fn json(&self) -> Result<Cow<'_, Value>, DomainError> {
+        let parse = |bytes: &[u8]| {
+            serde_json::from_slice(bytes)
+                .map(Cow::Owned)
+                .map_err(|e| DomainError::invalid_field("body", "not valid JSON").with_source(e))
+        };
+        match self {
+            Body::Text(text) => parse(text.as_bytes()),
+            Body::Bytes(bytes) => parse(bytes),
+            Body::Json(value) => Ok(Cow::Borrowed(value)),
+            Body::Stream(_) => Err(unbuffered()),
+        }
+    }
+
+    pub fn into_text(self) -> Result<String, DomainError> {
+        match self {
+            Body::Text(text) => Ok(text),
+            Body::Bytes(bytes) => String::from_utf8(bytes.into())
+                .map_err(|_| DomainError::invalid_field("body", "binary content is not valid UTF-8")),
+            other => other.text().map(Cow::into_owned),
+        }
+    }
+
+    /// Reads a stream body to the end and keeps the content as bytes; other bodies are left as they are.
+    pub async fn buffer(&mut self) -> Result<(), DomainError> {
+        let Body::Stream(stream) = self else {
+            return Ok(());
+        };
+        let mut stream = stream.take()?;
+        let mut content = BytesMut::new();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| DomainError::transient("Failed to read body stream").with_source(e))?;
+            content.extend_from_slice(&chunk);
+        }
+        *self = Body::Bytes(content.freeze());
+        Ok(())
+    }
+}
+
+fn unbuffered() -> DomainError {
+    DomainError::validation("Stream body must be buffered before it is read")
+}
+
+impl Default for Body {
+    fn default() -> Self {
+        Body::empty()
+    }
+}
+
+impl fmt::Display for Body {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Body::Text(text) => f.write_str(text),
+            Body::Bytes(bytes) => match std::str::from_utf8(bytes) {
+                Ok(text) => f.write_str(text),
+                Err(_) => write!(f, "<{} bytes>", bytes.len()),
+            },
+            Body::Json(value) => write!(f, "{}", value),
+            Body::Stream(_) => f.write_str("<stream>"),
+        }
+    }
+}
+
+impl PartialEq for Body {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Body::Text(a), Body::Text(b)) => a == b,
+            (Body::Bytes(a), Body::Bytes(b)) => a == b,
+            (Body::Json(a), Body::Json(b)) => a == b,
+            (Body::Stream(a), Body::Stream(b)) => Arc::ptr_eq(&a.0, &b.0),
+            _ => false,
+        }
+    }
+}
+
+/// Compares the body's text form, so `Body::Json` equals its serialization.
+impl PartialEq<str> for Body {
+    fn eq(&self, other: &str) -> bool {
+        self.text().is_ok_and(|text| text == other)
+    }
+}
+
+impl PartialEq<&str> for Body {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl PartialEq<String> for Body {
+    fn eq(&self, other: &String) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl From<String> for Body {
+    fn from(text: String) -> Self {
+        Body::Text(text)
+    }
+}
+
+impl From<&str> for Body {
+    fn from(text: &str) -> Self {
+        Body::Text(text.to_string())
+    }
+}
+
+impl From<Vec<u8>> for Body {
+    fn from(bytes: Vec<u8>) -> Self {
+        Body::Bytes(bytes.into())
+    }
+}
+
+impl From<Bytes> for Body {
+    fn from(bytes: Bytes) -> Self {
+        Body::Bytes(bytes)
+    }
+}
+
+impl From<Value> for Body {
+    fn from(value: Value) -> Self {
+        Body::Json(value)
+    }
+}
+
+/// Text bodies serialize as a plain string, which keeps exchanges stored before
+/// bodies were typed readable; other bodies are tagged with their encoding.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum BodyRepr {
+    Text(String),
+    Tagged(TaggedBody),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum TaggedBody {
+    Base64(String),
+    Json(Value),
+}
+
+impl Serialize for Body {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let repr = match self {
+            Body::Text(text) => BodyRepr::Text(text.clone()),
+            Body::Bytes(bytes) => BodyRepr::Tagged(TaggedBody::Base64(STANDARD.encode(bytes))),
+            Body::Json(value) => BodyRepr::Tagged(TaggedBody::Json(value.clone())),
+            Body::Stream(_) => return Err(serde::ser::Error::custom(unbuffered())),
+        };
+        repr.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Body {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match BodyRepr::deserialize(deserializer)? {
+            BodyRepr::Text(text) => Body::Text(text),
+            BodyRepr::Tagged(TaggedBody::Base64(content)) => {
+                Body::Bytes(STANDARD.decode(content).map_err(de::Error::custom)?.into())
+            }
+            BodyRepr::Tagged(TaggedBody::Json(value)) => Body::Json(value),
+        })
+    }
+}
+
+/// A body that is read at most once. Clones share the stream, so whichever copy
+/// buffers it first gets the content.
+#[derive(Clone)]
+pub struct BodyStream(Arc<Mutex<Option<BoxStream<'static, std::io::Result<Bytes>>>>>);
+
+impl BodyStream {
+    fn take(&self) -> Result<BoxStream<'static, std::io::Result<Bytes>>, DomainError> {
+        self.0
+            .lock()
+            .map_err(|e| DomainError::fatal(format!("Failed to acquire lock: {}", e)))?
+            .take()
+            .ok_or_else(|| DomainError::validation("Stream body has already been consumed"))
+    }
+}
+
+impl fmt::Debug for BodyStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BodyStream")
+    }
+}
@@ -0,0 +1,336 @@
+use crate::domain::models::{body::Body, error::DomainError, header::HeaderValue};
+use crate::domain::ports::type_converter::TypeConverter;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::any::{type_name, Any, TypeId};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock, RwLock};
+use uuid::Uuid;
+
+/// Exchange details a conversion may depend on.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConversionContext {
+    /// Charset for bytes <-> string conversions; UTF-8 when unset.
+    pub charset: Option<String>,
+}
+
+impl ConversionContext {
+    pub fn charset(mut self, charset: &str) -> Self {
+        self.charset = Some(charset.to_string());
+        self
+    }
+}
+
+/// Converters keyed by source and target type. When no converter links the two
+/// directly, a conversion may pass through `String`, `Value` or `Bytes`, so e.g.
+/// bytes become a typed struct by way of JSON.
+pub struct TypeConverterRegistry {
+    converters: RwLock<HashMap<(TypeId, TypeId), Arc<dyn TypeConverter>>>,
+}
+
+impl TypeConverterRegistry {
+    /// A registry without any converters.
+    pub fn empty() -> Self {
+        Self {
+            converters: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// A registry with the built-in converters between bytes, strings, JSON and
+    /// the scalar types headers are read as.
+    pub fn new() -> Self {
+        Self {
+            converters: RwLock::new(default_converters().into_iter().collect()),
+        }
+    }
+
+    /// The registry `Exchange::body_as` and `Exchange::header_as` use. Register
+    /// application converters here at startup.
+    pub fn global() -> &'static TypeConverterRegistry {
+        static GLOBAL: OnceLock<TypeConverterRegistry> = OnceLock::new();
+        GLOBAL.get_or_init(TypeConverterRegistry::new)
+    }
+
+    /// Registers `convert` for `S -> T`, replacing any previous converter.
+    pub fn register<S, T, F>(&self, convert: F) -> Result<(), DomainError>
+    where
+        S: 'static,
+        T: 'static,
+        F: Fn(&S, &ConversionContext) -> Result<T, String> + Send + Sync + 'static,
+    {
+        let ((source, target), converter) = converter(convert);
+        self.register_converter(source, target, converter)
+    }
+
+    pub fn register_converter(
+        &self,
+        source: TypeId,
+        target: TypeId,
+        converter: Arc<dyn TypeConverter>,
+    ) -> Result<(), DomainError> {
+        self.converters
+            .write()
+            .map_err(|e| DomainError::fatal(format!("Failed to acquire lock: {}", e)))?
+            .insert((source, target), converter);
+        Ok(())
+    }
+
+    /// Registers serde conversions between JSON values and `T`.
+    pub fn register_json<T>(&self) -> Result<(), DomainError>
+    where
+        T: Serialize + DeserializeOwned + 'static,
+    {
+        self.register::<Value, T, _>(|value, _| {
+            serde_json::from_value(value.clone()).map_err(|e| e.to_string())
+        })?;
+        self.register::<T, Value, _>(|value, _| serde_json::to_value(value).map_err(|e| e.to_string()))
+    }
+
+    pub fn has_converter(&self, source: TypeId, target: TypeId) -> bool {
+        self.converters
+            .read()
+            .is_ok_and(|converters| converters.contains_key(&(source, target)))
+    }
+
+    /// Converts `value` to `T`; `subject` names what is converted in errors, e.g. "body".
+    pub fn convert<S: 'static, T: 'static>(
+        &self,
+        subject: &str,
+        value: &S,
+        context: &ConversionContext,
+    ) -> Result<T, DomainError> {
+        let error = |reason: &dyn Display| {
+            DomainError::invalid_field(
+                subject,
+                format!(
+                    "cannot convert {} to {}: {}",
+                    short_type_name(type_name::<S>()),
+                    short_type_name(type_name::<T>()),
+                    reason
+                ),
+            )
+        };
+
+        let (source, target) = (TypeId::of::<S>(), TypeId::of::<T>());
+        let route = {
+            let converters = self
+                .converters
+                .read()
+                .map_err(|e| DomainError::fatal(format!("Failed to acquire lock: {}", e)))?;
+            match converters.get(&(source, target)) {
+                Some(direct) => vec![direct.clone()],
+                None => [TypeId::of::<String>(), TypeId::of::<Value>(), TypeId::of::<Bytes>()]
+                    .iter()
+                    .find_map(|via| {
+                        Some(vec![
+                            converters.get(&(source, *via))?.clone(),
+                            converters.get(&(*via, target))?.clone(),
+                        ])
+                    })
+                    .ok_or_else(|| error(&"no type converter registered"))?,
+            }
+        };
+
+        let mut converted: Box<dyn Any> = Box::new(());
+        for (step, converter) in route.iter().enumerate() {
+            let input: &dyn Any = if step == 0 { value } else { converted.as_ref() };
+            converted = converter.convert(input, context).map_err(|reason| error(&reason))?;
+        }
+        converted
+            .downcast::<T>()
+            .map(|value| *value)
+            .map_err(|_| error(&"converter returned a different type"))
+    }
+
+    /// Converts a body from whichever representation it holds.
+    pub fn convert_body<T: 'static>(&self, body: &Body, context: &ConversionContext) -> Result<T, DomainError> {
+        match body {
+            Body::Text(text) => self.convert("body", text, context),
+            Body::Bytes(bytes) => self.convert("body", bytes, context),
+            Body::Json(value) => self.convert("body", value, context),
+            Body::Stream(_) => Err(DomainError::invalid_field(
+                "body",
+                "a stream must be buffered before it is converted",
+            )),
+        }
+    }
+
+    /// Converts a header or property value from whichever type it holds.
+    pub fn convert_header<T: 'static>(
+        &self,
+        name: &str,
+        value: &HeaderValue,
+        context: &ConversionContext,
+    ) -> Result<T, DomainError> {
+        match value {
+            HeaderValue::String(value) => self.convert(name, value, context),
+            HeaderValue::Integer(value) => self.convert(name, value, context),
+            HeaderValue::Float(value) => self.convert(name, value, context),
+            HeaderValue::Bool(value) => self.convert(name, value, context),
+            HeaderValue::Timestamp(value) => self.convert(name, value, context),
+            HeaderValue::Bytes(value) => self.convert(name, value, context),
+            HeaderValue::List(items) => self.convert(name, items, context),
+        }
+    }
+}
+
+impl Default for TypeConverterRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+type ConverterEntry = ((TypeId, TypeId), Arc<dyn TypeConverter>);
+
+fn converter<S, T, F>(convert: F) -> ConverterEntry
+where
+    S: 'static,
+    T: 'static,
+    F: Fn(&S, &ConversionContext) -> Result<T, String> + Send + Sync + 'static,
+{
+    (
+        (TypeId::of::<S>(), TypeId::of::<T>()),
+        Arc::new(FnConverter {
+            convert,
+            types: PhantomData,
+        }),
+    )
+}
+
+fn default_converters() -> Vec<ConverterEntry> {
+    vec![
+        converter::<String, String, _>(|text, _| Ok(text.clone())),
+        converter::<Bytes, Bytes, _>(|bytes, _| Ok(bytes.clone())),
+        converter::<Value, Value, _>(|value, _| Ok(value.clone())),
+        converter::<i64, i64, _>(|value, _| Ok(*value)),
+        converter::<f64, f64, _>(|value, _| Ok(*value)),
+        converter::<bool, bool, _>(|value, _| Ok(*value)),
+        converter::<DateTime<Utc>, DateTime<Utc>, _>(|value, _| Ok(*value)),
+        converter::<Vec<HeaderValue>, Vec<HeaderValue>, _>(|items, _| Ok(items.clone())),
+        converter::<i64, f64, _>(|value, _| Ok(*value as f64)),
+        converter::<Vec<HeaderValue>, String, _>(|items, _| Ok(HeaderValue::List(items.clone()).to_string())),
+        converter::<Bytes, String, _>(|bytes, context| decode(bytes, context)),
+        converter::<String, Bytes, _>(|text, context| encode(text, context)),
+        converter::<Bytes, Vec<u8>, _>(|bytes, _| Ok(bytes.to_vec())),
+        converter::<String, Vec<u8>, _>(|text, context| encode(text, context).map(Vec::from)),
+        converter::<String, Value, _>(|text, _| serde_json::from_str(text).map_err(|e| e.to_string())),
+        converter::<Bytes, Value, _>(|bytes, _| serde_json::from_slice(bytes).map_err(|e| e.to_string())),
+        converter::<Value, String, _>(|value, _| Ok(value.to_string())),
+        converter::<Value, Bytes, _>(|value, _| Ok(value.to_string().into())),
+        converter::<Value, i64, _>(|value, _| value.as_i64().ok_or_else(|| not_a("integer", value))),
+        converter::<Value, u64, _>(|value, _| value.as_u64().ok_or_else(|| not_a("unsigned integer", value))),
+        converter::<Value, f64, _>(|value, _| value.as_f64().ok_or_else(|| not_a("number", value))),
+        converter::<Value, bool, _>(|value, _| value.as_bool().ok_or_else(|| not_a("boolean", value))),
+        converter::<String, i64, _>(|text, _| parse(text)),
+        converter::<String, i32, _>(|text, _| parse(text)),
+        converter::<String, u64, _>(|text, _| parse(text)),
+        converter::<String, u32, _>(|text, _| parse(text)),
+        converter::<String, f64, _>(|text, _| parse(text)),
+        converter::<String, Uuid, _>(|text, _| parse(text)),
+        converter::<String, DateTime<Utc>, _>(|text, _| parse(text)),
+        converter::<String, bool, _>(|text, _| match text.trim().to_ascii_lowercase().as_str() {
+            "true" => Ok(true),
+            "false" => Ok(false),
+            _ => Err(format!("'{}' is not true or false", text)),
+        }),
+        converter::<i64, String, _>(|value, _| Ok(value.to_string())),
+        converter::<u64, String, _>(|value, _| Ok(value.to_string())),
+        converter::<f64, String, _>(|value, _| Ok(value.to_string())),
+        converter::<bool, String, _>(|value, _| Ok(value.to_string())),
+        converter::<DateTime<Utc>, String, _>(|value, _| Ok(value.to_rfc3339())),
+    ]
+}
+
+struct FnConverter<S, T, F> {
+    convert: F,
+    types: PhantomData<fn(&S) -> T>,
+}
+
+impl<S, T, F> TypeConverter for FnConverter<S, T, F>
+where
+    S: 'static,
+    T: 'static,
+    F: Fn(&S, &ConversionContext) -> Result<T, String> + Send + Sync,
+{
+    fn convert(&self, value: &dyn Any, context: &ConversionContext) -> Result<Box<dyn Any>, String> {
+        let value = value
+            .downcast_ref::<S>()
+            .ok_or_else(|| format!("expected a {}", short_type_name(type_name::<S>())))?;
+        (self.convert)(value, context).map(|converted| Box::new(converted) as Box<dyn Any>)
+    }
+}
+
+fn parse<T>(text: &str) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    text.trim()
+        .parse()
+        .map_err(|e| format!("'{}' is not valid: {}", text, e))
+}
+
+fn not_a(expected: &str, value: &Value) -> String {
+    format!("{} is not a JSON {}", value, expected)
+}
+
+fn charset(context: &ConversionContext) -> String {
+    context
+        .charset
+        .as_deref()
+        .unwrap_or("utf-8")
+        .to_ascii_lowercase()
+}
+
+fn decode(bytes: &[u8], context: &ConversionContext) -> Result<String, String> {
+    match charset(context).as_str() {
+        "utf-8" | "utf8" => String::from_utf8(bytes.to_vec()).map_err(|e| format!("invalid UTF-8: {}", e)),
+        "us-ascii" | "ascii" => match bytes.iter().position(|byte| !byte.is_ascii()) {
+            Some(index) => Err(format!("byte {} is not ASCII", index)),
+            None => Ok(bytes.iter().map(|&byte| byte as char).collect()),
+        },
+        "iso-8859-1" | "latin1" => Ok(bytes.iter().map(|&byte| byte as char).collect()),
+        other => Err(format!("unsupported charset '{}'", other)),
+    }
+}
+
+fn encode(text: &str, context: &ConversionContext) -> Result<Bytes, String> {
+    let single_byte = |limit: u32| {
+        text.chars()
+            .map(|c| u8::try_from(c as u32).ok().filter(|_| (c as u32) < limit))
+            .collect::<Option<Vec<u8>>>()
+            .map(Bytes::from)
+            .ok_or_else(|| format!("text cannot be encoded as {}", charset(context)))
+    };
+    match charset(context).as_str() {
+        "utf-8" | "utf8" => Ok(Bytes::copy_from_slice(text.as_bytes())),
+        "us-ascii" | "ascii" => single_byte(0x80),
+        "iso-8859-1" | "latin1" => single_byte(0x100),
+        other => Err(format!("unsupported charset '{}'", other)),
+    }
+}
+
+/// `alloc::string::String` -> `String`, keeping generic arguments readable.
+fn short_type_name(name: &str) -> String {
+    let mut short = String::with_capacity(name.len());
+    let mut segment_start = 0;
+    for (index, c) in name.char_indices() {
+        if c.is_alphanumeric() || c == '_' {
+            continue;
+        }
+        if c == ':' {
+            segment_start = index + 1;
+            continue;
+        }
+        short.push_str(&name[segment_start..index]);
+        short.push(c);
+        segment_start = index + 1;
+    }
+    short.push_str(&name[segment_start..]);
+    short
+}
@@ -0,0 +1,13 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::models::exchange::Exchange;
+
+/// An exchange that exhausted its redeliveries, with the failure that sent it here.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DeadLetter {
+    pub exchange: Exchange,
+    pub route_id: Option<String>,
+    pub cause: String,
+    pub attempts: u32,
+    pub failed_at: DateTime<Utc>,
+}
@@ -0,0 +1,117 @@
+use crate::domain::models::error::DomainError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// A parsed endpoint uri of the form `scheme:path?key=value&key2=value2`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EndpointUri {
+    pub scheme: String,
+    pub path: String,
+    pub options: HashMap<String, String>,
+}
+
+impl EndpointUri {
+    pub fn parse(uri: &str) -> Result<Self, DomainError> {
+        let uri = uri.trim();
+        let (scheme, rest) = uri.split_once(':').ok_or_else(|| {
+            DomainError::validation(format!("Endpoint uri '{}' has no scheme", uri))
+        })?;
+
+        if scheme.is_empty()
+            || !scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '+' || c == '.')
+        {
+            return Err(DomainError::validation(format!(
+                "Endpoint uri '{}' has an invalid scheme",
+                uri
+            )));
+        }
+
+        let (path, query) = match rest.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (rest, None),
+        };
+        let path = path.strip_prefix("//").unwrap_or(path);
+        if path.is_empty() {
+            return Err(DomainError::validation(format!(
+                "Endpoint uri '{}' has no path",
+                uri
+            )));
+        }
+
+        let mut options = HashMap::new();
+        for pair in query.unwrap_or_default().split('&').filter(|p| !p.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            if key.is_empty() {
+                return Err(DomainError::validation(format!(
+                    "Endpoint uri '{}' has an option without a name",
+                    uri
+                )));
+            }
+            options.insert(key.to_string(), value.to_string());
+        }
+
+        Ok(Self {
+            scheme: scheme.to_lowercase(),
+            path: path.to_string(),
+            options,
+        })
+    }
+
+    pub fn option_str(&self, key: &str) -> Option<&str> {
+        self.options.get(key).map(|value| value.as_str())
+    }
+
+    /// Parses an option into `T`, failing with a validation error naming the option.
+    pub fn option<T: FromStr>(&self, key: &str) -> Result<Option<T>, DomainError> {
+        match self.options.get(key) {
+            None => Ok(None),
+            Some(value) => value.parse::<T>().map(Some).map_err(|_| {
+                DomainError::invalid_field(
+                    key,
+                    format!("invalid value '{}' on endpoint {}", value, self),
+                )
+            }),
+        }
+    }
+
+    pub fn option_or<T: FromStr>(&self, key: &str, default: T) -> Result<T, DomainError> {
+        Ok(self.option(key)?.unwrap_or(default))
+    }
+}
+
+impl fmt::Display for EndpointUri {
+    // Options are written in sorted order so equal uris always render the same way
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.scheme, self.path)?;
+        let mut keys: Vec<&String> = self.options.keys().collect();
+        keys.sort();
+        for (i, key) in keys.into_iter().enumerate() {
+            let separator = if i == 0 { '?' } else { '&' };
+            write!(f, "{}{}={}", separator, key, self.options[key])?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for EndpointUri {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// How full an endpoint's in-process queue is, as reported by health and metrics.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueueStats {
+    pub endpoint: String,
+    pub depth: usize,
+    pub capacity: usize,
+    pub consumers: usize,
+    /// Exchanges discarded because the queue was full.
+    pub dropped: u64,
+}
@@ -1,13 +1,238 @@
+use serde::{Deserialize, Serialize};
+use std::error::Error as StdError;
+use std::time::Duration;
 use thiserror::Error;
+use uuid::Uuid;
+
+/// Coarse category of a `DomainError`; HTTP status codes and retry decisions key off this.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ErrorKind {
+    NotFound,
+    Filtered,
+    Timeout,
+    Validation,
+    Conflict,
+    RateLimited,
+    Transient,
+    Fatal,
+}
+
+/// A problem with one input field, reported alongside validation errors.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
 
 #[derive(Error, Debug)]
 pub enum DomainError {
-    #[error("Processor error: {0}")]
-    ProcessorError(String),
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    #[error("Filtered: {0}")]
+    Filtered(String),
+
+    #[error("Timed out: {message}")]
+    Timeout {
+        message: String,
+        #[source]
+        source: Option<Box<dyn StdError + Send + Sync>>,
+    },
+
+    #[error("Validation error: {message}")]
+    Validation {
+        message: String,
+        fields: Vec<FieldError>,
+    },
+
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    #[error("Rate limited: {message}")]
+    RateLimited {
+        message: String,
+        retry_after: Option<Duration>,
+    },
+
+    #[error("Transient error: {message}")]
+    Transient {
+        message: String,
+        #[source]
+        source: Option<Box<dyn StdError + Send + Sync>>,
+    },
+
+    #[error("Fatal error: {message}")]
+    Fatal {
+        message: String,
+        #[source]
+        source: Option<Box<dyn StdError + Send + Sync>>,
+    },
+
+    /// Any of the above, raised while a processor was handling a specific exchange.
+    #[error("{processor} failed on exchange {exchange_id}: {source}")]
+    Processing {
+        exchange_id: Uuid,
+        processor: String,
+        source: Box<DomainError>,
+    },
+}
+
+impl DomainError {
+    pub fn not_found(message: impl Into<String>) -> Self {
+        DomainError::NotFound(message.into())
+    }
+
+    pub fn filtered(message: impl Into<String>) -> Self {
+        DomainError::Filtered(message.into())
+    }
+
+    pub fn timeout(message: impl Into<String>) -> Self {
+        DomainError::Timeout {
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        DomainError::Validation {
+            message: message.into(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// A validation error pointing at a single input field.
+    pub fn invalid_field(field: impl Into<String>, message: impl Into<String>) -> Self {
+        let field = field.into();
+        let message = message.into();
+        DomainError::Validation {
+            message: format!("{}: {}", field, message),
+            fields: vec![FieldError { field, message }],
+        }
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        DomainError::Conflict(message.into())
+    }
+
+    pub fn rate_limited(message: impl Into<String>, retry_after: Option<Duration>) -> Self {
+        DomainError::RateLimited {
+            message: message.into(),
+            retry_after,
+        }
+    }
+
+    pub fn transient(message: impl Into<String>) -> Self {
+        DomainError::Transient {
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    pub fn fatal(message: impl Into<String>) -> Self {
+        DomainError::Fatal {
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    /// Records which processor failed on which exchange. Errors that already carry
+    /// that context (e.g. from a nested pipeline) keep the innermost processor.
+    pub fn in_processor(self, exchange_id: Uuid, processor: &str) -> Self {
+        match self {
+            DomainError::Processing { .. } => self,
+            error => DomainError::Processing {
+                exchange_id,
+                processor: processor.to_string(),
+                source: Box::new(error),
+            },
+        }
+    }
+
+    /// The error without any processing context.
+    pub fn root(&self) -> &DomainError {
+        match self {
+            DomainError::Processing { source, .. } => source.root(),
+            error => error,
+        }
+    }
+
+    pub fn exchange_id(&self) -> Option<Uuid> {
+        match self {
+            DomainError::Processing { exchange_id, .. } => Some(*exchange_id),
+            _ => None,
+        }
+    }
+
+    pub fn processor(&self) -> Option<&str> {
+        match self {
+            DomainError::Processing { processor, .. } => Some(processor),
+            _ => None,
+        }
+    }
+
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self.root() {
+            DomainError::RateLimited { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// Attaches an underlying cause; variants without a source slot are returned unchanged.
+    pub fn with_source(mut self, cause: impl StdError + Send + Sync + 'static) -> Self {
+        match &mut self {
+            DomainError::Timeout { source, .. }
+            | DomainError::Transient { source, .. }
+            | DomainError::Fatal { source, .. } => *source = Some(Box::new(cause)),
+            DomainError::Processing { source, .. } => {
+                let inner = std::mem::replace(source.as_mut(), DomainError::fatal(""));
+                **source = inner.with_source(cause);
+            }
+            _ => {}
+        }
+        self
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            DomainError::NotFound(_) => ErrorKind::NotFound,
+            DomainError::Filtered(_) => ErrorKind::Filtered,
+            DomainError::Timeout { .. } => ErrorKind::Timeout,
+            DomainError::Validation { .. } => ErrorKind::Validation,
+            DomainError::Conflict(_) => ErrorKind::Conflict,
+            DomainError::RateLimited { .. } => ErrorKind::RateLimited,
+            DomainError::Transient { .. } => ErrorKind::Transient,
+            DomainError::Fatal { .. } => ErrorKind::Fatal,
+            DomainError::Processing { source, .. } => source.kind(),
+        }
+    }
+
+    /// Whether trying the same operation again may succeed.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self.kind(),
+            ErrorKind::Timeout | ErrorKind::Transient | ErrorKind::RateLimited
+        )
+    }
 
-    #[error("Repository error: {0}")]
-    RepositoryError(String),
+    /// The message without the kind prefix added by `Display`.
+    pub fn message(&self) -> &str {
+        match self {
+            DomainError::NotFound(message)
+            | DomainError::Filtered(message)
+            | DomainError::Conflict(message) => message,
+            DomainError::Timeout { message, .. }
+            | DomainError::Validation { message, .. }
+            | DomainError::RateLimited { message, .. }
+            | DomainError::Transient { message, .. }
+            | DomainError::Fatal { message, .. } => message,
+            DomainError::Processing { source, .. } => source.message(),
+        }
+    }
 
-    #[error("Validation error: {0}")]
-    ValidationError(String),
+    pub fn fields(&self) -> &[FieldError] {
+        match self.root() {
+            DomainError::Validation { fields, .. } => fields,
+            _ => &[],
+        }
+    }
 }
@@ -1,3 +1,9 @@
+use crate::domain::models::{
+    body::{Body, CONTENT_TYPE},
+    conversion::{ConversionContext, TypeConverterRegistry},
+    error::DomainError,
+    header::HeaderValue,
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -6,14 +12,20 @@ use uuid::Uuid;
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Exchange {
     pub id: Uuid,
-    pub body: String,
-    pub headers: HashMap<String, String>,
-    pub properties: HashMap<String, String>,
+    pub body: Body,
+    pub headers: HashMap<String, HeaderValue>,
+    pub properties: HashMap<String, HeaderValue>,
     pub pattern: ExchangePattern,
+    /// The reply of an `InOut` exchange, once a processor or producer has set one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub out: Option<Message>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub processing_history: Vec<ProcessingStep>,
     pub metadata: ExchangeMetadata,
+    /// Number of times this exchange has been saved; 0 until it is first stored.
+    #[serde(default)]
+    pub version: u64,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -33,21 +45,47 @@ pub struct ExchangeMetadata {
     pub retry_count: u32,
 }
 
+/// A body with its headers, as carried by the out message of an exchange.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Message {
+    pub body: Body,
+    #[serde(default)]
+    pub headers: HashMap<String, HeaderValue>,
+}
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// Whether the sender of an exchange waits for a reply (`InOut`) or only hands it over (`InOnly`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ExchangePattern {
+    #[default]
     InOnly,
     InOut,
 }
 
+impl ExchangePattern {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExchangePattern::InOnly => "InOnly",
+            ExchangePattern::InOut => "InOut",
+        }
+    }
+
+    /// Parses a pattern name, ignoring case.
+    pub fn parse(value: &str) -> Option<Self> {
+        [ExchangePattern::InOnly, ExchangePattern::InOut]
+            .into_iter()
+            .find(|pattern| pattern.as_str().eq_ignore_ascii_case(value.trim()))
+    }
+}
+
 impl Exchange {
-    pub fn new(body: String) -> Self {
+    pub fn new(body: impl Into<Body>) -> Self {
         Self {
             id: Uuid::new_v4(),
-            body,
+            body: body.into(),
             headers: HashMap::new(),
             properties: HashMap::new(),
             pattern: ExchangePattern::InOnly,
+            out: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             processing_history: Vec::new(),
@@ -57,16 +95,95 @@ impl Exchange {
                 priority: "normal".to_string(),
                 retry_count: 0,
             },
+            version: 0,
+        }
+    }
+
+    /// Fails with a conflict unless this copy was read at the `stored` version
+    /// (`None` when nothing is stored yet).
+    pub fn check_version(&self, stored: Option<u64>) -> Result<(), DomainError> {
+        let stored_version = stored.unwrap_or(0);
+        if self.version == stored_version {
+            return Ok(());
+        }
+        Err(DomainError::conflict(match stored {
+            Some(stored) => format!(
+                "Exchange {} is at version {}, but version {} was written",
+                self.id, stored, self.version
+            ),
+            None => format!("Exchange {} no longer exists", self.id),
+        }))
+    }
+
+    /// The body converted to `T` by the global `TypeConverterRegistry`.
+    pub fn body_as<T: 'static>(&self) -> Result<T, DomainError> {
+        TypeConverterRegistry::global().convert_body(&self.body, &self.conversion_context())
+    }
+
+    /// The header converted to `T` by the global `TypeConverterRegistry`; `None` when absent.
+    pub fn header_as<T: 'static>(&self, name: &str) -> Result<Option<T>, DomainError> {
+        self.headers
+            .get(name)
+            .map(|value| TypeConverterRegistry::global().convert_header(name, value, &self.conversion_context()))
+            .transpose()
+    }
+
+    /// Conversion settings taken from the exchange: the charset comes from the
+    /// `content_type` header, or else the `charset` property.
+    pub fn conversion_context(&self) -> ConversionContext {
+        let from_content_type = self.headers.get(CONTENT_TYPE).and_then(HeaderValue::as_str).and_then(|content_type| {
+            content_type.split(';').skip(1).find_map(|param| {
+                let (name, value) = param.split_once('=')?;
+                name.trim()
+                    .eq_ignore_ascii_case("charset")
+                    .then(|| value.trim().trim_matches('"').to_string())
+            })
+        });
+        ConversionContext {
+            charset: from_content_type.or_else(|| self.properties.get("charset").map(HeaderValue::to_string)),
+        }
+    }
+
+    pub fn is_in_out(&self) -> bool {
+        self.pattern == ExchangePattern::InOut
+    }
+
+    /// The out message, created empty on first use.
+    pub fn out_mut(&mut self) -> &mut Message {
+        self.updated_at = Utc::now();
+        self.out.get_or_insert_with(Message::default)
+    }
+
+    /// Replies with `body`, keeping any headers already set on the out message.
+    pub fn set_out(&mut self, body: impl Into<Body>) {
+        self.out_mut().body = body.into();
+    }
+
+    /// What the sender of an `InOut` exchange gets back: the out message when one
+    /// was set, otherwise the in message as processing left it.
+    pub fn into_reply(self) -> Message {
+        self.out.unwrap_or(Message {
+            body: self.body,
+            headers: self.headers,
+        })
+    }
+
+    /// Reads any streaming in or out body in full, so the exchange can be stored.
+    pub async fn buffer(&mut self) -> Result<(), DomainError> {
+        self.body.buffer().await?;
+        if let Some(out) = &mut self.out {
+            out.body.buffer().await?;
         }
+        Ok(())
     }
 
-    pub fn set_header(&mut self, key: &str, value: &str) {
-        self.headers.insert(key.to_string(), value.to_string());
+    pub fn set_header(&mut self, key: &str, value: impl Into<HeaderValue>) {
+        self.headers.insert(key.to_string(), value.into());
         self.updated_at = Utc::now();
     }
 
-    pub fn set_property(&mut self, key: &str, value: &str) {
-        self.properties.insert(key.to_string(), value.to_string());
+    pub fn set_property(&mut self, key: &str, value: impl Into<HeaderValue>) {
+        self.properties.insert(key.to_string(), value.into());
         self.updated_at = Utc::now();
     }
     
@@ -0,0 +1,227 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// A header or property value. Strings, numbers, booleans and lists serialize
+/// as the matching JSON value, so exchanges stored when every value was a string
+/// still load; timestamps and bytes are tagged to tell them apart from strings,
+/// and so are NaN and infinite floats, which JSON numbers cannot hold.
+#[derive(Clone, Debug, PartialEq)]
+pub enum HeaderValue {
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Bool(bool),
+    Timestamp(DateTime<Utc>),
+    Bytes(Bytes),
+    List(Vec<HeaderValue>),
+}
+
+impl HeaderValue {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            HeaderValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            HeaderValue::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            HeaderValue::Float(value) => Some(*value),
+            HeaderValue::Integer(value) => Some(*value as f64),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            HeaderValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_timestamp(&self) -> Option<DateTime<Utc>> {
+        match self {
+            HeaderValue::Timestamp(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+/// The string form that headers had before they were typed: timestamps as
+/// RFC 3339, bytes as base64 and lists comma-separated.
+impl fmt::Display for HeaderValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeaderValue::String(value) => f.write_str(value),
+            HeaderValue::Integer(value) => write!(f, "{}", value),
+            HeaderValue::Float(value) => write!(f, "{}", value),
+            HeaderValue::Bool(value) => write!(f, "{}", value),
+            HeaderValue::Timestamp(value) => f.write_str(&value.to_rfc3339()),
+            HeaderValue::Bytes(value) => f.write_str(&STANDARD.encode(value)),
+            HeaderValue::List(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Compares the string form, so `Integer(3)` equals `"3"`.
+impl PartialEq<str> for HeaderValue {
+    fn eq(&self, other: &str) -> bool {
+        match self {
+            HeaderValue::String(value) => value == other,
+            _ => {
+                let rendered = self.to_string();
+                rendered == other
+            }
+        }
+    }
+}
+
+impl PartialEq<&str> for HeaderValue {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl PartialEq<String> for HeaderValue {
+    fn eq(&self, other: &String) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl From<String> for HeaderValue {
+    fn from(value: String) -> Self {
+        HeaderValue::String(value)
+    }
+}
+
+impl From<&str> for HeaderValue {
+    fn from(value: &str) -> Self {
+        HeaderValue::String(value.to_string())
+    }
+}
+
+impl From<&String> for HeaderValue {
+    fn from(value: &String) -> Self {
+        HeaderValue::String(value.clone())
+    }
+}
+
+impl From<i64> for HeaderValue {
+    fn from(value: i64) -> Self {
+        HeaderValue::Integer(value)
+    }
+}
+
+impl From<u64> for HeaderValue {
+    fn from(value: u64) -> Self {
+        HeaderValue::Integer(i64::try_from(value).unwrap_or(i64::MAX))
+    }
+}
+
+impl From<usize> for HeaderValue {
+    fn from(value: usize) -> Self {
+        HeaderValue::Integer(i64::try_from(value).unwrap_or(i64::MAX))
+    }
+}
+
+impl From<f64> for HeaderValue {
+    fn from(value: f64) -> Self {
+        HeaderValue::Float(value)
+    }
+}
+
+impl From<bool> for HeaderValue {
+    fn from(value: bool) -> Self {
+        HeaderValue::Bool(value)
+    }
+}
+
+impl From<DateTime<Utc>> for HeaderValue {
+    fn from(value: DateTime<Utc>) -> Self {
+        HeaderValue::Timestamp(value)
+    }
+}
+
+impl From<Bytes> for HeaderValue {
+    fn from(value: Bytes) -> Self {
+        HeaderValue::Bytes(value)
+    }
+}
+
+impl From<Vec<HeaderValue>> for HeaderValue {
+    fn from(items: Vec<HeaderValue>) -> Self {
+        HeaderValue::List(items)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum HeaderRepr {
+    String(String),
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    List(Vec<HeaderValue>),
+    Tagged(TaggedHeader),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum TaggedHeader {
+    Timestamp(DateTime<Utc>),
+    Base64(String),
+    Float(String),
+}
+
+impl Serialize for HeaderValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let repr = match self {
+            HeaderValue::String(value) => HeaderRepr::String(value.clone()),
+            HeaderValue::Integer(value) => HeaderRepr::Integer(*value),
+            HeaderValue::Float(value) if value.is_finite() => HeaderRepr::Float(*value),
+            HeaderValue::Float(value) => HeaderRepr::Tagged(TaggedHeader::Float(value.to_string())),
+            HeaderValue::Bool(value) => HeaderRepr::Bool(*value),
+            HeaderValue::Timestamp(value) => HeaderRepr::Tagged(TaggedHeader::Timestamp(*value)),
+            HeaderValue::Bytes(value) => HeaderRepr::Tagged(TaggedHeader::Base64(STANDARD.encode(value))),
+            HeaderValue::List(items) => HeaderRepr::List(items.clone()),
+        };
+        repr.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for HeaderValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match HeaderRepr::deserialize(deserializer)? {
+            HeaderRepr::String(value) => HeaderValue::String(value),
+            HeaderRepr::Bool(value) => HeaderValue::Bool(value),
+            HeaderRepr::Integer(value) => HeaderValue::Integer(value),
+            HeaderRepr::Float(value) => HeaderValue::Float(value),
+            HeaderRepr::List(items) => HeaderValue::List(items),
+            HeaderRepr::Tagged(TaggedHeader::Timestamp(value)) => HeaderValue::Timestamp(value),
+            HeaderRepr::Tagged(TaggedHeader::Base64(value)) => {
+                HeaderValue::Bytes(STANDARD.decode(value).map_err(de::Error::custom)?.into())
+            }
+            HeaderRepr::Tagged(TaggedHeader::Float(value)) => {
+                HeaderValue::Float(value.parse().map_err(de::Error::custom)?)
+            }
+        })
+    }
+}
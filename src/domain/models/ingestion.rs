@@ -0,0 +1,76 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::domain::models::revision::ExchangeRevision;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IngestionStatus {
+    Queued,
+    Processing,
+    Completed,
+    Failed,
+    DeadLettered,
+}
+
+impl IngestionStatus {
+    pub fn is_finished(&self) -> bool {
+        !matches!(self, IngestionStatus::Queued | IngestionStatus::Processing)
+    }
+}
+
+/// Where an asynchronously submitted message is on its way through the pipeline.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ingestion {
+    pub message_id: Uuid,
+    pub status: IngestionStatus,
+    /// Why processing failed, for `failed` and `dead_lettered` messages.
+    pub error: Option<String>,
+    pub queued_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl Ingestion {
+    pub fn queued(message_id: Uuid) -> Self {
+        Self {
+            message_id,
+            status: IngestionStatus::Queued,
+            error: None,
+            queued_at: Utc::now(),
+            started_at: None,
+            finished_at: None,
+        }
+    }
+
+    /// Rebuilds the status from a message's stored revisions, oldest first, or `None`
+    /// when it was not submitted asynchronously. Dead lettering is not recorded in
+    /// the chain, so such a message reads as `Failed`; when it started is unknown.
+    pub fn from_revisions(revisions: &[ExchangeRevision]) -> Option<Self> {
+        let first = revisions.first().filter(|revision| revision.cause.trigger == "queued")?;
+        let last = revisions.last()?;
+        let mut ingestion = Self::queued(first.exchange.id);
+        ingestion.queued_at = first.recorded_at;
+        ingestion.status = match last.cause.trigger.as_str() {
+            "queued" => return Some(ingestion),
+            "failed" => {
+                ingestion.error = last.exchange.processing_history.last().and_then(|step| step.notes.clone());
+                IngestionStatus::Failed
+            }
+            _ => IngestionStatus::Completed,
+        };
+        ingestion.finished_at = Some(last.recorded_at);
+        Some(ingestion)
+    }
+
+    pub fn start(&mut self) {
+        self.status = IngestionStatus::Processing;
+        self.started_at = Some(Utc::now());
+    }
+
+    pub fn finish(&mut self, status: IngestionStatus, error: Option<String>) {
+        self.status = status;
+        self.error = error;
+        self.finished_at = Some(Utc::now());
+    }
+}
@@ -1,3 +1,14 @@
 // src/domain/models/mod.rs
+pub mod aggregation;
+pub mod body;
+pub mod conversion;
+pub mod dead_letter;
+pub mod endpoint;
 pub mod exchange;
-pub mod error;
\ No newline at end of file
+pub mod error;
+pub mod header;
+pub mod ingestion;
+pub mod query;
+pub mod replay;
+pub mod retention;
+pub mod revision;
\ No newline at end of file
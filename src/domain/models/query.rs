@@ -0,0 +1,136 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::domain::models::{exchange::Exchange, revision::ExchangeRevision};
+
+/// Filters and paging for listing stored exchanges. Every filter that is set must match.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MessageQuery {
+    pub headers: HashMap<String, String>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+    pub source_system: Option<String>,
+    pub priority: Option<String>,
+    /// `Some(true)` keeps exchanges whose every processing step succeeded,
+    /// `Some(false)` those with at least one failed step.
+    pub success: Option<bool>,
+    /// Keeps exchanges whose latest revision was written for this trigger, e.g.
+    /// "queued" for messages no worker has picked up yet.
+    pub trigger: Option<String>,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+impl MessageQuery {
+    pub const DEFAULT_LIMIT: usize = 50;
+    pub const MAX_LIMIT: usize = 500;
+
+    pub fn new() -> Self {
+        Self {
+            headers: HashMap::new(),
+            created_after: None,
+            created_before: None,
+            source_system: None,
+            priority: None,
+            success: None,
+            trigger: None,
+            offset: 0,
+            limit: Self::DEFAULT_LIMIT,
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    pub fn created_between(
+        mut self,
+        after: Option<DateTime<Utc>>,
+        before: Option<DateTime<Utc>>,
+    ) -> Self {
+        self.created_after = after;
+        self.created_before = before;
+        self
+    }
+
+    pub fn source_system(mut self, source_system: &str) -> Self {
+        self.source_system = Some(source_system.to_string());
+        self
+    }
+
+    pub fn priority(mut self, priority: &str) -> Self {
+        self.priority = Some(priority.to_string());
+        self
+    }
+
+    pub fn success(mut self, success: bool) -> Self {
+        self.success = Some(success);
+        self
+    }
+
+    pub fn trigger(mut self, trigger: &str) -> Self {
+        self.trigger = Some(trigger.to_string());
+        self
+    }
+
+    pub fn page(mut self, offset: usize, limit: usize) -> Self {
+        self.offset = offset;
+        self.limit = limit.clamp(1, Self::MAX_LIMIT);
+        self
+    }
+
+    pub fn matches(&self, exchange: &Exchange) -> bool {
+        self.headers
+            .iter()
+            .all(|(name, value)| exchange.headers.get(name).is_some_and(|header| header == value))
+            && self.created_after.is_none_or(|after| exchange.created_at >= after)
+            && self.created_before.is_none_or(|before| exchange.created_at < before)
+            && self
+                .source_system
+                .as_ref()
+                .is_none_or(|source| &exchange.metadata.source_system == source)
+            && self
+                .priority
+                .as_ref()
+                .is_none_or(|priority| &exchange.metadata.priority == priority)
+            && self.success.is_none_or(|success| {
+                exchange.processing_history.iter().all(|step| step.success) == success
+            })
+    }
+
+    /// `matches` for the exchange as stored in `revision`, also checking `trigger`.
+    pub fn matches_revision(&self, revision: &ExchangeRevision) -> bool {
+        self.matches(&revision.exchange)
+            && self
+                .trigger
+                .as_ref()
+                .is_none_or(|trigger| &revision.cause.trigger == trigger)
+    }
+}
+
+impl Default for MessageQuery {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One page of query results plus the total number of matches.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+impl<T> Page<T> {
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            total: self.total,
+            offset: self.offset,
+            limit: self.limit,
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use crate::domain::models::query::MessageQuery;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// The stored exchanges a replay job sends back through a route.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReplaySelection {
+    Ids(Vec<Uuid>),
+    /// Every exchange the query matches; its paging is ignored.
+    Query(MessageQuery),
+}
+
+/// What to replay, where to, and how fast.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReplayRequest {
+    pub selection: ReplaySelection,
+    pub route_id: String,
+    /// Upper bound on exchanges replayed per second; unlimited when `None`.
+    pub rate_limit: Option<f64>,
+    pub triggered_by: Option<String>,
+}
+
+impl ReplayRequest {
+    pub fn new(selection: ReplaySelection, route_id: &str) -> Self {
+        Self {
+            selection,
+            route_id: route_id.to_string(),
+            rate_limit: None,
+            triggered_by: None,
+        }
+    }
+
+    pub fn rate_limit(mut self, per_second: f64) -> Self {
+        self.rate_limit = Some(per_second);
+        self
+    }
+
+    pub fn triggered_by(mut self, triggered_by: Option<String>) -> Self {
+        self.triggered_by = triggered_by;
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplayStatus {
+    Running,
+    Completed,
+    Cancelled,
+}
+
+impl ReplayStatus {
+    pub fn is_finished(&self) -> bool {
+        *self != ReplayStatus::Running
+    }
+}
+
+/// A failed exchange and why, as reported in the job's progress.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayFailure {
+    pub message_id: Uuid,
+    pub error: String,
+}
+
+/// Progress of a replay job; `processed` counts succeeded and failed exchanges.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReplayJob {
+    pub id: Uuid,
+    pub route_id: String,
+    pub status: ReplayStatus,
+    pub total: usize,
+    pub processed: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    /// The most recent failures, capped at `ReplayJob::MAX_FAILURES`.
+    pub failures: Vec<ReplayFailure>,
+    pub rate_limit: Option<f64>,
+    pub triggered_by: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl ReplayJob {
+    pub const MAX_FAILURES: usize = 100;
+
+    pub fn new(request: &ReplayRequest, total: usize) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            route_id: request.route_id.clone(),
+            status: ReplayStatus::Running,
+            total,
+            processed: 0,
+            succeeded: 0,
+            failed: 0,
+            failures: Vec::new(),
+            rate_limit: request.rate_limit,
+            triggered_by: request.triggered_by.clone(),
+            started_at: Utc::now(),
+            finished_at: None,
+        }
+    }
+
+    pub fn record_success(&mut self) {
+        self.processed += 1;
+        self.succeeded += 1;
+    }
+
+    pub fn record_failure(&mut self, message_id: Uuid, error: String) {
+        self.processed += 1;
+        self.failed += 1;
+        if self.failures.len() == Self::MAX_FAILURES {
+            self.failures.remove(0);
+        }
+        self.failures.push(ReplayFailure { message_id, error });
+    }
+
+    pub fn finish(&mut self, status: ReplayStatus) {
+        self.status = status;
+        self.finished_at = Some(Utc::now());
+    }
+}
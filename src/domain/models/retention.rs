@@ -0,0 +1,79 @@
+use crate::domain::models::query::MessageQuery;
+use std::time::Duration;
+
+/// Limits on the stored exchanges a rule selects. A rule without selectors applies
+/// to every exchange; one with both selectors only to exchanges matching both.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RetentionRule {
+    pub source_system: Option<String>,
+    pub priority: Option<String>,
+    pub max_age: Option<Duration>,
+    pub max_count: Option<usize>,
+}
+
+impl RetentionRule {
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    pub fn for_source_system(source_system: &str) -> Self {
+        Self {
+            source_system: Some(source_system.to_string()),
+            ..Self::default()
+        }
+    }
+
+    pub fn for_priority(priority: &str) -> Self {
+        Self {
+            priority: Some(priority.to_string()),
+            ..Self::default()
+        }
+    }
+
+    pub fn priority(mut self, priority: &str) -> Self {
+        self.priority = Some(priority.to_string());
+        self
+    }
+
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    pub fn max_count(mut self, max_count: usize) -> Self {
+        self.max_count = Some(max_count);
+        self
+    }
+
+    /// A query selecting the exchanges this rule governs.
+    pub fn query(&self) -> MessageQuery {
+        let mut query = MessageQuery::new();
+        query.source_system = self.source_system.clone();
+        query.priority = self.priority.clone();
+        query
+    }
+}
+
+/// Every rule is enforced independently, so an exchange is expired as soon as
+/// any rule selecting it says so.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    pub rules: Vec<RetentionRule>,
+}
+
+impl RetentionPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn rule(mut self, rule: RetentionRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules
+            .iter()
+            .all(|rule| rule.max_age.is_none() && rule.max_count.is_none())
+    }
+}
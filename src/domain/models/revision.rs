@@ -0,0 +1,107 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
+use crate::domain::models::exchange::Exchange;
+
+/// What caused a revision to be written and, when known, who asked for it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevisionCause {
+    pub trigger: String,
+    pub triggered_by: Option<String>,
+}
+
+impl RevisionCause {
+    pub fn new(trigger: &str) -> Self {
+        Self {
+            trigger: trigger.to_string(),
+            triggered_by: None,
+        }
+    }
+
+    pub fn triggered_by(mut self, actor: Option<String>) -> Self {
+        self.triggered_by = actor;
+        self
+    }
+}
+
+/// The positions, in a non-empty chain of `len` revisions, that a cap of
+/// `max_revisions` drops: the oldest ones after the first, which records how the
+/// exchange arrived and is always kept. Caps below 2 count as 2.
+pub fn surplus_revisions(len: usize, max_revisions: usize) -> Range<usize> {
+    1..len.saturating_sub(max_revisions.max(2) - 1).max(1)
+}
+
+/// An immutable snapshot of an exchange as it was saved at `exchange.version`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExchangeRevision {
+    pub exchange: Exchange,
+    pub cause: RevisionCause,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl ExchangeRevision {
+    pub fn new(exchange: Exchange, cause: RevisionCause) -> Self {
+        Self {
+            exchange,
+            cause,
+            recorded_at: Utc::now(),
+        }
+    }
+
+    pub fn version(&self) -> u64 {
+        self.exchange.version
+    }
+
+    /// What changed from `previous` to this revision.
+    pub fn diff(&self, previous: &ExchangeRevision) -> RevisionDiff {
+        let (before, after) = (&previous.exchange, &self.exchange);
+        RevisionDiff {
+            from_version: previous.version(),
+            to_version: self.version(),
+            body: (before.body != after.body).then(|| ValueChange {
+                from: Some(before.body.to_string()),
+                to: Some(after.body.to_string()),
+            }),
+            headers: map_diff(&before.headers, &after.headers),
+            properties: map_diff(&before.properties, &after.properties),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValueChange {
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevisionDiff {
+    pub from_version: u64,
+    pub to_version: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<ValueChange>,
+    /// Changed keys only; `from: None` means added and `to: None` removed.
+    pub headers: BTreeMap<String, ValueChange>,
+    pub properties: BTreeMap<String, ValueChange>,
+}
+
+fn map_diff<V: PartialEq + ToString>(
+    before: &HashMap<String, V>,
+    after: &HashMap<String, V>,
+) -> BTreeMap<String, ValueChange> {
+    before
+        .keys()
+        .chain(after.keys())
+        .filter(|key| before.get(*key) != after.get(*key))
+        .map(|key| {
+            (
+                key.clone(),
+                ValueChange {
+                    from: before.get(key).map(ToString::to_string),
+                    to: after.get(key).map(ToString::to_string),
+                },
+            )
+        })
+        .collect()
+}
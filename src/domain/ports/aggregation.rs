@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+use crate::domain::models::{aggregation::AggregationGroup, exchange::Exchange, error::DomainError};
+
+/// Merges exchanges one at a time; `aggregated` is `None` for the first exchange of a group.
+pub trait AggregationStrategy: Send + Sync {
+    fn aggregate(&self, aggregated: Option<Exchange>, next: Exchange) -> Result<Exchange, DomainError>;
+}
+
+/// Stores in-flight aggregation groups so they outlive the process that started them.
+#[async_trait]
+pub trait AggregationRepository: Send + Sync {
+    async fn get(&self, correlation_key: &str) -> Result<Option<AggregationGroup>, DomainError>;
+    async fn save(&self, group: &AggregationGroup) -> Result<(), DomainError>;
+    async fn remove(&self, correlation_key: &str) -> Result<(), DomainError>;
+    async fn find_all(&self) -> Result<Vec<AggregationGroup>, DomainError>;
+}
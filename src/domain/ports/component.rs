@@ -0,0 +1,18 @@
+use crate::domain::models::{
+    endpoint::{EndpointUri, QueueStats},
+    error::DomainError,
+};
+use crate::domain::ports::endpoint::Endpoint;
+use std::sync::Arc;
+
+/// A named factory for endpoints of one URI scheme (e.g. `seda`, `file`).
+pub trait Component: Send + Sync {
+    fn scheme(&self) -> &str;
+
+    fn create_endpoint(&self, uri: &EndpointUri) -> Result<Arc<dyn Endpoint>, DomainError>;
+
+    /// The queues this component buffers exchanges in; empty for components that do not queue.
+    fn queue_stats(&self) -> Vec<QueueStats> {
+        Vec::new()
+    }
+}
@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+use crate::domain::models::{dead_letter::DeadLetter, error::DomainError};
+
+#[async_trait]
+pub trait DeadLetterRepository: Send + Sync {
+    async fn save(&self, dead_letter: &DeadLetter) -> Result<(), DomainError>;
+    async fn find_by_id(&self, id: &Uuid) -> Result<Option<DeadLetter>, DomainError>;
+    async fn find_all(&self) -> Result<Vec<DeadLetter>, DomainError>;
+    async fn delete(&self, id: &Uuid) -> Result<(), DomainError>;
+}
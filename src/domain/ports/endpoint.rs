@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+use crate::domain::models::{endpoint::EndpointUri, exchange::Exchange, error::DomainError};
+use crate::domain::ports::processor::Processor;
+
+/// An addressable source or destination of exchanges, created by a `Component`.
+pub trait Endpoint: Send + Sync {
+    fn uri(&self) -> &EndpointUri;
+
+    fn create_producer(&self) -> Result<Arc<dyn Producer>, DomainError>;
+
+    /// Creates a consumer that feeds every received exchange into `processor`.
+    fn create_consumer(&self, processor: Arc<dyn Processor>) -> Result<Box<dyn Consumer>, DomainError>;
+}
+
+/// Sends exchanges to an endpoint, returning the exchange as the endpoint left it.
+#[async_trait]
+pub trait Producer: Send + Sync {
+    async fn send(&self, exchange: Exchange) -> Result<Exchange, DomainError>;
+}
+
+/// Receives exchanges from an endpoint while started.
+pub trait Consumer: Send + Sync {
+    fn start(&mut self) -> Result<(), DomainError>;
+
+    fn stop(&mut self) -> Result<(), DomainError>;
+
+    fn suspend(&mut self) -> Result<(), DomainError> {
+        self.stop()
+    }
+
+    fn resume(&mut self) -> Result<(), DomainError> {
+        self.start()
+    }
+}
@@ -1,3 +1,8 @@
 // src/domain/ports/mod.rs
+pub mod aggregation;
+pub mod component;
+pub mod dead_letter;
+pub mod endpoint;
 pub mod processor;
-pub mod repository;
\ No newline at end of file
+pub mod repository;
+pub mod type_converter;
\ No newline at end of file
@@ -4,4 +4,10 @@ use crate::domain::models::{exchange::Exchange, error::DomainError};
 #[async_trait]
 pub trait Processor: Send + Sync {
     async fn process(&self, exchange: Exchange) -> Result<Exchange, DomainError>;
+
+    /// Name reported when this processor fails; defaults to the type name.
+    fn name(&self) -> &str {
+        let type_name = std::any::type_name::<Self>();
+        type_name.rsplit("::").next().unwrap_or(type_name)
+    }
 }
\ No newline at end of file
@@ -1,12 +1,32 @@
 use async_trait::async_trait;
 use uuid::Uuid;
-use crate::domain::models::{exchange::Exchange, error::DomainError};
+use crate::domain::models::{
+    exchange::Exchange,
+    error::DomainError,
+    query::{MessageQuery, Page},
+    revision::{ExchangeRevision, RevisionCause},
+};
 
 #[async_trait]
 pub trait MessageRepository: Send + Sync {
-    async fn save(&self, exchange: &Exchange) -> Result<Uuid, DomainError>;
+    /// `save_revision` with a generic `save` cause.
+    async fn save(&self, exchange: &Exchange) -> Result<Uuid, DomainError> {
+        self.save_revision(exchange, &RevisionCause::new("save")).await
+    }
+
+    /// Stores `exchange` as `exchange.version + 1` and appends that version to its
+    /// revision chain. Fails with a conflict when the stored copy is at a different
+    /// version than the one `exchange` was read at; earlier revisions never change.
+    async fn save_revision(&self, exchange: &Exchange, cause: &RevisionCause) -> Result<Uuid, DomainError>;
+
     async fn find_by_id(&self, id: &Uuid) -> Result<Option<Exchange>, DomainError>;
+
+    /// Removes the exchange together with its revision chain.
     async fn delete(&self, id: &Uuid) -> Result<(), DomainError>;
-}
 
+    /// Every stored revision of the exchange, oldest first; empty when unknown.
+    async fn revisions(&self, id: &Uuid) -> Result<Vec<ExchangeRevision>, DomainError>;
 
+    /// Matching exchanges, newest first, paged by `query.offset` and `query.limit`.
+    async fn query(&self, query: &MessageQuery) -> Result<Page<Exchange>, DomainError>;
+}
@@ -0,0 +1,9 @@
+use crate::domain::models::conversion::ConversionContext;
+use std::any::Any;
+
+/// Converts values of one source type into one target type. Converters are looked
+/// up by that pair in a `TypeConverterRegistry`; the error is a reason, which the
+/// registry reports together with both type names.
+pub trait TypeConverter: Send + Sync {
+    fn convert(&self, value: &dyn Any, context: &ConversionContext) -> Result<Box<dyn Any>, String>;
+}
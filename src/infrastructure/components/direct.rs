@@ -0,0 +1,140 @@
+use crate::domain::{
+    models::{endpoint::EndpointUri, error::DomainError, exchange::Exchange},
+    ports::{
+        component::Component,
+        endpoint::{Consumer, Endpoint, Producer},
+        processor::Processor,
+    },
+};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+type Consumers = Arc<RwLock<HashMap<String, Arc<dyn Processor>>>>;
+
+/// Synchronous calls between routes, addressed as `direct:name`.
+///
+/// A producer runs the consuming route inline, in the caller's task and on the
+/// caller's exchange, so a route can be reused as a sub-route of others. Each
+/// name has at most one consumer, and sending to a name without one fails.
+pub struct DirectComponent {
+    consumers: Consumers,
+}
+
+impl DirectComponent {
+    pub fn new() -> Self {
+        Self {
+            consumers: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+}
+
+impl Default for DirectComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component for DirectComponent {
+    fn scheme(&self) -> &str {
+        "direct"
+    }
+
+    fn create_endpoint(&self, uri: &EndpointUri) -> Result<Arc<dyn Endpoint>, DomainError> {
+        Ok(Arc::new(DirectEndpoint {
+            uri: uri.clone(),
+            consumers: self.consumers.clone(),
+        }))
+    }
+}
+
+pub struct DirectEndpoint {
+    uri: EndpointUri,
+    consumers: Consumers,
+}
+
+impl Endpoint for DirectEndpoint {
+    fn uri(&self) -> &EndpointUri {
+        &self.uri
+    }
+
+    fn create_producer(&self) -> Result<Arc<dyn Producer>, DomainError> {
+        Ok(Arc::new(DirectProducer {
+            name: self.uri.path.clone(),
+            consumers: self.consumers.clone(),
+        }))
+    }
+
+    fn create_consumer(&self, processor: Arc<dyn Processor>) -> Result<Box<dyn Consumer>, DomainError> {
+        Ok(Box::new(DirectConsumer {
+            name: self.uri.path.clone(),
+            consumers: self.consumers.clone(),
+            processor,
+        }))
+    }
+}
+
+struct DirectProducer {
+    name: String,
+    consumers: Consumers,
+}
+
+#[async_trait]
+impl Producer for DirectProducer {
+    async fn send(&self, exchange: Exchange) -> Result<Exchange, DomainError> {
+        let processor = self
+            .consumers
+            .read()
+            .map_err(|e| DomainError::fatal(format!("Failed to acquire lock: {}", e)))?
+            .get(&self.name)
+            .cloned()
+            .ok_or_else(|| {
+                DomainError::not_found(format!(
+                    "No consumer on endpoint direct:{}; is the route reading from it started?",
+                    self.name
+                ))
+            })?;
+        processor.process(exchange).await
+    }
+}
+
+struct DirectConsumer {
+    name: String,
+    consumers: Consumers,
+    processor: Arc<dyn Processor>,
+}
+
+impl Consumer for DirectConsumer {
+    fn start(&mut self) -> Result<(), DomainError> {
+        let mut consumers = self
+            .consumers
+            .write()
+            .map_err(|e| DomainError::fatal(format!("Failed to acquire lock: {}", e)))?;
+        match consumers.get(&self.name) {
+            Some(existing) if Arc::ptr_eq(existing, &self.processor) => Ok(()),
+            Some(_) => Err(DomainError::conflict(format!(
+                "Endpoint direct:{} already has a consumer",
+                self.name
+            ))),
+            None => {
+                consumers.insert(self.name.clone(), self.processor.clone());
+                Ok(())
+            }
+        }
+    }
+
+    fn stop(&mut self) -> Result<(), DomainError> {
+        let mut consumers = self
+            .consumers
+            .write()
+            .map_err(|e| DomainError::fatal(format!("Failed to acquire lock: {}", e)))?;
+        // Only unregister ourselves, never a consumer that took the name afterwards
+        if consumers
+            .get(&self.name)
+            .is_some_and(|existing| Arc::ptr_eq(existing, &self.processor))
+        {
+            consumers.remove(&self.name);
+        }
+        Ok(())
+    }
+}
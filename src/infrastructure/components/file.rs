@@ -0,0 +1,778 @@
+use crate::domain::{
+    models::{body::Body, endpoint::EndpointUri, error::DomainError, exchange::Exchange},
+    ports::{
+        component::Component,
+        endpoint::{Consumer, Endpoint, Producer},
+        processor::Processor,
+    },
+};
+use async_trait::async_trait;
+use chrono::{
+    format::{Item, StrftimeItems},
+    DateTime, Utc,
+};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Component as PathComponent, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::watch;
+use tracing::{debug, warn};
+
+/// Header with the consumed file's path relative to the endpoint directory; the
+/// producer writes to this name when it has no `fileName`.
+pub const FILE_NAME: &str = "file_name";
+/// Header with the consumed file's name without its directories.
+pub const FILE_NAME_ONLY: &str = "file_name_only";
+/// Header with the consumed file's full path.
+pub const FILE_PATH: &str = "file_path";
+pub const FILE_LENGTH: &str = "file_length";
+pub const FILE_LAST_MODIFIED: &str = "file_last_modified";
+/// Header with the full path the producer wrote to.
+pub const FILE_NAME_PRODUCED: &str = "file_name_produced";
+
+/// How the consumer makes sure a file is complete and not taken by someone else.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReadLock {
+    /// Take files as soon as they are seen.
+    None,
+    /// Take a file once its size and modification time held still for a whole poll.
+    Changed,
+    /// Claim a file by creating a `.<name>.lock` marker next to it, so consumers
+    /// sharing the directory never take the same file. Markers older than the
+    /// consumer's `read_lock_timeout` were left by a crash and are taken over.
+    MarkerFile,
+}
+
+impl ReadLock {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "none" => Some(ReadLock::None),
+            "changed" => Some(ReadLock::Changed),
+            "markerfile" => Some(ReadLock::MarkerFile),
+            _ => None,
+        }
+    }
+}
+
+/// What the consumer does with a file it processed successfully.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AfterRead {
+    /// Leave the file where it is; idempotent tracking keeps it from being read again.
+    Keep,
+    Delete,
+    /// Move the file under this directory, keeping its relative path.
+    Move(PathBuf),
+}
+
+/// What the producer does when the target file already exists.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileExist {
+    Overwrite,
+    Append,
+    /// Fail the exchange with a conflict error.
+    Fail,
+}
+
+impl FileExist {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "overwrite" => Some(FileExist::Overwrite),
+            "append" => Some(FileExist::Append),
+            "fail" => Some(FileExist::Fail),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FileConsumerConfig {
+    pub directory: PathBuf,
+    /// Time between polls; the first poll happens on start.
+    pub delay: Duration,
+    pub recursive: bool,
+    /// Globs matched against the path relative to `directory`; empty includes everything.
+    pub include: Option<GlobSet>,
+    pub exclude: Option<GlobSet>,
+    pub read_lock: ReadLock,
+    /// Age after which a lock marker counts as stale; `None` honours markers forever.
+    pub read_lock_timeout: Option<Duration>,
+    pub after_read: AfterRead,
+    /// Where failed files are moved; they stay in place and are retried on every poll otherwise.
+    pub move_failed: Option<PathBuf>,
+    /// Skip files already processed with the same name, size and modification time.
+    /// Tracking lives in memory, so a restart forgets it and `noop` files are read again.
+    pub idempotent: bool,
+}
+
+impl FileConsumerConfig {
+    /// Whether files are moved into `path`, which a recursive poll must then skip
+    /// so moved files are not read again.
+    fn is_move_target(&self, path: &Path) -> bool {
+        matches!(&self.after_read, AfterRead::Move(target) if target == path)
+            || self.move_failed.as_deref() == Some(path)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FileProducerConfig {
+    pub directory: PathBuf,
+    /// Name of the written file relative to `directory`; see `FileComponent`.
+    pub file_name: Option<String>,
+    pub file_exist: FileExist,
+    /// Write to a hidden temporary file first and rename it into place, so readers
+    /// never see a partial file. Appends always write in place.
+    pub temp_file: bool,
+}
+
+/// Polls and writes directories, addressed as `file:path/to/dir?options`.
+///
+/// Consumer options: `delay=500`, `recursive=false`, `include` and `exclude`
+/// (comma-separated globs such as `*.csv`), `readLock=markerFile|changed|none`,
+/// `readLockTimeout=600000` (0 never expires a marker), `move=.done`,
+/// `delete=true`, `noop=true`, `moveFailed=.error` and `idempotent=true`. Each
+/// file becomes an exchange with its content as a bytes body and the `file_*`
+/// headers. Hidden files and directories are never read, which keeps the default
+/// `.done` directory, lock markers and temporary files out of the way; a
+/// recursive poll also skips the `move` and `moveFailed` directories. Without
+/// `moveFailed` a failed file stays in place and is read again on every poll.
+/// Processed files are remembered in memory only, so after a restart
+/// `noop=true` reads every file in the directory again.
+///
+/// Producer options: `fileName`, `fileExist=overwrite|append|fail` and
+/// `tempFile=true`. `fileName` may use `${id}`, `${header.name}`,
+/// `${date:%Y%m%d}`, `${file:name}`, `${file:name.noext}` and `${file:ext}`.
+pub struct FileComponent {
+    /// Processed files by consumed directory, shared by every endpoint on it.
+    processed: Mutex<HashMap<PathBuf, Arc<Mutex<ProcessedFiles>>>>,
+}
+
+impl FileComponent {
+    pub const DEFAULT_DELAY: Duration = Duration::from_millis(500);
+    pub const DEFAULT_MOVE: &'static str = ".done";
+    pub const DEFAULT_READ_LOCK_TIMEOUT: Duration = Duration::from_secs(600);
+
+    pub fn new() -> Self {
+        Self {
+            processed: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn processed(&self, directory: &Path) -> Result<Arc<Mutex<ProcessedFiles>>, DomainError> {
+        let mut processed = self
+            .processed
+            .lock()
+            .map_err(|e| DomainError::fatal(format!("Failed to acquire lock: {}", e)))?;
+        Ok(processed.entry(directory.to_path_buf()).or_default().clone())
+    }
+}
+
+impl Default for FileComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component for FileComponent {
+    fn scheme(&self) -> &str {
+        "file"
+    }
+
+    fn create_endpoint(&self, uri: &EndpointUri) -> Result<Arc<dyn Endpoint>, DomainError> {
+        let directory = PathBuf::from(&uri.path);
+        let consumer = consumer_config(uri, &directory)?;
+        let producer = producer_config(uri, &directory)?;
+        Ok(Arc::new(FileEndpoint {
+            uri: uri.clone(),
+            processed: self.processed(&directory)?,
+            consumer,
+            producer,
+        }))
+    }
+}
+
+fn consumer_config(uri: &EndpointUri, directory: &Path) -> Result<FileConsumerConfig, DomainError> {
+    let read_lock = match uri.option_str("readLock") {
+        None => ReadLock::MarkerFile,
+        Some(value) => ReadLock::parse(value).ok_or_else(|| {
+            DomainError::invalid_field("readLock", "must be one of markerFile, changed or none")
+        })?,
+    };
+    let after_read = match (
+        uri.option_or("noop", false)?,
+        uri.option_or("delete", false)?,
+        uri.option_str("move"),
+    ) {
+        (false, false, None) => AfterRead::Move(directory.join(FileComponent::DEFAULT_MOVE)),
+        (false, false, Some(target)) => AfterRead::Move(directory.join(target)),
+        (false, true, None) => AfterRead::Delete,
+        (true, false, None) => AfterRead::Keep,
+        _ => {
+            return Err(DomainError::validation(format!(
+                "Endpoint {} may set only one of noop, delete and move",
+                uri
+            )))
+        }
+    };
+    let idempotent = uri.option_or("idempotent", true)?;
+    if after_read == AfterRead::Keep && !idempotent {
+        return Err(DomainError::invalid_field(
+            "idempotent",
+            "must stay enabled with noop, or every poll reads the same files",
+        ));
+    }
+
+    Ok(FileConsumerConfig {
+        directory: directory.to_path_buf(),
+        delay: uri
+            .option::<u64>("delay")?
+            .map_or(FileComponent::DEFAULT_DELAY, Duration::from_millis),
+        recursive: uri.option_or("recursive", false)?,
+        include: globs(uri, "include")?,
+        exclude: globs(uri, "exclude")?,
+        read_lock,
+        read_lock_timeout: match uri.option::<u64>("readLockTimeout")? {
+            None => Some(FileComponent::DEFAULT_READ_LOCK_TIMEOUT),
+            Some(0) => None,
+            Some(millis) => Some(Duration::from_millis(millis)),
+        },
+        after_read,
+        move_failed: uri.option_str("moveFailed").map(|target| directory.join(target)),
+        idempotent,
+    })
+}
+
+fn producer_config(uri: &EndpointUri, directory: &Path) -> Result<FileProducerConfig, DomainError> {
+    let file_name = uri.option_str("fileName").map(str::to_string);
+    if let Some(file_name) = &file_name {
+        FileNameTemplate::parse(file_name)?;
+    }
+    let file_exist = match uri.option_str("fileExist") {
+        None => FileExist::Overwrite,
+        Some(value) => FileExist::parse(value).ok_or_else(|| {
+            DomainError::invalid_field("fileExist", "must be one of overwrite, append or fail")
+        })?,
+    };
+
+    Ok(FileProducerConfig {
+        directory: directory.to_path_buf(),
+        file_name,
+        file_exist,
+        temp_file: uri.option_or("tempFile", true)?,
+    })
+}
+
+fn globs(uri: &EndpointUri, key: &str) -> Result<Option<GlobSet>, DomainError> {
+    let Some(patterns) = uri.option_str(key) else {
+        return Ok(None);
+    };
+    let mut set = GlobSetBuilder::new();
+    for pattern in patterns.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let glob = GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()
+            .map_err(|e| DomainError::invalid_field(key, format!("invalid glob '{}': {}", pattern, e)))?;
+        set.add(glob);
+    }
+    set.build()
+        .map(Some)
+        .map_err(|e| DomainError::invalid_field(key, e.to_string()))
+}
+
+pub struct FileEndpoint {
+    uri: EndpointUri,
+    processed: Arc<Mutex<ProcessedFiles>>,
+    consumer: FileConsumerConfig,
+    producer: FileProducerConfig,
+}
+
+impl Endpoint for FileEndpoint {
+    fn uri(&self) -> &EndpointUri {
+        &self.uri
+    }
+
+    fn create_producer(&self) -> Result<Arc<dyn Producer>, DomainError> {
+        Ok(Arc::new(FileProducer {
+            file_name: match &self.producer.file_name {
+                Some(file_name) => Some(FileNameTemplate::parse(file_name)?),
+                None => None,
+            },
+            config: self.producer.clone(),
+        }))
+    }
+
+    fn create_consumer(&self, processor: Arc<dyn Processor>) -> Result<Box<dyn Consumer>, DomainError> {
+        Ok(Box::new(FileConsumer {
+            config: self.consumer.clone(),
+            processed: self.processed.clone(),
+            processor,
+            running: None,
+        }))
+    }
+}
+
+/// Keys of files already consumed, forgetting the oldest beyond `MAX_TRACKED`.
+#[derive(Default)]
+struct ProcessedFiles {
+    keys: HashSet<String>,
+    order: VecDeque<String>,
+}
+
+impl ProcessedFiles {
+    const MAX_TRACKED: usize = 10_000;
+
+    fn contains(&self, key: &str) -> bool {
+        self.keys.contains(key)
+    }
+
+    fn insert(&mut self, key: String) {
+        if !self.keys.insert(key.clone()) {
+            return;
+        }
+        self.order.push_back(key);
+        while self.order.len() > Self::MAX_TRACKED {
+            if let Some(oldest) = self.order.pop_front() {
+                self.keys.remove(&oldest);
+            }
+        }
+    }
+}
+
+struct FileConsumer {
+    config: FileConsumerConfig,
+    processed: Arc<Mutex<ProcessedFiles>>,
+    processor: Arc<dyn Processor>,
+    /// Dropped to stop polling.
+    running: Option<watch::Sender<()>>,
+}
+
+impl Consumer for FileConsumer {
+    fn start(&mut self) -> Result<(), DomainError> {
+        if self.running.is_some() {
+            return Ok(());
+        }
+        std::fs::create_dir_all(&self.config.directory).map_err(|e| io_error(&self.config.directory, e))?;
+        let (running, stopped) = watch::channel(());
+        let poller = Poller {
+            config: self.config.clone(),
+            processed: self.processed.clone(),
+            processor: self.processor.clone(),
+            last_seen: HashMap::new(),
+        };
+        tokio::spawn(poller.run(stopped));
+        self.running = Some(running);
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), DomainError> {
+        self.running = None;
+        Ok(())
+    }
+}
+
+struct FoundFile {
+    path: PathBuf,
+    /// Path relative to the consumed directory, `/`-separated.
+    relative: String,
+    length: u64,
+    modified: SystemTime,
+}
+
+impl FoundFile {
+    fn key(&self) -> String {
+        let modified = self
+            .modified
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        format!("{}:{}:{}", self.relative, self.length, modified)
+    }
+}
+
+struct Poller {
+    config: FileConsumerConfig,
+    processed: Arc<Mutex<ProcessedFiles>>,
+    processor: Arc<dyn Processor>,
+    /// Size and modification time of every file at the previous poll, for `ReadLock::Changed`.
+    last_seen: HashMap<PathBuf, (u64, SystemTime)>,
+}
+
+impl Poller {
+    async fn run(mut self, mut stopped: watch::Receiver<()>) {
+        loop {
+            self.poll(&stopped).await;
+            tokio::select! {
+                _ = stopped.changed() => return,
+                _ = tokio::time::sleep(self.config.delay) => {}
+            }
+        }
+    }
+
+    async fn poll(&mut self, stopped: &watch::Receiver<()>) {
+        let files = match list(&self.config).await {
+            Ok(files) => files,
+            Err(e) => {
+                warn!("Failed to poll {}: {}", self.config.directory.display(), e);
+                return;
+            }
+        };
+
+        let mut seen = HashMap::new();
+        for file in files {
+            // Stopping lets the file being processed finish, but takes no new ones
+            if stopped.has_changed().is_err() {
+                return;
+            }
+            let key = file.key();
+            if self.config.idempotent && self.is_processed(&key) {
+                continue;
+            }
+            if self.config.read_lock == ReadLock::Changed {
+                let stamp = (file.length, file.modified);
+                seen.insert(file.path.clone(), stamp);
+                if self.last_seen.get(&file.path) != Some(&stamp) {
+                    continue;
+                }
+            }
+            let marker = if self.config.read_lock == ReadLock::MarkerFile {
+                match claim(&file.path, self.config.read_lock_timeout).await {
+                    Ok(Some(marker)) => Some(marker),
+                    Ok(None) => continue,
+                    Err(e) => {
+                        warn!("Failed to lock {}: {}", file.path.display(), e);
+                        continue;
+                    }
+                }
+            } else {
+                None
+            };
+
+            self.consume(&file, key).await;
+            if let Some(marker) = marker {
+                if let Err(e) = tokio::fs::remove_file(&marker).await {
+                    warn!("Failed to remove lock {}: {}", marker.display(), e);
+                }
+            }
+        }
+        self.last_seen = seen;
+    }
+
+    fn is_processed(&self, key: &str) -> bool {
+        self.processed.lock().map(|processed| processed.contains(key)).unwrap_or(false)
+    }
+
+    async fn consume(&self, file: &FoundFile, key: String) {
+        let content = match tokio::fs::read(&file.path).await {
+            Ok(content) => content,
+            // Another consumer without a read lock may have taken it meanwhile
+            Err(e) => {
+                warn!("Failed to read {}: {}", file.path.display(), e);
+                return;
+            }
+        };
+
+        let result = self.processor.process(exchange(file, content)).await;
+        let outcome = match &result {
+            Ok(exchange) => {
+                debug!("File {} consumed as exchange {}", file.path.display(), exchange.id);
+                if self.config.idempotent {
+                    if let Ok(mut processed) = self.processed.lock() {
+                        processed.insert(key);
+                    }
+                }
+                match &self.config.after_read {
+                    AfterRead::Keep => Ok(()),
+                    AfterRead::Delete => tokio::fs::remove_file(&file.path)
+                        .await
+                        .map_err(|e| io_error(&file.path, e)),
+                    AfterRead::Move(directory) => relocate(file, directory).await,
+                }
+            }
+            Err(e) => {
+                warn!("Exchange for file {} failed: {}", file.path.display(), e);
+                match &self.config.move_failed {
+                    Some(directory) => relocate(file, directory).await,
+                    None => Ok(()),
+                }
+            }
+        };
+        if let Err(e) = outcome {
+            warn!("Failed to clean up file {}: {}", file.path.display(), e);
+        }
+    }
+}
+
+/// Files under the consumed directory that pass the filters, in name order.
+async fn list(config: &FileConsumerConfig) -> Result<Vec<FoundFile>, DomainError> {
+    let mut files = Vec::new();
+    let mut pending = vec![(config.directory.clone(), String::new())];
+    while let Some((directory, prefix)) = pending.pop() {
+        let mut entries = tokio::fs::read_dir(&directory).await.map_err(|e| io_error(&directory, e))?;
+        while let Some(entry) = entries.next_entry().await.map_err(|e| io_error(&directory, e))? {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name.starts_with('.') {
+                continue;
+            }
+            let relative = format!("{}{}", prefix, name);
+            let path = entry.path();
+            let metadata = entry.metadata().await.map_err(|e| io_error(&path, e))?;
+            if metadata.is_dir() {
+                if config.recursive && !config.is_move_target(&path) {
+                    pending.push((path, format!("{}/", relative)));
+                }
+                continue;
+            }
+            if !metadata.is_file()
+                || config.include.as_ref().is_some_and(|include| !include.is_match(&relative))
+                || config.exclude.as_ref().is_some_and(|exclude| exclude.is_match(&relative))
+            {
+                continue;
+            }
+            files.push(FoundFile {
+                path,
+                relative,
+                length: metadata.len(),
+                modified: metadata.modified().map_err(|e| io_error(&entry.path(), e))?,
+            });
+        }
+    }
+    files.sort_by(|a, b| a.relative.cmp(&b.relative));
+    Ok(files)
+}
+
+/// Creates the lock marker for `path`, or returns `None` if another consumer holds it.
+/// A marker older than `timeout` is replaced, since its consumer died holding it.
+async fn claim(path: &Path, timeout: Option<Duration>) -> std::io::Result<Option<PathBuf>> {
+    let marker = hidden_sibling(path, "lock");
+    if create_marker(&marker).await? {
+        return Ok(Some(marker));
+    }
+    let Some(timeout) = timeout else {
+        return Ok(None);
+    };
+
+    let age = match tokio::fs::metadata(&marker).await {
+        Ok(metadata) => metadata.modified()?.elapsed().unwrap_or_default(),
+        // Released in the meantime; the next poll claims it
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    if age < timeout {
+        return Ok(None);
+    }
+    warn!("Taking over stale lock {} held for {:?}", marker.display(), age);
+    match tokio::fs::remove_file(&marker).await {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+    // Another consumer taking over the same marker may win the race
+    Ok(create_marker(&marker).await?.then_some(marker))
+}
+
+/// Creates `marker`, or returns `false` if it already exists.
+async fn create_marker(marker: &Path) -> std::io::Result<bool> {
+    match tokio::fs::OpenOptions::new().write(true).create_new(true).open(marker).await {
+        Ok(_) => Ok(true),
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// `dir/.name.suffix` for `dir/name`.
+fn hidden_sibling(path: &Path, suffix: &str) -> PathBuf {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!(".{}.{}", name, suffix))
+}
+
+async fn relocate(file: &FoundFile, directory: &Path) -> Result<(), DomainError> {
+    let target = directory.join(&file.relative);
+    if let Some(parent) = target.parent() {
+        tokio::fs::create_dir_all(parent).await.map_err(|e| io_error(parent, e))?;
+    }
+    if tokio::fs::rename(&file.path, &target).await.is_ok() {
+        return Ok(());
+    }
+    // Renaming fails across file systems
+    tokio::fs::copy(&file.path, &target).await.map_err(|e| io_error(&target, e))?;
+    tokio::fs::remove_file(&file.path).await.map_err(|e| io_error(&file.path, e))
+}
+
+fn exchange(file: &FoundFile, content: Vec<u8>) -> Exchange {
+    let mut exchange = Exchange::new(Body::Bytes(content.into()));
+    exchange.metadata.source_system = "file".to_string();
+    let name_only = file.relative.rsplit('/').next().unwrap_or(&file.relative);
+    exchange.set_header(FILE_NAME, file.relative.as_str());
+    exchange.set_header(FILE_NAME_ONLY, name_only);
+    exchange.set_header(FILE_PATH, file.path.display().to_string());
+    exchange.set_header(FILE_LENGTH, file.length);
+    exchange.set_header(FILE_LAST_MODIFIED, DateTime::<Utc>::from(file.modified));
+    exchange
+}
+
+struct FileProducer {
+    config: FileProducerConfig,
+    file_name: Option<FileNameTemplate>,
+}
+
+#[async_trait]
+impl Producer for FileProducer {
+    async fn send(&self, mut exchange: Exchange) -> Result<Exchange, DomainError> {
+        let name = match &self.file_name {
+            Some(template) => template.render(&exchange)?,
+            None => exchange
+                .headers
+                .get(FILE_NAME)
+                .map(ToString::to_string)
+                .unwrap_or_else(|| exchange.id.to_string()),
+        };
+        if name.is_empty() || !Path::new(&name).components().all(|c| matches!(c, PathComponent::Normal(_))) {
+            return Err(DomainError::invalid_field(
+                "fileName",
+                format!("'{}' is not a relative path inside {}", name, self.config.directory.display()),
+            ));
+        }
+        let target = self.config.directory.join(&name);
+
+        exchange.body.buffer().await?;
+        self.write(&target, &exchange.body.bytes()?, &exchange).await?;
+        debug!("Exchange {} written to {}", exchange.id, target.display());
+        exchange.set_header(FILE_NAME_PRODUCED, target.display().to_string());
+        Ok(exchange)
+    }
+}
+
+impl FileProducer {
+    async fn write(&self, target: &Path, content: &[u8], exchange: &Exchange) -> Result<(), DomainError> {
+        if let Some(parent) = target.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(|e| io_error(parent, e))?;
+        }
+        let exists = || DomainError::conflict(format!("File {} already exists", target.display()));
+
+        if self.config.file_exist == FileExist::Append || !self.config.temp_file {
+            let mut options = tokio::fs::OpenOptions::new();
+            match self.config.file_exist {
+                FileExist::Overwrite => options.write(true).create(true).truncate(true),
+                FileExist::Append => options.append(true).create(true),
+                FileExist::Fail => options.write(true).create_new(true),
+            };
+            let mut file = options.open(target).await.map_err(|e| match e.kind() {
+                std::io::ErrorKind::AlreadyExists => exists(),
+                _ => io_error(target, e),
+            })?;
+            file.write_all(content).await.map_err(|e| io_error(target, e))?;
+            return file.flush().await.map_err(|e| io_error(target, e));
+        }
+
+        let temp = hidden_sibling(target, &format!("{}.tmp", exchange.id));
+        tokio::fs::write(&temp, content).await.map_err(|e| io_error(&temp, e))?;
+        let placed = match self.config.file_exist {
+            // A hard link, unlike a rename, never replaces an existing file
+            FileExist::Fail => match tokio::fs::hard_link(&temp, target).await {
+                Ok(()) => tokio::fs::remove_file(&temp).await.map_err(|e| io_error(&temp, e)),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Err(exists()),
+                Err(e) => Err(io_error(target, e)),
+            },
+            _ => tokio::fs::rename(&temp, target).await.map_err(|e| io_error(target, e)),
+        };
+        if placed.is_err() {
+            let _ = tokio::fs::remove_file(&temp).await;
+        }
+        placed
+    }
+}
+
+/// A `fileName` option, parsed once so that mistakes fail when the endpoint is created.
+struct FileNameTemplate(Vec<Segment>);
+
+enum Segment {
+    Literal(String),
+    Id,
+    Header(String),
+    Date(String),
+    FileName,
+    FileNameNoExt,
+    FileExt,
+}
+
+impl FileNameTemplate {
+    fn parse(template: &str) -> Result<Self, DomainError> {
+        let invalid = |message: String| DomainError::invalid_field("fileName", message);
+        let mut segments = Vec::new();
+        let mut rest = template;
+        while let Some(start) = rest.find("${") {
+            if start > 0 {
+                segments.push(Segment::Literal(rest[..start].to_string()));
+            }
+            let end = rest[start..]
+                .find('}')
+                .ok_or_else(|| invalid(format!("unclosed expression in '{}'", template)))?;
+            let expression = &rest[start + 2..start + end];
+            segments.push(match expression {
+                "id" => Segment::Id,
+                "file:name" => Segment::FileName,
+                "file:name.noext" => Segment::FileNameNoExt,
+                "file:ext" => Segment::FileExt,
+                _ => match (expression.strip_prefix("header."), expression.strip_prefix("date:")) {
+                    (Some(name), _) if !name.is_empty() => Segment::Header(name.to_string()),
+                    (_, Some(format)) if !StrftimeItems::new(format).any(|item| item == Item::Error) => {
+                        Segment::Date(format.to_string())
+                    }
+                    _ => return Err(invalid(format!("unknown expression '${{{}}}'", expression))),
+                },
+            });
+            rest = &rest[start + end + 1..];
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Literal(rest.to_string()));
+        }
+        Ok(Self(segments))
+    }
+
+    fn render(&self, exchange: &Exchange) -> Result<String, DomainError> {
+        let header = |name: &str| {
+            exchange.headers.get(name).map(ToString::to_string).ok_or_else(|| {
+                DomainError::invalid_field(
+                    "fileName",
+                    format!("header {} is not set on exchange {}", name, exchange.id),
+                )
+            })
+        };
+        let mut name = String::new();
+        for segment in &self.0 {
+            match segment {
+                Segment::Literal(text) => name.push_str(text),
+                Segment::Id => name.push_str(&exchange.id.to_string()),
+                Segment::Header(header_name) => name.push_str(&header(header_name)?),
+                Segment::Date(format) => name.push_str(&Utc::now().format(format).to_string()),
+                Segment::FileName => name.push_str(&header(FILE_NAME)?),
+                Segment::FileNameNoExt => {
+                    let file_name = header(FILE_NAME)?;
+                    name.push_str(split_extension(&file_name).0);
+                }
+                Segment::FileExt => {
+                    let file_name = header(FILE_NAME)?;
+                    name.push_str(split_extension(&file_name).1.unwrap_or_default());
+                }
+            }
+        }
+        Ok(name)
+    }
+}
+
+/// Splits `dir/name.ext` into `dir/name` and `ext`, leaving dotfiles whole.
+fn split_extension(file_name: &str) -> (&str, Option<&str>) {
+    let name_start = file_name.rfind('/').map_or(0, |slash| slash + 1);
+    match file_name[name_start..].rfind('.') {
+        Some(dot) if dot > 0 => {
+            let dot = name_start + dot;
+            (&file_name[..dot], Some(&file_name[dot + 1..]))
+        }
+        _ => (file_name, None),
+    }
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> DomainError {
+    DomainError::transient(format!("I/O error on {}", path.display())).with_source(e)
+}
@@ -0,0 +1,4 @@
+pub mod direct;
+pub mod file;
+pub mod seda;
+pub mod timer;
\ No newline at end of file
@@ -0,0 +1,355 @@
+use crate::domain::{
+    models::{
+        endpoint::{EndpointUri, QueueStats},
+        error::DomainError,
+        exchange::{Exchange, Message},
+    },
+    ports::{
+        component::Component,
+        endpoint::{Consumer, Endpoint, Producer},
+        processor::Processor,
+    },
+};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot, watch};
+use tracing::{debug, warn};
+
+/// What a producer does when the queue has no room left.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WhenFull {
+    /// Wait until a consumer takes an exchange off the queue.
+    Block,
+    /// Discard the exchange and carry on.
+    Drop,
+    /// Fail the exchange with a transient error, so error handlers can redeliver it.
+    Fail,
+}
+
+impl WhenFull {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "block" => Some(WhenFull::Block),
+            "drop" => Some(WhenFull::Drop),
+            "fail" => Some(WhenFull::Fail),
+            _ => None,
+        }
+    }
+}
+
+/// In-process queues between routes, addressed as
+/// `seda:name?size=1000&concurrentConsumers=1&whenFull=block&offerTimeout=30000&timeout=30000`.
+///
+/// Every endpoint with the same name shares one bounded queue, sized by whichever
+/// uri created it first. `InOnly` exchanges are handed over and the producer moves
+/// on; `InOut` producers wait for the consuming route and get its result as the reply.
+/// A blocked send gives up after `offerTimeout` and an `InOut` producer after
+/// `timeout` milliseconds, both with a timeout error; 0 waits indefinitely.
+pub struct SedaComponent {
+    queues: Mutex<HashMap<String, Arc<SedaQueue>>>,
+}
+
+impl SedaComponent {
+    pub const DEFAULT_SIZE: usize = 1000;
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+    pub fn new() -> Self {
+        Self {
+            queues: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn queue(&self, name: &str, size: Option<usize>) -> Result<Arc<SedaQueue>, DomainError> {
+        let mut queues = self
+            .queues
+            .lock()
+            .map_err(|e| DomainError::fatal(format!("Failed to acquire lock: {}", e)))?;
+        if let Some(queue) = queues.get(name) {
+            return match size {
+                Some(size) if size != queue.sender.max_capacity() => Err(DomainError::invalid_field(
+                    "size",
+                    format!(
+                        "queue seda:{} already exists with size {}",
+                        name,
+                        queue.sender.max_capacity()
+                    ),
+                )),
+                _ => Ok(queue.clone()),
+            };
+        }
+        let queue = Arc::new(SedaQueue::new(name, size.unwrap_or(Self::DEFAULT_SIZE)));
+        queues.insert(name.to_string(), queue.clone());
+        Ok(queue)
+    }
+}
+
+impl Default for SedaComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component for SedaComponent {
+    fn scheme(&self) -> &str {
+        "seda"
+    }
+
+    fn create_endpoint(&self, uri: &EndpointUri) -> Result<Arc<dyn Endpoint>, DomainError> {
+        let size = uri.option::<usize>("size")?;
+        if size == Some(0) {
+            return Err(DomainError::invalid_field("size", "must be at least 1"));
+        }
+        let concurrent_consumers = uri.option_or("concurrentConsumers", 1usize)?;
+        if concurrent_consumers == 0 {
+            return Err(DomainError::invalid_field("concurrentConsumers", "must be at least 1"));
+        }
+        let when_full = match uri.option_str("whenFull") {
+            None => WhenFull::Block,
+            Some(value) => WhenFull::parse(value).ok_or_else(|| {
+                DomainError::invalid_field("whenFull", "must be one of block, drop or fail")
+            })?,
+        };
+
+        Ok(Arc::new(SedaEndpoint {
+            uri: uri.clone(),
+            queue: self.queue(&uri.path, size)?,
+            concurrent_consumers,
+            when_full,
+            offer_timeout: timeout_option(uri, "offerTimeout")?,
+            timeout: timeout_option(uri, "timeout")?,
+        }))
+    }
+
+    fn queue_stats(&self) -> Vec<QueueStats> {
+        let Ok(queues) = self.queues.lock() else {
+            return Vec::new();
+        };
+        queues.values().map(|queue| queue.stats()).collect()
+    }
+}
+
+/// A wait limit in milliseconds, where 0 means no limit.
+fn timeout_option(uri: &EndpointUri, key: &str) -> Result<Option<Duration>, DomainError> {
+    Ok(match uri.option::<u64>(key)? {
+        None => Some(SedaComponent::DEFAULT_TIMEOUT),
+        Some(0) => None,
+        Some(millis) => Some(Duration::from_millis(millis)),
+    })
+}
+
+/// Runs `future` to completion, or gives up with `None` once `limit` has passed.
+async fn within<F: std::future::Future>(limit: Option<Duration>, future: F) -> Option<F::Output> {
+    match limit {
+        Some(limit) => tokio::time::timeout(limit, future).await.ok(),
+        None => Some(future.await),
+    }
+}
+
+struct QueuedExchange {
+    exchange: Exchange,
+    /// Set for `InOut` exchanges, whose producer waits for the result.
+    reply: Option<oneshot::Sender<Result<Exchange, DomainError>>>,
+}
+
+struct SedaQueue {
+    name: String,
+    sender: mpsc::Sender<QueuedExchange>,
+    // Shared so that every consumer task, of any route, competes for the next exchange
+    receiver: Arc<tokio::sync::Mutex<mpsc::Receiver<QueuedExchange>>>,
+    consumers: AtomicUsize,
+    dropped: AtomicU64,
+}
+
+impl SedaQueue {
+    fn new(name: &str, size: usize) -> Self {
+        let (sender, receiver) = mpsc::channel(size);
+        Self {
+            name: name.to_string(),
+            sender,
+            receiver: Arc::new(tokio::sync::Mutex::new(receiver)),
+            consumers: AtomicUsize::new(0),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    fn stats(&self) -> QueueStats {
+        QueueStats {
+            endpoint: format!("seda:{}", self.name),
+            depth: self.sender.max_capacity() - self.sender.capacity(),
+            capacity: self.sender.max_capacity(),
+            consumers: self.consumers.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+pub struct SedaEndpoint {
+    uri: EndpointUri,
+    queue: Arc<SedaQueue>,
+    concurrent_consumers: usize,
+    when_full: WhenFull,
+    offer_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+}
+
+impl Endpoint for SedaEndpoint {
+    fn uri(&self) -> &EndpointUri {
+        &self.uri
+    }
+
+    fn create_producer(&self) -> Result<Arc<dyn Producer>, DomainError> {
+        Ok(Arc::new(SedaProducer {
+            queue: self.queue.clone(),
+            when_full: self.when_full,
+            offer_timeout: self.offer_timeout,
+            timeout: self.timeout,
+        }))
+    }
+
+    fn create_consumer(&self, processor: Arc<dyn Processor>) -> Result<Box<dyn Consumer>, DomainError> {
+        Ok(Box::new(SedaConsumer {
+            queue: self.queue.clone(),
+            processor,
+            concurrent_consumers: self.concurrent_consumers,
+            running: None,
+        }))
+    }
+}
+
+struct SedaProducer {
+    queue: Arc<SedaQueue>,
+    when_full: WhenFull,
+    offer_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+}
+
+#[async_trait]
+impl Producer for SedaProducer {
+    async fn send(&self, exchange: Exchange) -> Result<Exchange, DomainError> {
+        let (reply, response) = if exchange.is_in_out() {
+            let (sender, receiver) = oneshot::channel();
+            (Some(sender), Some(receiver))
+        } else {
+            (None, None)
+        };
+        let queued = QueuedExchange {
+            exchange: exchange.clone(),
+            reply,
+        };
+
+        let closed = || DomainError::fatal(format!("Queue seda:{} is closed", self.queue.name));
+        match self.when_full {
+            WhenFull::Block => within(self.offer_timeout, self.queue.sender.send(queued))
+                .await
+                .ok_or_else(|| {
+                    DomainError::timeout(format!(
+                        "Queue seda:{} stayed full; is the route reading from it started?",
+                        self.queue.name
+                    ))
+                })?
+                .map_err(|_| closed())?,
+            WhenFull::Drop | WhenFull::Fail => match self.queue.sender.try_send(queued) {
+                Ok(()) => {}
+                Err(mpsc::error::TrySendError::Closed(_)) => return Err(closed()),
+                Err(mpsc::error::TrySendError::Full(_)) if self.when_full == WhenFull::Drop => {
+                    self.queue.dropped.fetch_add(1, Ordering::Relaxed);
+                    warn!("Queue seda:{} is full; dropped exchange {}", self.queue.name, exchange.id);
+                    return Ok(exchange);
+                }
+                Err(mpsc::error::TrySendError::Full(_)) => {
+                    return Err(DomainError::transient(format!("Queue seda:{} is full", self.queue.name)))
+                }
+            },
+        }
+
+        let Some(response) = response else {
+            return Ok(exchange);
+        };
+        // The exchange stays queued after a timeout; its eventual reply is discarded
+        let processed = within(self.timeout, response)
+            .await
+            .ok_or_else(|| {
+                DomainError::timeout(format!(
+                    "No reply from seda:{} to exchange {}; is the route reading from it started?",
+                    self.queue.name, exchange.id
+                ))
+            })?
+            .map_err(|_| {
+                DomainError::transient(format!(
+                    "Consumer of seda:{} stopped before replying to exchange {}",
+                    self.queue.name, exchange.id
+                ))
+            })??;
+
+        // The consuming route's result becomes the reply, unless it set an explicit one
+        let mut replied = exchange;
+        replied.out = Some(processed.out.unwrap_or(Message {
+            body: processed.body,
+            headers: processed.headers,
+        }));
+        Ok(replied)
+    }
+}
+
+struct SedaConsumer {
+    queue: Arc<SedaQueue>,
+    processor: Arc<dyn Processor>,
+    concurrent_consumers: usize,
+    /// Dropped to stop the consumer tasks.
+    running: Option<watch::Sender<()>>,
+}
+
+impl Consumer for SedaConsumer {
+    fn start(&mut self) -> Result<(), DomainError> {
+        if self.running.is_some() {
+            return Ok(());
+        }
+        let (running, stopped) = watch::channel(());
+        for _ in 0..self.concurrent_consumers {
+            let queue = self.queue.clone();
+            let processor = self.processor.clone();
+            let mut stopped = stopped.clone();
+            tokio::spawn(async move {
+                loop {
+                    // Stopping only interrupts the wait, so an exchange being processed still completes
+                    let next = tokio::select! {
+                        _ = stopped.changed() => return,
+                        next = async { queue.receiver.lock().await.recv().await } => next,
+                    };
+                    let Some(QueuedExchange { exchange, reply }) = next else {
+                        return;
+                    };
+                    let id = exchange.id;
+                    let result = processor.process(exchange).await;
+                    match (&result, reply) {
+                        (_, Some(reply)) => {
+                            // The producer may have given up waiting; nothing is lost but the reply
+                            let _ = reply.send(result);
+                        }
+                        (Ok(_), None) => debug!("Exchange {} consumed from seda:{}", id, queue.name),
+                        (Err(e), None) => warn!("Exchange {} from seda:{} failed: {}", id, queue.name, e),
+                    }
+                }
+            });
+        }
+        self.queue.consumers.fetch_add(self.concurrent_consumers, Ordering::Relaxed);
+        self.running = Some(running);
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), DomainError> {
+        if self.running.take().is_some() {
+            self.queue.consumers.fetch_sub(self.concurrent_consumers, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+}
+
+impl Drop for SedaConsumer {
+    fn drop(&mut self) {
+        let _ = self.stop();
+    }
+}
@@ -0,0 +1,237 @@
+use crate::domain::{
+    models::{endpoint::EndpointUri, error::DomainError, exchange::Exchange},
+    ports::{
+        component::Component,
+        endpoint::{Consumer, Endpoint, Producer},
+        processor::Processor,
+    },
+};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tracing::{debug, warn};
+
+/// Header naming the timer that fired the exchange.
+pub const TIMER_NAME: &str = "timer_name";
+/// Header counting the timer's firings, starting at 1.
+pub const TIMER_COUNTER: &str = "timer_counter";
+/// Header with the time the firing was due.
+pub const TIMER_SCHEDULED_AT: &str = "timer_scheduled_at";
+/// Header with the time the firing actually happened.
+pub const TIMER_FIRED_AT: &str = "timer_fired_at";
+
+/// When a timer fires.
+#[derive(Clone, Debug)]
+pub enum TimerSchedule {
+    /// Every `period`, measured from the first firing.
+    Period(Duration),
+    /// Whenever the cron expression (with seconds) matches in `time_zone`.
+    Cron { schedule: Box<cron::Schedule>, time_zone: Tz },
+}
+
+impl TimerSchedule {
+    /// Parses a cron expression of `sec min hour day-of-month month day-of-week [year]`;
+    /// `+` may stand in for spaces, as in endpoint uris.
+    pub fn cron(expression: &str, time_zone: Tz) -> Result<Self, DomainError> {
+        let expression = expression.replace('+', " ");
+        let schedule = cron::Schedule::from_str(&expression)
+            .map_err(|e| DomainError::invalid_field("cron", format!("invalid expression '{}': {}", expression, e)))?;
+        Ok(TimerSchedule::Cron {
+            schedule: Box::new(schedule),
+            time_zone,
+        })
+    }
+
+    /// The first firing strictly after `after`; `None` once a cron schedule has run out.
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            TimerSchedule::Period(period) => Some(after + *period),
+            TimerSchedule::Cron { schedule, time_zone } => schedule
+                .after(&after.with_timezone(time_zone))
+                .next()
+                .map(|next| next.with_timezone(&Utc)),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TimerConfig {
+    pub name: String,
+    pub schedule: TimerSchedule,
+    /// Wait before the first firing (or, for cron, before looking for it).
+    pub delay: Duration,
+    /// Firings before the timer stops; unlimited when 0.
+    pub repeat_count: u64,
+    /// Fire even while the previous firing is still being processed. Otherwise firings
+    /// that fall due meanwhile are skipped.
+    pub allow_overlap: bool,
+}
+
+/// Fires exchanges on a schedule, addressed as
+/// `timer:name?period=1000&delay=0&repeatCount=0&allowOverlap=false` or
+/// `timer:name?cron=0+0/5+*+*+*+*&timeZone=Europe/Berlin`.
+///
+/// Each exchange has an empty body and the `timer_*` headers. Timers can only be
+/// consumed from.
+pub struct TimerComponent;
+
+impl TimerComponent {
+    pub const DEFAULT_PERIOD: Duration = Duration::from_millis(1000);
+
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for TimerComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component for TimerComponent {
+    fn scheme(&self) -> &str {
+        "timer"
+    }
+
+    fn create_endpoint(&self, uri: &EndpointUri) -> Result<Arc<dyn Endpoint>, DomainError> {
+        let time_zone = uri.option_or("timeZone", Tz::UTC)?;
+        let schedule = match (uri.option::<u64>("period")?, uri.option_str("cron")) {
+            (Some(_), Some(_)) => {
+                return Err(DomainError::validation(format!(
+                    "Endpoint {} sets both period and cron",
+                    uri
+                )))
+            }
+            (None, Some(expression)) => TimerSchedule::cron(expression, time_zone)?,
+            (Some(0), None) => return Err(DomainError::invalid_field("period", "must be at least 1")),
+            (Some(period), None) => TimerSchedule::Period(Duration::from_millis(period)),
+            (None, None) => TimerSchedule::Period(Self::DEFAULT_PERIOD),
+        };
+
+        Ok(Arc::new(TimerEndpoint {
+            uri: uri.clone(),
+            config: TimerConfig {
+                name: uri.path.clone(),
+                schedule,
+                delay: Duration::from_millis(uri.option_or("delay", 0)?),
+                repeat_count: uri.option_or("repeatCount", 0)?,
+                allow_overlap: uri.option_or("allowOverlap", false)?,
+            },
+        }))
+    }
+}
+
+pub struct TimerEndpoint {
+    uri: EndpointUri,
+    config: TimerConfig,
+}
+
+impl Endpoint for TimerEndpoint {
+    fn uri(&self) -> &EndpointUri {
+        &self.uri
+    }
+
+    fn create_producer(&self) -> Result<Arc<dyn Producer>, DomainError> {
+        Err(DomainError::validation(format!(
+            "Endpoint {} fires exchanges and cannot be sent to",
+            self.uri
+        )))
+    }
+
+    fn create_consumer(&self, processor: Arc<dyn Processor>) -> Result<Box<dyn Consumer>, DomainError> {
+        Ok(Box::new(TimerConsumer {
+            config: self.config.clone(),
+            processor,
+            running: None,
+        }))
+    }
+}
+
+struct TimerConsumer {
+    config: TimerConfig,
+    processor: Arc<dyn Processor>,
+    /// Dropped to stop the timer.
+    running: Option<watch::Sender<()>>,
+}
+
+impl Consumer for TimerConsumer {
+    fn start(&mut self) -> Result<(), DomainError> {
+        if self.running.is_some() {
+            return Ok(());
+        }
+        let (running, stopped) = watch::channel(());
+        tokio::spawn(run(self.config.clone(), self.processor.clone(), stopped));
+        self.running = Some(running);
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), DomainError> {
+        self.running = None;
+        Ok(())
+    }
+}
+
+async fn run(config: TimerConfig, processor: Arc<dyn Processor>, mut stopped: watch::Receiver<()>) {
+    let started = Utc::now() + config.delay;
+    let first = match config.schedule {
+        TimerSchedule::Period(_) => Some(started),
+        TimerSchedule::Cron { .. } => config.schedule.next_after(started),
+    };
+    let Some(mut scheduled) = first else {
+        return;
+    };
+
+    let mut counter = 0;
+    loop {
+        let wait = (scheduled - Utc::now()).to_std().unwrap_or_default();
+        tokio::select! {
+            _ = stopped.changed() => return,
+            _ = tokio::time::sleep(wait) => {}
+        }
+
+        counter += 1;
+        let exchange = fire(&config, counter, scheduled);
+        if config.allow_overlap {
+            let processor = processor.clone();
+            let name = config.name.clone();
+            tokio::spawn(async move { report(&name, processor.process(exchange).await) });
+        } else {
+            report(&config.name, processor.process(exchange).await);
+        }
+        if config.repeat_count > 0 && counter >= config.repeat_count {
+            return;
+        }
+
+        // Firings that fell due while the last one was processed are skipped, not caught up
+        let now = Utc::now();
+        let mut next = config.schedule.next_after(scheduled);
+        while let Some(due) = next.filter(|due| *due <= now) {
+            next = config.schedule.next_after(due);
+        }
+        match next {
+            Some(next) => scheduled = next,
+            None => return,
+        }
+    }
+}
+
+fn fire(config: &TimerConfig, counter: u64, scheduled: DateTime<Utc>) -> Exchange {
+    let mut exchange = Exchange::new("");
+    exchange.metadata.source_system = "timer".to_string();
+    exchange.set_header(TIMER_NAME, config.name.as_str());
+    exchange.set_header(TIMER_COUNTER, counter);
+    exchange.set_header(TIMER_SCHEDULED_AT, scheduled);
+    exchange.set_header(TIMER_FIRED_AT, Utc::now());
+    exchange
+}
+
+fn report(name: &str, result: Result<Exchange, DomainError>) {
+    match result {
+        Ok(exchange) => debug!("Timer {} fired exchange {}", name, exchange.id),
+        Err(e) => warn!("Timer {} firing failed: {}", name, e),
+    }
+}
@@ -1,3 +1,4 @@
 // src/infrastructure/mod.rs
 pub mod repositories;
-pub mod adapters;
\ No newline at end of file
+pub mod adapters;
+pub mod components;
\ No newline at end of file
@@ -0,0 +1,144 @@
+use crate::domain::{
+    models::{aggregation::AggregationGroup, error::DomainError},
+    ports::aggregation::AggregationRepository,
+};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tokio::io::AsyncWriteExt;
+
+pub struct InMemoryAggregationRepository {
+    groups: Mutex<HashMap<String, AggregationGroup>>,
+}
+
+impl InMemoryAggregationRepository {
+    pub fn new() -> Self {
+        Self {
+            groups: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for InMemoryAggregationRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl AggregationRepository for InMemoryAggregationRepository {
+    async fn get(&self, correlation_key: &str) -> Result<Option<AggregationGroup>, DomainError> {
+        let groups = self.groups.lock().map_err(lock_error)?;
+        Ok(groups.get(correlation_key).cloned())
+    }
+
+    async fn save(&self, group: &AggregationGroup) -> Result<(), DomainError> {
+        let mut groups = self.groups.lock().map_err(lock_error)?;
+        groups.insert(group.correlation_key.clone(), group.clone());
+        Ok(())
+    }
+
+    async fn remove(&self, correlation_key: &str) -> Result<(), DomainError> {
+        let mut groups = self.groups.lock().map_err(lock_error)?;
+        groups.remove(correlation_key);
+        Ok(())
+    }
+
+    async fn find_all(&self) -> Result<Vec<AggregationGroup>, DomainError> {
+        let groups = self.groups.lock().map_err(lock_error)?;
+        Ok(groups.values().cloned().collect())
+    }
+}
+
+/// Keeps every group in a single JSON file, rewritten through a synced temp file and
+/// rename on each change so a crash never leaves a half-written snapshot behind. A
+/// change is only applied in memory once the file holds it.
+pub struct FileAggregationRepository {
+    path: PathBuf,
+    groups: tokio::sync::Mutex<HashMap<String, AggregationGroup>>,
+}
+
+impl FileAggregationRepository {
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self, DomainError> {
+        let path = path.as_ref().to_path_buf();
+        let groups = match tokio::fs::read(&path).await {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| {
+                DomainError::fatal(format!("Corrupt aggregation store {}", path.display()))
+                    .with_source(e)
+            })?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(io_error(&path, e)),
+        };
+
+        Ok(Self {
+            path,
+            groups: tokio::sync::Mutex::new(groups),
+        })
+    }
+
+    async fn persist(&self, groups: &HashMap<String, AggregationGroup>) -> Result<(), DomainError> {
+        let bytes = serde_json::to_vec(groups)
+            .map_err(|e| DomainError::fatal("Failed to encode aggregation groups").with_source(e))?;
+        let tmp = self.path.with_extension("tmp");
+        let mut file = tokio::fs::File::create(&tmp).await.map_err(|e| io_error(&tmp, e))?;
+        file.write_all(&bytes).await.map_err(|e| io_error(&tmp, e))?;
+        file.sync_all().await.map_err(|e| io_error(&tmp, e))?;
+        drop(file);
+        tokio::fs::rename(&tmp, &self.path)
+            .await
+            .map_err(|e| io_error(&self.path, e))?;
+
+        // The rename itself is only durable once the directory entry is synced
+        let parent = match self.path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        tokio::fs::File::open(parent)
+            .await
+            .map_err(|e| io_error(parent, e))?
+            .sync_all()
+            .await
+            .map_err(|e| io_error(parent, e))
+    }
+}
+
+#[async_trait]
+impl AggregationRepository for FileAggregationRepository {
+    async fn get(&self, correlation_key: &str) -> Result<Option<AggregationGroup>, DomainError> {
+        Ok(self.groups.lock().await.get(correlation_key).cloned())
+    }
+
+    async fn save(&self, group: &AggregationGroup) -> Result<(), DomainError> {
+        let mut groups = self.groups.lock().await;
+        let mut changed = groups.clone();
+        changed.insert(group.correlation_key.clone(), group.clone());
+        self.persist(&changed).await?;
+        *groups = changed;
+        Ok(())
+    }
+
+    async fn remove(&self, correlation_key: &str) -> Result<(), DomainError> {
+        let mut groups = self.groups.lock().await;
+        if !groups.contains_key(correlation_key) {
+            return Ok(());
+        }
+        let mut changed = groups.clone();
+        changed.remove(correlation_key);
+        self.persist(&changed).await?;
+        *groups = changed;
+        Ok(())
+    }
+
+    async fn find_all(&self) -> Result<Vec<AggregationGroup>, DomainError> {
+        Ok(self.groups.lock().await.values().cloned().collect())
+    }
+}
+
+fn lock_error<T>(e: std::sync::PoisonError<T>) -> DomainError {
+    DomainError::fatal(format!("Failed to acquire lock: {}", e))
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> DomainError {
+    DomainError::transient(format!("I/O error on {}", path.display())).with_source(e)
+}
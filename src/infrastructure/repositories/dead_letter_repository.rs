@@ -0,0 +1,65 @@
+use crate::domain::{
+    models::{dead_letter::DeadLetter, error::DomainError},
+    ports::dead_letter::DeadLetterRepository,
+};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+pub struct InMemoryDeadLetterRepository {
+    dead_letters: Mutex<HashMap<Uuid, DeadLetter>>,
+}
+
+impl InMemoryDeadLetterRepository {
+    pub fn new() -> Self {
+        Self {
+            dead_letters: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for InMemoryDeadLetterRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DeadLetterRepository for InMemoryDeadLetterRepository {
+    async fn save(&self, dead_letter: &DeadLetter) -> Result<(), DomainError> {
+        let mut dead_letters = self
+            .dead_letters
+            .lock()
+            .map_err(|e| DomainError::fatal(format!("Failed to acquire lock: {}", e)))?;
+        dead_letters.insert(dead_letter.exchange.id, dead_letter.clone());
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: &Uuid) -> Result<Option<DeadLetter>, DomainError> {
+        let dead_letters = self
+            .dead_letters
+            .lock()
+            .map_err(|e| DomainError::fatal(format!("Failed to acquire lock: {}", e)))?;
+        Ok(dead_letters.get(id).cloned())
+    }
+
+    async fn find_all(&self) -> Result<Vec<DeadLetter>, DomainError> {
+        let dead_letters = self
+            .dead_letters
+            .lock()
+            .map_err(|e| DomainError::fatal(format!("Failed to acquire lock: {}", e)))?;
+        let mut all: Vec<DeadLetter> = dead_letters.values().cloned().collect();
+        all.sort_by_key(|dead_letter| dead_letter.failed_at);
+        Ok(all)
+    }
+
+    async fn delete(&self, id: &Uuid) -> Result<(), DomainError> {
+        let mut dead_letters = self
+            .dead_letters
+            .lock()
+            .map_err(|e| DomainError::fatal(format!("Failed to acquire lock: {}", e)))?;
+        dead_letters.remove(id);
+        Ok(())
+    }
+}
@@ -0,0 +1,705 @@
+use crate::domain::{
+    models::{
+        error::DomainError,
+        exchange::Exchange,
+        query::{MessageQuery, Page},
+        revision::{surplus_revisions, ExchangeRevision, RevisionCause},
+    },
+    ports::repository::MessageRepository,
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+// Each record is framed as [payload length: u32 LE][crc32 of payload: u32 LE][payload]
+const HEADER_LEN: u64 = 8;
+const SEGMENT_PREFIX: &str = "segment-";
+const SEGMENT_SUFFIX: &str = ".log";
+
+/// When appended records are flushed to stable storage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FsyncPolicy {
+    /// Sync after every write; nothing acknowledged is ever lost.
+    Always,
+    /// Sync on the first write after `interval` has passed since the last sync.
+    Interval(Duration),
+    /// Leave flushing to the operating system.
+    Never,
+}
+
+#[derive(Clone, Debug)]
+pub struct FileLogConfig {
+    pub max_segment_bytes: u64,
+    pub fsync: FsyncPolicy,
+    /// Fraction of dead bytes (deleted or superseded records) that triggers background compaction.
+    pub compaction_threshold: f64,
+    /// Revisions kept per exchange; see `surplus_revisions`. Dropped revisions stay
+    /// on disk as dead bytes until compaction, and opening the log drops them again.
+    pub max_revisions: Option<usize>,
+}
+
+impl FileLogConfig {
+    pub fn max_segment_bytes(mut self, bytes: u64) -> Self {
+        self.max_segment_bytes = bytes.max(1);
+        self
+    }
+
+    pub fn fsync(mut self, policy: FsyncPolicy) -> Self {
+        self.fsync = policy;
+        self
+    }
+
+    pub fn compaction_threshold(mut self, threshold: f64) -> Self {
+        self.compaction_threshold = threshold.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn max_revisions(mut self, max: usize) -> Self {
+        self.max_revisions = Some(max.max(2));
+        self
+    }
+}
+
+impl Default for FileLogConfig {
+    fn default() -> Self {
+        Self {
+            max_segment_bytes: 64 * 1024 * 1024,
+            fsync: FsyncPolicy::Interval(Duration::from_secs(1)),
+            compaction_threshold: 0.5,
+            max_revisions: None,
+        }
+    }
+}
+
+/// Sizes reported by `FileLogMessageRepository::stats`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct FileLogStats {
+    pub segments: usize,
+    pub live_records: usize,
+    pub live_bytes: u64,
+    pub dead_bytes: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+enum LogRecord {
+    /// Written before revision chains were kept; still read back as a revision.
+    Put(Box<Exchange>),
+    Revision(Box<ExchangeRevision>),
+    Delete(Uuid),
+}
+
+#[derive(Clone, Copy)]
+struct Location {
+    segment: u64,
+    offset: u64,
+    len: u64,
+    version: u64,
+}
+
+struct Log {
+    dir: PathBuf,
+    config: FileLogConfig,
+    segments: BTreeMap<u64, u64>,
+    active: File,
+    active_id: u64,
+    // Every revision of each exchange, oldest first
+    index: HashMap<Uuid, Vec<Location>>,
+    /// Deleted exchanges whose records may still be in a segment; compaction
+    /// carries their tombstones over until those segments are gone.
+    deleted: HashSet<Uuid>,
+    dead_bytes: u64,
+    last_sync: Instant,
+}
+
+/// Durable message store for hosts without a database: every revision is appended
+/// to the active log segment and an in-memory index points at each exchange's
+/// revision records. Deleted chains stay on disk until compaction.
+pub struct FileLogMessageRepository {
+    log: Arc<Mutex<Log>>,
+}
+
+impl FileLogMessageRepository {
+    /// Opens the log in `dir`, replaying every segment to rebuild the index. A torn
+    /// record at the end of the newest segment (from a crash mid-write) is truncated
+    /// away. Older segments were synced before the log moved on, so damage there is
+    /// not a torn write and fails the open instead of discarding later records.
+    pub fn open(dir: impl AsRef<Path>, config: FileLogConfig) -> Result<Self, DomainError> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir).map_err(|e| io_error(&dir, e))?;
+
+        let mut segment_ids = Vec::new();
+        for entry in fs::read_dir(&dir).map_err(|e| io_error(&dir, e))? {
+            let entry = entry.map_err(|e| io_error(&dir, e))?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            if let Some(id) = parse_segment_name(&name) {
+                segment_ids.push(id);
+            } else if name.starts_with(SEGMENT_PREFIX) && name.ends_with(".tmp") {
+                // Left behind by a compaction that never reached its rename
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+        segment_ids.sort_unstable();
+
+        let mut segments = BTreeMap::new();
+        let mut index = HashMap::new();
+        let mut deleted = HashSet::new();
+        let mut dead_bytes = 0;
+        for id in &segment_ids {
+            let sealed = Some(id) != segment_ids.last();
+            let size = recover_segment(&dir, *id, sealed, &mut index, &mut deleted, &mut dead_bytes)?;
+            segments.insert(*id, size);
+        }
+        for chain in index.values_mut() {
+            dead_bytes += trim(chain, config.max_revisions);
+        }
+
+        let active_id = segment_ids.last().copied().unwrap_or(1);
+        segments.entry(active_id).or_insert(0);
+        let active = open_for_append(&segment_path(&dir, active_id))?;
+        info!(
+            "Opened message log {} with {} segments and {} exchanges",
+            dir.display(),
+            segments.len(),
+            index.len()
+        );
+
+        Ok(Self {
+            log: Arc::new(Mutex::new(Log {
+                dir,
+                config,
+                segments,
+                active,
+                active_id,
+                index,
+                deleted,
+                dead_bytes,
+                last_sync: Instant::now(),
+            })),
+        })
+    }
+
+    pub async fn stats(&self) -> Result<FileLogStats, DomainError> {
+        self.with(|log| Ok(log.stats())).await
+    }
+
+    /// Rewrites every live record into a fresh segment and removes the old ones.
+    pub async fn compact(&self) -> Result<FileLogStats, DomainError> {
+        self.with(|log| {
+            log.compact()?;
+            Ok(log.stats())
+        })
+        .await
+    }
+
+    /// Spawns a task that compacts whenever the dead byte ratio reaches the
+    /// configured threshold, checking every `interval`.
+    pub fn start_compaction(self: &Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        let repository = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let result = repository
+                    .with(|log| {
+                        if log.needs_compaction() {
+                            log.compact()?;
+                        }
+                        Ok(())
+                    })
+                    .await;
+                if let Err(e) = result {
+                    warn!("Message log compaction failed: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Runs `f` against the log on the blocking thread pool.
+    async fn with<T, F>(&self, f: F) -> Result<T, DomainError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Log) -> Result<T, DomainError> + Send + 'static,
+    {
+        let log = self.log.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut log = log
+                .lock()
+                .map_err(|e| DomainError::fatal(format!("Failed to acquire lock: {}", e)))?;
+            f(&mut log)
+        })
+        .await
+        .map_err(|e| DomainError::fatal("Message log task failed").with_source(e))?
+    }
+}
+
+impl Log {
+    fn append(&mut self, record: &LogRecord, version: u64) -> Result<Location, DomainError> {
+        if self.segments[&self.active_id] >= self.config.max_segment_bytes {
+            self.roll()?;
+        }
+
+        let frame = encode(record)?;
+        let offset = self.segments[&self.active_id];
+        let path = segment_path(&self.dir, self.active_id);
+        if let Err(e) = self.active.write_all(&frame) {
+            // Drop any partial frame so later appends are not stranded behind it
+            let _ = self.active.set_len(offset);
+            return Err(io_error(&path, e));
+        }
+
+        let sync = match self.config.fsync {
+            FsyncPolicy::Always => true,
+            FsyncPolicy::Interval(interval) => self.last_sync.elapsed() >= interval,
+            FsyncPolicy::Never => false,
+        };
+        if sync {
+            self.active.sync_data().map_err(|e| io_error(&path, e))?;
+            self.last_sync = Instant::now();
+        }
+
+        let len = frame.len() as u64;
+        *self.segments.entry(self.active_id).or_insert(0) += len;
+        Ok(Location {
+            segment: self.active_id,
+            offset,
+            len,
+            version,
+        })
+    }
+
+    fn roll(&mut self) -> Result<(), DomainError> {
+        let path = segment_path(&self.dir, self.active_id);
+        self.active.sync_all().map_err(|e| io_error(&path, e))?;
+        self.active_id += 1;
+        self.active = open_for_append(&segment_path(&self.dir, self.active_id))?;
+        self.segments.insert(self.active_id, 0);
+        Ok(())
+    }
+
+    fn read(&self, location: Location) -> Result<ExchangeRevision, DomainError> {
+        let path = segment_path(&self.dir, location.segment);
+        let mut file = File::open(&path).map_err(|e| io_error(&path, e))?;
+        read_revision(&mut file, &path, location)
+    }
+
+    /// The current revision of every exchange, with the segments holding them opened.
+    fn snapshot(&self) -> Result<Snapshot, DomainError> {
+        let locations: Vec<Location> = self.index.values().filter_map(|chain| chain.last()).copied().collect();
+        let mut segments = HashMap::new();
+        for location in &locations {
+            if let std::collections::hash_map::Entry::Vacant(entry) = segments.entry(location.segment) {
+                let path = segment_path(&self.dir, location.segment);
+                entry.insert(File::open(&path).map_err(|e| io_error(&path, e))?);
+            }
+        }
+        Ok(Snapshot {
+            dir: self.dir.clone(),
+            locations,
+            segments,
+        })
+    }
+
+    fn current(&self, id: &Uuid) -> Result<Option<Exchange>, DomainError> {
+        match self.index.get(id).and_then(|chain| chain.last()) {
+            Some(location) => Ok(Some(self.read(*location)?.exchange)),
+            None => Ok(None),
+        }
+    }
+
+    fn live_bytes(&self) -> u64 {
+        self.index.values().flatten().map(|location| location.len).sum()
+    }
+
+    fn stats(&self) -> FileLogStats {
+        FileLogStats {
+            segments: self.segments.len(),
+            live_records: self.index.len(),
+            live_bytes: self.live_bytes(),
+            dead_bytes: self.dead_bytes,
+        }
+    }
+
+    fn needs_compaction(&self) -> bool {
+        let total = self.live_bytes() + self.dead_bytes;
+        total > 0 && self.dead_bytes as f64 / total as f64 >= self.config.compaction_threshold
+    }
+
+    fn compact(&mut self) -> Result<(), DomainError> {
+        let compacted_id = self.active_id + 1;
+        let tmp = self.dir.join(format!("{}{:010}.tmp", SEGMENT_PREFIX, compacted_id));
+        let mut file = File::create(&tmp).map_err(|e| io_error(&tmp, e))?;
+
+        // Tombstones come first: if an old segment outlives the compaction, replaying
+        // it before this one must not bring back what was deleted
+        let mut offset = 0;
+        for id in &self.deleted {
+            let frame = encode(&LogRecord::Delete(*id))?;
+            file.write_all(&frame).map_err(|e| io_error(&tmp, e))?;
+            offset += frame.len() as u64;
+        }
+
+        let mut index = HashMap::with_capacity(self.index.len());
+        for (id, chain) in &self.index {
+            let mut compacted = Vec::with_capacity(chain.len());
+            for location in chain {
+                let frame = encode(&LogRecord::Revision(Box::new(self.read(*location)?)))?;
+                file.write_all(&frame).map_err(|e| io_error(&tmp, e))?;
+                let len = frame.len() as u64;
+                compacted.push(Location {
+                    segment: compacted_id,
+                    offset,
+                    len,
+                    version: location.version,
+                });
+                offset += len;
+            }
+            index.insert(*id, compacted);
+        }
+        file.sync_all().map_err(|e| io_error(&tmp, e))?;
+        drop(file);
+
+        // Once the rename lands, replaying old segments followed by the compacted
+        // one yields the same index, so a crash past this point loses nothing and
+        // restores nothing that was deleted
+        let path = segment_path(&self.dir, compacted_id);
+        fs::rename(&tmp, &path).map_err(|e| io_error(&path, e))?;
+        self.active = open_for_append(&path)?;
+        self.active_id = compacted_id;
+
+        // A segment that cannot be removed stays tracked, all dead, for the next compaction
+        let mut leftover = BTreeMap::new();
+        for (old, size) in &self.segments {
+            let old_path = segment_path(&self.dir, *old);
+            if let Err(e) = fs::remove_file(&old_path) {
+                warn!("Failed to remove compacted segment {}: {}", old_path.display(), e);
+                leftover.insert(*old, *size);
+            }
+        }
+        if leftover.is_empty() {
+            self.deleted.clear();
+        }
+        // The tombstones are left out of the dead bytes; the next compaction drops them
+        let reclaimed = self.dead_bytes.saturating_sub(leftover.values().sum());
+        self.dead_bytes = leftover.values().sum();
+        leftover.insert(compacted_id, offset);
+        self.segments = leftover;
+        self.index = index;
+        self.last_sync = Instant::now();
+        info!(
+            "Compacted message log {}: reclaimed {} bytes",
+            self.dir.display(),
+            reclaimed
+        );
+        Ok(())
+    }
+}
+
+/// Records to read without holding the log's lock. The segments are already open,
+/// so they stay readable even if a compaction removes them in the meantime.
+struct Snapshot {
+    dir: PathBuf,
+    locations: Vec<Location>,
+    segments: HashMap<u64, File>,
+}
+
+impl Snapshot {
+    fn read_all(mut self) -> Result<Vec<ExchangeRevision>, DomainError> {
+        let mut revisions = Vec::with_capacity(self.locations.len());
+        for location in &self.locations {
+            let path = segment_path(&self.dir, location.segment);
+            let file = self
+                .segments
+                .get_mut(&location.segment)
+                .ok_or_else(|| DomainError::fatal(format!("Segment {} is not open", path.display())))?;
+            revisions.push(read_revision(file, &path, *location)?);
+        }
+        Ok(revisions)
+    }
+}
+
+#[async_trait]
+impl MessageRepository for FileLogMessageRepository {
+    async fn save_revision(&self, exchange: &Exchange, cause: &RevisionCause) -> Result<Uuid, DomainError> {
+        let mut stored = exchange.clone();
+        let cause = cause.clone();
+        self.with(move |log| {
+            let id = stored.id;
+            let current = log.index.get(&id).and_then(|chain| chain.last());
+            stored.check_version(current.map(|location| location.version))?;
+            stored.version += 1;
+            let version = stored.version;
+            let revision = ExchangeRevision::new(stored, cause);
+            let location = log.append(&LogRecord::Revision(Box::new(revision)), version)?;
+            let chain = log.index.entry(id).or_default();
+            chain.push(location);
+            let trimmed = trim(chain, log.config.max_revisions);
+            log.dead_bytes += trimmed;
+            Ok(id)
+        })
+        .await
+    }
+
+    async fn find_by_id(&self, id: &Uuid) -> Result<Option<Exchange>, DomainError> {
+        let id = *id;
+        self.with(move |log| log.current(&id)).await
+    }
+
+    async fn delete(&self, id: &Uuid) -> Result<(), DomainError> {
+        let id = *id;
+        self.with(move |log| {
+            if let Some(chain) = log.index.remove(&id) {
+                let tombstone = log.append(&LogRecord::Delete(id), 0)?;
+                log.deleted.insert(id);
+                log.dead_bytes += chain.iter().map(|location| location.len).sum::<u64>() + tombstone.len;
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    async fn revisions(&self, id: &Uuid) -> Result<Vec<ExchangeRevision>, DomainError> {
+        let id = *id;
+        self.with(move |log| {
+            log.index
+                .get(&id)
+                .into_iter()
+                .flatten()
+                .map(|location| log.read(*location))
+                .collect()
+        })
+        .await
+    }
+
+    async fn query(&self, query: &MessageQuery) -> Result<Page<Exchange>, DomainError> {
+        let query = query.clone();
+        // Reading every record takes a while, so it happens after the lock is released
+        let snapshot = self.with(|log| log.snapshot()).await?;
+        tokio::task::spawn_blocking(move || {
+            let mut matching: Vec<Exchange> = snapshot
+                .read_all()?
+                .into_iter()
+                .filter(|revision| query.matches_revision(revision))
+                .map(|revision| revision.exchange)
+                .collect();
+            matching.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(a.id.cmp(&b.id)));
+
+            Ok(Page {
+                total: matching.len(),
+                items: matching
+                    .into_iter()
+                    .skip(query.offset)
+                    .take(query.limit)
+                    .collect(),
+                offset: query.offset,
+                limit: query.limit,
+            })
+        })
+        .await
+        .map_err(|e| DomainError::fatal("Message log task failed").with_source(e))?
+    }
+}
+
+/// Replays one segment into `index`. The newest segment is truncated after its last
+/// intact record; a damaged record in a `sealed` one is an error.
+fn recover_segment(
+    dir: &Path,
+    id: u64,
+    sealed: bool,
+    index: &mut HashMap<Uuid, Vec<Location>>,
+    deleted: &mut HashSet<Uuid>,
+    dead_bytes: &mut u64,
+) -> Result<u64, DomainError> {
+    let path = segment_path(dir, id);
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(&path)
+        .map_err(|e| io_error(&path, e))?;
+    let size = file.metadata().map_err(|e| io_error(&path, e))?.len();
+
+    let mut offset = 0;
+    loop {
+        let record = match read_record(&mut file) {
+            Ok(Some(record)) => record,
+            Ok(None) => break,
+            Err(e) if sealed => {
+                return Err(DomainError::fatal(format!(
+                    "Sealed segment {} is damaged at offset {}; restore it or move it aside to open the log",
+                    path.display(),
+                    offset
+                ))
+                .with_source(e))
+            }
+            Err(e) => {
+                warn!(
+                    "Discarding damaged tail of {} at offset {}: {}",
+                    path.display(),
+                    offset,
+                    e
+                );
+                break;
+            }
+        };
+
+        let (record, len) = record;
+        let exchange = match record {
+            LogRecord::Put(exchange) => *exchange,
+            LogRecord::Revision(revision) => revision.exchange,
+            LogRecord::Delete(id) => {
+                let chain = index.remove(&id).unwrap_or_default();
+                deleted.insert(id);
+                *dead_bytes += len + chain.iter().map(|location| location.len).sum::<u64>();
+                offset += len;
+                continue;
+            }
+        };
+
+        // A record at or below a version already seen supersedes it: either an
+        // unversioned legacy overwrite or a copy written by compaction
+        let chain = index.entry(exchange.id).or_default();
+        chain.retain(|location| {
+            let keep = location.version < exchange.version;
+            if !keep {
+                *dead_bytes += location.len;
+            }
+            keep
+        });
+        chain.push(Location {
+            segment: id,
+            offset,
+            len,
+            version: exchange.version,
+        });
+        offset += len;
+    }
+
+    if offset < size {
+        file.set_len(offset).map_err(|e| io_error(&path, e))?;
+        file.sync_all().map_err(|e| io_error(&path, e))?;
+    }
+    Ok(offset)
+}
+
+/// Drops the revisions past `max_revisions` from `chain`, returning their size.
+fn trim(chain: &mut Vec<Location>, max_revisions: Option<usize>) -> u64 {
+    match max_revisions {
+        Some(max) => chain.drain(surplus_revisions(chain.len(), max)).map(|location| location.len).sum(),
+        None => 0,
+    }
+}
+
+fn read_revision(file: &mut File, path: &Path, location: Location) -> Result<ExchangeRevision, DomainError> {
+    file.seek(SeekFrom::Start(location.offset))
+        .map_err(|e| io_error(path, e))?;
+    match read_record(file).map_err(|e| io_error(path, e))? {
+        Some((LogRecord::Revision(revision), _)) => Ok(*revision),
+        Some((LogRecord::Put(exchange), _)) => Ok(legacy_revision(*exchange)),
+        _ => Err(DomainError::fatal(format!(
+            "Corrupt record in {} at offset {}",
+            path.display(),
+            location.offset
+        ))),
+    }
+}
+
+// Records written before revisions existed carry no cause
+fn legacy_revision(exchange: Exchange) -> ExchangeRevision {
+    ExchangeRevision {
+        recorded_at: exchange.updated_at,
+        cause: RevisionCause::new("save"),
+        exchange,
+    }
+}
+
+/// Reads the record at the current position; `Ok(None)` at a clean end of file.
+fn read_record(file: &mut File) -> std::io::Result<Option<(LogRecord, u64)>> {
+    let mut header = [0u8; HEADER_LEN as usize];
+    let mut filled = 0;
+    while filled < header.len() {
+        match file.read(&mut header[filled..])? {
+            0 if filled == 0 => return Ok(None),
+            0 => return Err(std::io::ErrorKind::UnexpectedEof.into()),
+            n => filled += n,
+        }
+    }
+
+    let len = u32::from_le_bytes(header[..4].try_into().unwrap_or_default()) as usize;
+    let checksum = u32::from_le_bytes(header[4..].try_into().unwrap_or_default());
+    // The length is not covered by the checksum, so a damaged header must not decide
+    // how much to allocate; a record running past the end of the segment is torn
+    let remaining = file.metadata()?.len().saturating_sub(file.stream_position()?);
+    if len as u64 > remaining {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::UnexpectedEof,
+            format!("record of {} bytes runs past the end of the segment", len),
+        ));
+    }
+    let mut payload = vec![0u8; len];
+    file.read_exact(&mut payload)?;
+    if crc32(&payload) != checksum {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "checksum mismatch",
+        ));
+    }
+
+    let record = serde_json::from_slice(&payload)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    Ok(Some((record, HEADER_LEN + len as u64)))
+}
+
+fn encode(record: &LogRecord) -> Result<Vec<u8>, DomainError> {
+    let payload = serde_json::to_vec(record)
+        .map_err(|e| DomainError::fatal("Failed to encode log record").with_source(e))?;
+    let len = u32::try_from(payload.len())
+        .map_err(|_| DomainError::validation("Exchange is too large for the message log"))?;
+
+    let mut frame = Vec::with_capacity(HEADER_LEN as usize + payload.len());
+    frame.extend_from_slice(&len.to_le_bytes());
+    frame.extend_from_slice(&crc32(&payload).to_le_bytes());
+    frame.extend_from_slice(&payload);
+    Ok(frame)
+}
+
+// CRC-32 (IEEE 802.3), computed bitwise; records are small enough not to need a table
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn segment_path(dir: &Path, id: u64) -> PathBuf {
+    dir.join(format!("{}{:010}{}", SEGMENT_PREFIX, id, SEGMENT_SUFFIX))
+}
+
+fn parse_segment_name(name: &str) -> Option<u64> {
+    name.strip_prefix(SEGMENT_PREFIX)?
+        .strip_suffix(SEGMENT_SUFFIX)?
+        .parse()
+        .ok()
+}
+
+fn open_for_append(path: &Path) -> Result<File, DomainError> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| io_error(path, e))
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> DomainError {
+    DomainError::transient(format!("I/O error on {}", path.display())).with_source(e)
+}
@@ -1,5 +1,10 @@
 use crate::domain::{
-    models::{error::DomainError, exchange::Exchange},
+    models::{
+        error::DomainError,
+        exchange::Exchange,
+        query::{MessageQuery, Page},
+        revision::{surplus_revisions, ExchangeRevision, RevisionCause},
+    },
     ports::repository::MessageRepository,
 };
 use async_trait::async_trait;
@@ -7,26 +12,53 @@ use std::collections::HashMap;
 use std::sync::Mutex;
 use uuid::Uuid;
 
+/// Keeps each exchange's revision chain; the last revision is the current exchange.
 pub struct InMemoryMessageRepository {
-    messages: Mutex<HashMap<Uuid, Exchange>>,
+    messages: Mutex<HashMap<Uuid, Vec<ExchangeRevision>>>,
+    max_revisions: Option<usize>,
 }
 
 impl InMemoryMessageRepository {
     pub fn new() -> Self {
         Self {
             messages: Mutex::new(HashMap::new()),
+            max_revisions: None,
         }
     }
+
+    /// Caps each chain at `max` revisions; see `surplus_revisions`.
+    pub fn max_revisions(mut self, max: usize) -> Self {
+        self.max_revisions = Some(max.max(2));
+        self
+    }
+}
+
+impl Default for InMemoryMessageRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn current(revisions: &[ExchangeRevision]) -> Option<&Exchange> {
+    revisions.last().map(|revision| &revision.exchange)
 }
 
 #[async_trait]
 impl MessageRepository for InMemoryMessageRepository {
-    async fn save(&self, exchange: &Exchange) -> Result<Uuid, DomainError> {
+    async fn save_revision(&self, exchange: &Exchange, cause: &RevisionCause) -> Result<Uuid, DomainError> {
         let mut messages = self
             .messages
             .lock()
-            .map_err(|e| DomainError::RepositoryError(format!("Failed to acquire lock: {}", e)))?;
-        messages.insert(exchange.id, exchange.clone());
+            .map_err(|e| DomainError::fatal(format!("Failed to acquire lock: {}", e)))?;
+        // Checked before the entry exists, so a rejected save of an unknown id leaves nothing behind
+        exchange.check_version(messages.get(&exchange.id).and_then(|revisions| current(revisions)).map(|stored| stored.version))?;
+        let revisions = messages.entry(exchange.id).or_default();
+        let mut stored = exchange.clone();
+        stored.version += 1;
+        revisions.push(ExchangeRevision::new(stored, cause.clone()));
+        if let Some(max) = self.max_revisions {
+            revisions.drain(surplus_revisions(revisions.len(), max));
+        }
         Ok(exchange.id)
     }
 
@@ -34,16 +66,50 @@ impl MessageRepository for InMemoryMessageRepository {
         let messages = self
             .messages
             .lock()
-            .map_err(|e| DomainError::RepositoryError(format!("Failed to acquire lock: {}", e)))?;
-        Ok(messages.get(&id).cloned())
+            .map_err(|e| DomainError::fatal(format!("Failed to acquire lock: {}", e)))?;
+        Ok(messages.get(id).and_then(|revisions| current(revisions)).cloned())
     }
 
     async fn delete(&self, id: &Uuid) -> Result<(), DomainError> {
         let mut messages = self
             .messages
             .lock()
-            .map_err(|e| DomainError::RepositoryError(format!("Failed to acquire lock: {}", e)))?;
+            .map_err(|e| DomainError::fatal(format!("Failed to acquire lock: {}", e)))?;
         messages.remove(id);
         Ok(())
     }
+
+    async fn revisions(&self, id: &Uuid) -> Result<Vec<ExchangeRevision>, DomainError> {
+        let messages = self
+            .messages
+            .lock()
+            .map_err(|e| DomainError::fatal(format!("Failed to acquire lock: {}", e)))?;
+        Ok(messages.get(id).cloned().unwrap_or_default())
+    }
+
+    async fn query(&self, query: &MessageQuery) -> Result<Page<Exchange>, DomainError> {
+        let messages = self
+            .messages
+            .lock()
+            .map_err(|e| DomainError::fatal(format!("Failed to acquire lock: {}", e)))?;
+        let mut matching: Vec<&Exchange> = messages
+            .values()
+            .filter_map(|revisions| revisions.last())
+            .filter(|revision| query.matches_revision(revision))
+            .map(|revision| &revision.exchange)
+            .collect();
+        matching.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(a.id.cmp(&b.id)));
+
+        Ok(Page {
+            total: matching.len(),
+            items: matching
+                .into_iter()
+                .skip(query.offset)
+                .take(query.limit)
+                .cloned()
+                .collect(),
+            offset: query.offset,
+            limit: query.limit,
+        })
+    }
 }
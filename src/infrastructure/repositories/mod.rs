@@ -1 +1,5 @@
-pub mod message_repository;
\ No newline at end of file
+pub mod aggregation_repository;
+pub mod dead_letter_repository;
+pub mod file_log_repository;
+pub mod message_repository;
+pub mod sqlite_message_repository;
\ No newline at end of file
@@ -0,0 +1,438 @@
+use crate::domain::{
+    models::{
+        body::{Body, BodyEncoding},
+        error::DomainError,
+        exchange::{Exchange, ExchangeMetadata},
+        query::{MessageQuery, Page},
+        revision::{ExchangeRevision, RevisionCause},
+    },
+    ports::repository::MessageRepository,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{
+    params, params_from_iter, types::Value, Connection, OptionalExtension, Row, TransactionBehavior,
+};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Schema changes in the order they were introduced. Applied migrations are tracked
+/// in `PRAGMA user_version`, so only append to this list.
+const MIGRATIONS: &[&str] = &[
+    "CREATE TABLE messages (
+        id TEXT PRIMARY KEY NOT NULL,
+        body TEXT NOT NULL,
+        headers TEXT NOT NULL,
+        properties TEXT NOT NULL,
+        pattern TEXT NOT NULL,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL,
+        processing_history TEXT NOT NULL,
+        source_system TEXT NOT NULL,
+        correlation_id TEXT,
+        priority TEXT NOT NULL,
+        retry_count INTEGER NOT NULL,
+        success INTEGER NOT NULL
+    );
+    CREATE TABLE message_headers (
+        message_id TEXT NOT NULL REFERENCES messages(id) ON DELETE CASCADE,
+        name TEXT NOT NULL,
+        value TEXT NOT NULL,
+        PRIMARY KEY (message_id, name)
+    );
+    CREATE INDEX idx_messages_created_at ON messages(created_at);
+    CREATE INDEX idx_messages_source_system ON messages(source_system);
+    CREATE INDEX idx_messages_priority ON messages(priority);
+    CREATE INDEX idx_messages_success ON messages(success);
+    CREATE INDEX idx_message_headers_name_value ON message_headers(name, value);",
+    "ALTER TABLE messages ADD COLUMN version INTEGER NOT NULL DEFAULT 0;",
+    "CREATE TABLE message_revisions (
+        message_id TEXT NOT NULL,
+        version INTEGER NOT NULL,
+        exchange TEXT NOT NULL,
+        cause TEXT NOT NULL,
+        triggered_by TEXT,
+        recorded_at TEXT NOT NULL,
+        PRIMARY KEY (message_id, version)
+    );",
+    "ALTER TABLE messages ADD COLUMN body_encoding TEXT NOT NULL DEFAULT 'text';",
+    "ALTER TABLE messages ADD COLUMN out_message TEXT;",
+    "ALTER TABLE messages ADD COLUMN last_trigger TEXT;
+    UPDATE messages SET last_trigger = (
+        SELECT r.cause FROM message_revisions r WHERE r.message_id = messages.id
+        ORDER BY r.version DESC LIMIT 1
+    );
+    CREATE INDEX idx_messages_last_trigger ON messages(last_trigger);",
+];
+
+const COLUMNS: &str = "id, body, headers, properties, pattern, created_at, updated_at, \
+    processing_history, source_system, correlation_id, priority, retry_count, version, body_encoding, out_message";
+
+/// Stores exchanges in a SQLite database. Headers are also kept in a side table so
+/// header filters can use an index; everything else needed to rebuild the exchange
+/// lives on the `messages` row. Every saved version is also kept, as JSON, in
+/// `message_revisions`, up to `max_revisions` per exchange when set.
+pub struct SqliteMessageRepository {
+    connection: Arc<Mutex<Connection>>,
+    max_revisions: Option<usize>,
+}
+
+impl SqliteMessageRepository {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, DomainError> {
+        let path = path.as_ref();
+        let connection = Connection::open(path).map_err(|e| {
+            DomainError::fatal(format!("Failed to open SQLite store {}", path.display())).with_source(e)
+        })?;
+        Self::with_connection(connection)
+    }
+
+    pub fn in_memory() -> Result<Self, DomainError> {
+        let connection = Connection::open_in_memory()
+            .map_err(|e| DomainError::fatal("Failed to open in-memory SQLite store").with_source(e))?;
+        Self::with_connection(connection)
+    }
+
+    fn with_connection(mut connection: Connection) -> Result<Self, DomainError> {
+        connection
+            .execute_batch("PRAGMA foreign_keys = ON; PRAGMA journal_mode = WAL;")
+            .map_err(sql_error)?;
+        migrate(&mut connection)?;
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+            max_revisions: None,
+        })
+    }
+
+    /// Caps each chain at `max` revisions; see `surplus_revisions`.
+    pub fn max_revisions(mut self, max: usize) -> Self {
+        self.max_revisions = Some(max.max(2));
+        self
+    }
+
+    /// Runs `f` against the connection on the blocking thread pool.
+    async fn with<T, F>(&self, f: F) -> Result<T, DomainError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Connection) -> Result<T, DomainError> + Send + 'static,
+    {
+        let connection = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = connection
+                .lock()
+                .map_err(|e| DomainError::fatal(format!("Failed to acquire lock: {}", e)))?;
+            f(&mut connection)
+        })
+        .await
+        .map_err(|e| DomainError::fatal("SQLite task failed").with_source(e))?
+    }
+}
+
+fn migrate(connection: &mut Connection) -> Result<(), DomainError> {
+    let version: usize = connection
+        .query_row("PRAGMA user_version", [], |row| row.get(0))
+        .map_err(sql_error)?;
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        let tx = connection.transaction().map_err(sql_error)?;
+        tx.execute_batch(migration).map_err(sql_error)?;
+        tx.pragma_update(None, "user_version", index + 1).map_err(sql_error)?;
+        tx.commit().map_err(sql_error)?;
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl MessageRepository for SqliteMessageRepository {
+    async fn save_revision(&self, exchange: &Exchange, cause: &RevisionCause) -> Result<Uuid, DomainError> {
+        let exchange = exchange.clone();
+        let cause = cause.clone();
+        let max_revisions = self.max_revisions;
+        self.with(move |connection| {
+            // IMMEDIATE takes the write lock up front so the version check cannot race
+            let tx = connection
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .map_err(sql_error)?;
+            let id = exchange.id.to_string();
+            let stored: Option<u64> = tx
+                .query_row("SELECT version FROM messages WHERE id = ?1", params![id], |row| row.get(0))
+                .optional()
+                .map_err(sql_error)?;
+            exchange.check_version(stored)?;
+
+            tx.execute(
+                &format!(
+                    "INSERT OR REPLACE INTO messages ({}, success, last_trigger)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
+                    COLUMNS
+                ),
+                params![
+                    id,
+                    exchange.body.encode()?,
+                    to_json(&exchange.headers)?,
+                    to_json(&exchange.properties)?,
+                    to_json(&exchange.pattern)?,
+                    timestamp(&exchange.created_at),
+                    timestamp(&exchange.updated_at),
+                    to_json(&exchange.processing_history)?,
+                    exchange.metadata.source_system,
+                    exchange.metadata.correlation_id,
+                    exchange.metadata.priority,
+                    exchange.metadata.retry_count,
+                    exchange.version + 1,
+                    exchange.body.encoding()?.as_str(),
+                    exchange.out.as_ref().map(to_json).transpose()?,
+                    exchange.processing_history.iter().all(|step| step.success),
+                    cause.trigger,
+                ],
+            )
+            .map_err(sql_error)?;
+
+            tx.execute("DELETE FROM message_headers WHERE message_id = ?1", params![id])
+                .map_err(sql_error)?;
+            {
+                let mut insert = tx
+                    .prepare("INSERT INTO message_headers (message_id, name, value) VALUES (?1, ?2, ?3)")
+                    .map_err(sql_error)?;
+                for (name, value) in &exchange.headers {
+                    insert.execute(params![id, name, value.to_string()]).map_err(sql_error)?;
+                }
+            }
+
+            let mut stored = exchange.clone();
+            stored.version += 1;
+            let revision = ExchangeRevision::new(stored, cause);
+            tx.execute(
+                "INSERT INTO message_revisions (message_id, version, exchange, cause, triggered_by, recorded_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    id,
+                    revision.version(),
+                    to_json(&revision.exchange)?,
+                    revision.cause.trigger,
+                    revision.cause.triggered_by,
+                    timestamp(&revision.recorded_at),
+                ],
+            )
+            .map_err(sql_error)?;
+            if let Some(max) = max_revisions {
+                // Keeps the first revision and the newest `max - 1`, as `surplus_revisions` does
+                tx.execute(
+                    "DELETE FROM message_revisions WHERE message_id = ?1 AND version < ?2
+                     AND version > (SELECT MIN(version) FROM message_revisions WHERE message_id = ?1)",
+                    params![id, (revision.version() + 2).saturating_sub(max as u64)],
+                )
+                .map_err(sql_error)?;
+            }
+            tx.commit().map_err(sql_error)?;
+            Ok(exchange.id)
+        })
+        .await
+    }
+
+    async fn find_by_id(&self, id: &Uuid) -> Result<Option<Exchange>, DomainError> {
+        let id = id.to_string();
+        self.with(move |connection| {
+            connection
+                .query_row(
+                    &format!("SELECT {} FROM messages WHERE id = ?1", COLUMNS),
+                    params![id],
+                    |row| Ok(read_exchange(row)),
+                )
+                .optional()
+                .map_err(sql_error)?
+                .transpose()
+        })
+        .await
+    }
+
+    async fn delete(&self, id: &Uuid) -> Result<(), DomainError> {
+        let id = id.to_string();
+        self.with(move |connection| {
+            let tx = connection.transaction().map_err(sql_error)?;
+            tx.execute("DELETE FROM message_revisions WHERE message_id = ?1", params![id])
+                .map_err(sql_error)?;
+            tx.execute("DELETE FROM messages WHERE id = ?1", params![id])
+                .map_err(sql_error)?;
+            tx.commit().map_err(sql_error)
+        })
+        .await
+    }
+
+    async fn revisions(&self, id: &Uuid) -> Result<Vec<ExchangeRevision>, DomainError> {
+        let id = id.to_string();
+        self.with(move |connection| {
+            let mut statement = connection
+                .prepare(
+                    "SELECT exchange, cause, triggered_by, recorded_at FROM message_revisions
+                     WHERE message_id = ?1 ORDER BY version",
+                )
+                .map_err(sql_error)?;
+            let revisions = statement
+                .query_map(params![id], |row| Ok(read_revision(row)))
+                .map_err(sql_error)?
+                .map(|row| row.map_err(sql_error)?)
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(revisions)
+        })
+        .await
+    }
+
+    async fn query(&self, query: &MessageQuery) -> Result<Page<Exchange>, DomainError> {
+        let query = query.clone();
+        self.with(move |connection| {
+            let (filter, values) = where_clause(&query);
+
+            let total: i64 = connection
+                .query_row(
+                    &format!("SELECT COUNT(*) FROM messages m {}", filter),
+                    params_from_iter(values.iter()),
+                    |row| row.get(0),
+                )
+                .map_err(sql_error)?;
+
+            let mut statement = connection
+                .prepare(&format!(
+                    "SELECT {} FROM messages m {} ORDER BY created_at DESC, id ASC LIMIT {} OFFSET {}",
+                    COLUMNS, filter, query.limit, query.offset
+                ))
+                .map_err(sql_error)?;
+            let items = statement
+                .query_map(params_from_iter(values.iter()), |row| Ok(read_exchange(row)))
+                .map_err(sql_error)?
+                .map(|row| row.map_err(sql_error)?)
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(Page {
+                items,
+                total: total as usize,
+                offset: query.offset,
+                limit: query.limit,
+            })
+        })
+        .await
+    }
+}
+
+fn where_clause(query: &MessageQuery) -> (String, Vec<Value>) {
+    let mut conditions = Vec::new();
+    let mut values = Vec::new();
+
+    for (name, value) in &query.headers {
+        conditions.push(
+            "EXISTS (SELECT 1 FROM message_headers h WHERE h.message_id = m.id AND h.name = ? AND h.value = ?)",
+        );
+        values.push(Value::Text(name.clone()));
+        values.push(Value::Text(value.clone()));
+    }
+    if let Some(after) = &query.created_after {
+        conditions.push("m.created_at >= ?");
+        values.push(Value::Text(timestamp(after)));
+    }
+    if let Some(before) = &query.created_before {
+        conditions.push("m.created_at < ?");
+        values.push(Value::Text(timestamp(before)));
+    }
+    if let Some(source_system) = &query.source_system {
+        conditions.push("m.source_system = ?");
+        values.push(Value::Text(source_system.clone()));
+    }
+    if let Some(priority) = &query.priority {
+        conditions.push("m.priority = ?");
+        values.push(Value::Text(priority.clone()));
+    }
+    if let Some(success) = query.success {
+        conditions.push("m.success = ?");
+        values.push(Value::Integer(success as i64));
+    }
+    if let Some(trigger) = &query.trigger {
+        conditions.push("m.last_trigger = ?");
+        values.push(Value::Text(trigger.clone()));
+    }
+
+    if conditions.is_empty() {
+        (String::new(), values)
+    } else {
+        (format!("WHERE {}", conditions.join(" AND ")), values)
+    }
+}
+
+fn read_exchange(row: &Row<'_>) -> Result<Exchange, DomainError> {
+    let text = |index: usize| row.get::<_, String>(index).map_err(sql_error);
+    let id = text(0)?;
+
+    Ok(Exchange {
+        id: Uuid::parse_str(&id)
+            .map_err(|e| DomainError::fatal(format!("Corrupt message id {}", id)).with_source(e))?,
+        body: read_body(&text(1)?, &text(13)?)?,
+        headers: from_json(&text(2)?)?,
+        properties: from_json(&text(3)?)?,
+        pattern: from_json(&text(4)?)?,
+        out: row
+            .get::<_, Option<String>>(14)
+            .map_err(sql_error)?
+            .map(|out| from_json(&out))
+            .transpose()?,
+        created_at: parse_timestamp(&text(5)?)?,
+        updated_at: parse_timestamp(&text(6)?)?,
+        processing_history: from_json(&text(7)?)?,
+        metadata: ExchangeMetadata {
+            source_system: text(8)?,
+            correlation_id: row.get(9).map_err(sql_error)?,
+            priority: text(10)?,
+            retry_count: row.get(11).map_err(sql_error)?,
+        },
+        version: row.get(12).map_err(sql_error)?,
+    })
+}
+
+fn read_body(content: &str, encoding: &str) -> Result<Body, DomainError> {
+    let encoding = BodyEncoding::parse(encoding)
+        .ok_or_else(|| DomainError::fatal(format!("Unknown body encoding '{}'", encoding)))?;
+    Body::decode(content, encoding)
+}
+
+fn read_revision(row: &Row<'_>) -> Result<ExchangeRevision, DomainError> {
+    let text = |index: usize| row.get::<_, String>(index).map_err(sql_error);
+    Ok(ExchangeRevision {
+        exchange: from_json(&text(0)?)?,
+        cause: RevisionCause {
+            trigger: text(1)?,
+            triggered_by: row.get(2).map_err(sql_error)?,
+        },
+        recorded_at: parse_timestamp(&text(3)?)?,
+    })
+}
+
+// Fixed-width UTC timestamps sort the same as text and as instants
+fn timestamp(value: &DateTime<Utc>) -> String {
+    value.to_rfc3339_opts(SecondsFormat::Nanos, true)
+}
+
+fn parse_timestamp(value: &str) -> Result<DateTime<Utc>, DomainError> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|value| value.with_timezone(&Utc))
+        .map_err(|e| DomainError::fatal(format!("Corrupt timestamp '{}'", value)).with_source(e))
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String, DomainError> {
+    serde_json::to_string(value).map_err(|e| DomainError::fatal("Failed to encode exchange").with_source(e))
+}
+
+fn from_json<T: serde::de::DeserializeOwned>(value: &str) -> Result<T, DomainError> {
+    serde_json::from_str(value).map_err(|e| DomainError::fatal("Corrupt stored exchange").with_source(e))
+}
+
+fn sql_error(e: rusqlite::Error) -> DomainError {
+    match e {
+        rusqlite::Error::SqliteFailure(ref failure, _)
+            if matches!(
+                failure.code,
+                rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked
+            ) =>
+        {
+            DomainError::transient("SQLite database is busy").with_source(e)
+        }
+        e => DomainError::fatal("SQLite error").with_source(e),
+    }
+}
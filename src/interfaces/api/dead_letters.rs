@@ -0,0 +1,36 @@
+use crate::interfaces::api::error::{error_response, parse_uuid};
+use crate::interfaces::api::rest::{AppState, MessageResponse};
+use actix_web::{web, HttpResponse, Responder};
+use tracing::info;
+
+pub async fn list_dead_letters(state: web::Data<AppState>) -> impl Responder {
+    match state.dead_letter_service.list().await {
+        Ok(dead_letters) => HttpResponse::Ok().json(dead_letters),
+        Err(e) => error_response(&e),
+    }
+}
+
+pub async fn replay_dead_letter(state: web::Data<AppState>, path: web::Path<String>) -> HttpResponse {
+    let id = match parse_uuid("id", &path.into_inner()) {
+        Ok(id) => id,
+        Err(e) => return error_response(&e),
+    };
+    info!("Received request to replay dead letter {}", id);
+
+    match state.dead_letter_service.replay(&id).await {
+        Ok(exchange) => HttpResponse::Ok().json(MessageResponse::from(exchange)),
+        Err(e) => error_response(&e),
+    }
+}
+
+pub async fn delete_dead_letter(state: web::Data<AppState>, path: web::Path<String>) -> HttpResponse {
+    let id = match parse_uuid("id", &path.into_inner()) {
+        Ok(id) => id,
+        Err(e) => return error_response(&e),
+    };
+
+    match state.dead_letter_service.discard(&id).await {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(e) => error_response(&e),
+    }
+}
@@ -0,0 +1,130 @@
+use crate::domain::models::error::{DomainError, ErrorKind, FieldError};
+use actix_web::{
+    error::{InternalError, JsonPayloadError, PayloadError},
+    http::StatusCode,
+    web, HttpResponse,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+pub fn status_for(kind: ErrorKind) -> StatusCode {
+    match kind {
+        ErrorKind::NotFound => StatusCode::NOT_FOUND,
+        ErrorKind::Filtered => StatusCode::UNPROCESSABLE_ENTITY,
+        ErrorKind::Validation => StatusCode::BAD_REQUEST,
+        ErrorKind::Conflict => StatusCode::CONFLICT,
+        ErrorKind::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+        ErrorKind::Timeout => StatusCode::GATEWAY_TIMEOUT,
+        ErrorKind::Transient => StatusCode::SERVICE_UNAVAILABLE,
+        ErrorKind::Fatal => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+fn problem_type(kind: ErrorKind) -> (&'static str, &'static str) {
+    match kind {
+        ErrorKind::NotFound => ("not-found", "Resource not found"),
+        ErrorKind::Filtered => ("filtered", "Message filtered out"),
+        ErrorKind::Validation => ("validation", "Invalid request"),
+        ErrorKind::Conflict => ("conflict", "Conflicting state"),
+        ErrorKind::RateLimited => ("rate-limited", "Too many requests"),
+        ErrorKind::Timeout => ("timeout", "Processing timed out"),
+        ErrorKind::Transient => ("unavailable", "Temporarily unavailable"),
+        ErrorKind::Fatal => ("internal", "Internal error"),
+    }
+}
+
+/// An RFC 7807 problem document, extended with the exchange and processor that failed.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProblemDetails {
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exchange_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub processor: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldError>,
+}
+
+impl ProblemDetails {
+    pub fn from_error(e: &DomainError) -> Self {
+        let kind = e.kind();
+        let (slug, title) = problem_type(kind);
+        Self {
+            problem_type: format!("urn:rust-camel:problem:{}", slug),
+            title: title.to_string(),
+            status: status_for(kind).as_u16(),
+            detail: e.message().to_string(),
+            exchange_id: e.exchange_id().map(|id| id.to_string()),
+            processor: e.processor().map(str::to_string),
+            errors: e.fields().to_vec(),
+        }
+    }
+}
+
+pub fn error_response(e: &DomainError) -> HttpResponse {
+    let problem = ProblemDetails::from_error(e);
+    let mut response = HttpResponse::build(status_for(e.kind()));
+    response.content_type(PROBLEM_JSON);
+    if let Some(retry_after) = e.retry_after() {
+        response.insert_header(("Retry-After", retry_after.as_secs().max(1).to_string()));
+    }
+    response.json(problem)
+}
+
+/// Parses a uuid taken from a path or body field, naming the field on failure.
+pub fn parse_uuid(field: &str, value: &str) -> Result<Uuid, DomainError> {
+    Uuid::parse_str(value).map_err(|_| DomainError::invalid_field(field, "must be a valid UUID"))
+}
+
+/// Largest request body accepted, whether it is read as JSON or as raw content.
+pub const MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// JSON extractor config that reports malformed bodies as problem documents,
+/// and bodies over `MAX_BODY_BYTES` with `payload_too_large`.
+pub fn json_config() -> web::JsonConfig {
+    web::JsonConfig::default().limit(MAX_BODY_BYTES).error_handler(|err, _req| {
+        let response = match &err {
+            JsonPayloadError::Overflow { .. }
+            | JsonPayloadError::OverflowKnownLength { .. }
+            | JsonPayloadError::Payload(PayloadError::Overflow) => payload_too_large(),
+            _ => error_response(&DomainError::invalid_field("body", err.to_string())),
+        };
+        InternalError::from_response(err, response).into()
+    })
+}
+
+/// Raw body extractor config with the same limit as JSON bodies. It has no error
+/// handler hook, so handlers take `Result<web::Bytes, actix_web::Error>` and answer
+/// failures with `payload_error`.
+pub fn payload_config() -> web::PayloadConfig {
+    web::PayloadConfig::new(MAX_BODY_BYTES)
+}
+
+/// Reports an oversized or unreadable raw body as a problem document, as `json_config` does.
+pub fn payload_error(err: &actix_web::Error) -> HttpResponse {
+    match err.as_error::<PayloadError>() {
+        Some(PayloadError::Overflow) => payload_too_large(),
+        _ => error_response(&DomainError::invalid_field("body", err.to_string())),
+    }
+}
+
+/// 413 for a body over `MAX_BODY_BYTES`. The request never reaches the domain,
+/// so this problem type has no `ErrorKind`.
+fn payload_too_large() -> HttpResponse {
+    let status = StatusCode::PAYLOAD_TOO_LARGE;
+    HttpResponse::build(status).content_type(PROBLEM_JSON).json(ProblemDetails {
+        problem_type: "urn:rust-camel:problem:payload-too-large".to_string(),
+        title: "Payload too large".to_string(),
+        status: status.as_u16(),
+        detail: format!("Request body exceeds the limit of {} bytes", MAX_BODY_BYTES),
+        exchange_id: None,
+        processor: None,
+        errors: Vec::new(),
+    })
+}
@@ -1,16 +1,21 @@
-use actix_web::{HttpResponse, Responder};
+use crate::domain::models::endpoint::QueueStats;
+use crate::interfaces::api::rest::AppState;
+use actix_web::{web, HttpResponse, Responder};
 use serde::Serialize;
 
 #[derive(Serialize)]
 pub struct HealthResponse {
     status: String,
     version: String,
+    queues: Vec<QueueStats>,
 }
 
-pub async fn health_check() -> impl Responder {
+pub async fn health_check(state: web::Data<AppState>) -> impl Responder {
     let response = HealthResponse {
         status: "ok".to_string(),
         version: env!("CARGO_PKG_VERSION").to_string(),
+        // Queue depth is informational; a context that cannot report it is still up
+        queues: state.context.queue_stats().unwrap_or_default(),
     };
     HttpResponse::Ok().json(response)
 }
@@ -0,0 +1,25 @@
+use crate::application::services::retention_service::RetentionMetrics;
+use crate::domain::models::endpoint::QueueStats;
+use crate::interfaces::api::error::error_response;
+use crate::interfaces::api::rest::AppState;
+use actix_web::{web, HttpResponse};
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct MetricsResponse {
+    retention: RetentionMetrics,
+    queues: Vec<QueueStats>,
+}
+
+pub async fn metrics(state: web::Data<AppState>) -> HttpResponse {
+    let metrics = state.retention_service.metrics().and_then(|retention| {
+        Ok(MetricsResponse {
+            retention,
+            queues: state.context.queue_stats()?,
+        })
+    });
+    match metrics {
+        Ok(metrics) => HttpResponse::Ok().json(metrics),
+        Err(e) => error_response(&e),
+    }
+}
@@ -1,2 +1,7 @@
 pub mod rest;
-pub mod health;
\ No newline at end of file
+pub mod dead_letters;
+pub mod error;
+pub mod health;
+pub mod metrics;
+pub mod replays;
+pub mod routes;
\ No newline at end of file
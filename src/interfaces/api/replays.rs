@@ -0,0 +1,128 @@
+use crate::domain::models::{
+    error::DomainError,
+    query::MessageQuery,
+    replay::{ReplayRequest, ReplaySelection},
+};
+use crate::interfaces::api::error::{error_response, parse_uuid};
+use crate::interfaces::api::rest::AppState;
+use actix_web::{http::header, web, HttpRequest, HttpResponse};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::info;
+
+/// Body of `POST /replays`: exactly one of `ids` or `filter` selects the messages.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReplayJobRequest {
+    pub(crate) route_id: String,
+    pub(crate) ids: Option<Vec<String>>,
+    pub(crate) filter: Option<ReplayFilter>,
+    /// Maximum messages replayed per second.
+    pub(crate) rate_limit: Option<f64>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReplayFilter {
+    pub(crate) created_after: Option<DateTime<Utc>>,
+    pub(crate) created_before: Option<DateTime<Utc>>,
+    /// Only messages whose latest run failed, i.e. whose current revision is "failed".
+    #[serde(default)]
+    pub(crate) failed_only: bool,
+    pub(crate) source_system: Option<String>,
+    pub(crate) priority: Option<String>,
+    #[serde(default)]
+    pub(crate) headers: HashMap<String, String>,
+}
+
+impl From<ReplayFilter> for MessageQuery {
+    fn from(filter: ReplayFilter) -> Self {
+        let mut query = MessageQuery::new().created_between(filter.created_after, filter.created_before);
+        query.headers = filter.headers;
+        query.source_system = filter.source_system;
+        query.priority = filter.priority;
+        if filter.failed_only {
+            query = query.trigger("failed");
+        }
+        query
+    }
+}
+
+/// `POST /replays`: starts a background replay and answers 202 with the job,
+/// whose progress is then available at the `Location` it returns.
+pub async fn start_replay(
+    state: web::Data<AppState>,
+    http: HttpRequest,
+    req: web::Json<ReplayJobRequest>,
+) -> HttpResponse {
+    let req = req.into_inner();
+    let selection = match (req.ids, req.filter) {
+        (Some(ids), None) => match ids
+            .iter()
+            .map(|id| parse_uuid("ids", id))
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(ids) => ReplaySelection::Ids(ids),
+            Err(e) => return error_response(&e),
+        },
+        (None, Some(filter)) => ReplaySelection::Query(filter.into()),
+        _ => {
+            return error_response(&DomainError::validation(
+                "Exactly one of 'ids' or 'filter' must be given",
+            ))
+        }
+    };
+
+    let mut request = ReplayRequest::new(selection, &req.route_id).triggered_by(
+        http.headers()
+            .get("X-Triggered-By")
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string),
+    );
+    if let Some(rate_limit) = req.rate_limit {
+        request = request.rate_limit(rate_limit);
+    }
+
+    match state.replay_service.start(request).await {
+        Ok(job) => {
+            info!("Accepted replay {} of {} messages", job.id, job.total);
+            HttpResponse::Accepted()
+                .insert_header((header::LOCATION, format!("/api/replays/{}", job.id)))
+                .json(job)
+        }
+        Err(e) => error_response(&e),
+    }
+}
+
+pub async fn list_replays(state: web::Data<AppState>) -> HttpResponse {
+    match state.replay_service.jobs() {
+        Ok(jobs) => HttpResponse::Ok().json(jobs),
+        Err(e) => error_response(&e),
+    }
+}
+
+pub async fn get_replay(state: web::Data<AppState>, path: web::Path<String>) -> HttpResponse {
+    let id = match parse_uuid("id", &path.into_inner()) {
+        Ok(id) => id,
+        Err(e) => return error_response(&e),
+    };
+
+    match state.replay_service.job(&id) {
+        Ok(job) => HttpResponse::Ok().json(job),
+        Err(e) => error_response(&e),
+    }
+}
+
+pub async fn cancel_replay(state: web::Data<AppState>, path: web::Path<String>) -> HttpResponse {
+    let id = match parse_uuid("id", &path.into_inner()) {
+        Ok(id) => id,
+        Err(e) => return error_response(&e),
+    };
+    info!("Received request to cancel replay {}", id);
+
+    match state.replay_service.cancel(&id) {
+        Ok(job) => HttpResponse::Ok().json(job),
+        Err(e) => error_response(&e),
+    }
+}
@@ -1,14 +1,67 @@
-use crate::application::services::message_service::MessageService;
-use crate::domain::models::error::DomainError;
-use crate::domain::models::exchange::{Exchange, ExchangeMetadata, ProcessingStep};
-use actix_web::{web, HttpResponse, Responder};
+use crate::application::context::CamelContext;
+use crate::application::services::{
+    dead_letter_service::DeadLetterService, ingestion_service::IngestionService,
+    message_service::MessageService, replay_service::ReplayService,
+    retention_service::RetentionService,
+};
+use crate::interfaces::api::error::{error_response, parse_uuid, payload_error};
+use crate::domain::models::{
+    body::{Body, BodyEncoding, CONTENT_TYPE},
+    error::DomainError,
+    exchange::{Exchange, ExchangeMetadata, ExchangePattern, Message, ProcessingStep},
+    header::HeaderValue,
+    query::MessageQuery,
+    revision::RevisionDiff,
+};
+use actix_web::{
+    http::header::{self, EntityTag},
+    web, HttpMessage, HttpRequest, HttpResponse, HttpResponseBuilder, Responder,
+};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::str::FromStr;
 use std::sync::Arc;
 use tracing::info;
 
+/// A message body in a JSON request. Strings are text unless `encoding` says
+/// `base64`; any other JSON value is taken as a JSON body.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MessageRequest {
-    pub(crate) body: String,
+    pub(crate) body: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) encoding: Option<BodyEncoding>,
+    /// Overrides the `X-Exchange-Pattern` header.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) pattern: Option<ExchangePattern>,
+}
+
+impl MessageRequest {
+    pub fn text(body: &str) -> Self {
+        Self {
+            body: Value::String(body.to_string()),
+            encoding: None,
+            pattern: None,
+        }
+    }
+
+    pub fn pattern(mut self, pattern: ExchangePattern) -> Self {
+        self.pattern = Some(pattern);
+        self
+    }
+
+    pub fn into_body(self) -> Result<Body, DomainError> {
+        match (self.body, self.encoding) {
+            (Value::String(text), None) => Ok(Body::Text(text)),
+            (body, None | Some(BodyEncoding::Json)) => Ok(Body::Json(body)),
+            (Value::String(content), Some(encoding)) => Body::decode(&content, encoding),
+            (_, Some(encoding)) => Err(DomainError::invalid_field(
+                "body",
+                format!("must be a string for encoding '{}'", encoding.as_str()),
+            )),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -20,63 +73,150 @@ pub struct ProcessMessageRequest {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MessageResponse {
     id: String,
-    body: String,
+    /// Text, base64 or a JSON value, as told by `encoding`.
+    body: Value,
+    encoding: BodyEncoding,
     headers: std::collections::HashMap<String, String>,
     created_at: String,
     updated_at: String,
     processing_history: Vec<ProcessingStep>,
     metadata: ExchangeMetadata,
+    version: u64,
 }
 
 impl From<Exchange> for MessageResponse {
     fn from(exchange: Exchange) -> Self {
+        let encoding = exchange.body.encoding().unwrap_or(BodyEncoding::Text);
+        let body = match exchange.body {
+            Body::Json(value) => value,
+            other => Value::String(other.encode().unwrap_or_else(|_| other.to_string())),
+        };
         MessageResponse {
             id: exchange.id.to_string(),
-            body: exchange.body,
-            headers: exchange.headers,
+            body,
+            encoding,
+            // Typed values render as the strings headers always were in responses
+            headers: exchange
+                .headers
+                .into_iter()
+                .map(|(name, value)| (name, value.to_string()))
+                .collect(),
             created_at: exchange.created_at.to_rfc3339(),
             updated_at: exchange.updated_at.to_rfc3339(),
             processing_history: exchange.processing_history,
             metadata: exchange.metadata,
+            version: exchange.version,
         }
     }
 }
 
 pub struct AppState {
     pub message_service: Arc<MessageService>,
+    pub context: Arc<CamelContext>,
+    pub dead_letter_service: Arc<DeadLetterService>,
+    pub retention_service: Arc<RetentionService>,
+    pub replay_service: Arc<ReplayService>,
+    pub ingestion_service: Arc<IngestionService>,
 }
 
+/// Accepts a JSON `MessageRequest`, or any other content type as the raw body;
+/// the raw content type is kept in the `content_type` header.
+///
+/// `InOnly` messages (the default) are acknowledged with 202 and the stored
+/// message; `InOut` messages get the reply as raw content, as `get_message_body`
+/// serves bodies. Either way `Location` points at the stored message.
+///
+/// With `Prefer: respond-async` the message is only stored and queued: the
+/// answer is 202 with its ingestion status, and `Location` points at
+/// `get_message_status` to poll.
 pub async fn create_message(
     state: web::Data<AppState>,
-    message: web::Json<MessageRequest>,
+    http: HttpRequest,
+    payload: Result<web::Bytes, actix_web::Error>,
 ) -> impl Responder {
-    info!("Received request to create message: {}", message.body);
+    let payload = match payload {
+        Ok(payload) => payload,
+        Err(e) => return payload_error(&e),
+    };
+    let (body, pattern) = match request_body(&http, payload) {
+        Ok(request) => request,
+        Err(e) => return error_response(&e),
+    };
+    let pattern = match pattern.map_or_else(|| exchange_pattern(&http), Ok) {
+        Ok(pattern) => pattern,
+        Err(e) => return error_response(&e),
+    };
+    info!("Received {} request to create message: {}", pattern.as_str(), body);
+
+    let mut exchange = Exchange::new(body);
+    exchange.pattern = pattern;
+    if let Some(content_type) = raw_content_type(&http) {
+        exchange.set_header(CONTENT_TYPE, content_type);
+    }
 
-    let exchange = Exchange::new(message.body.clone());
+    if prefers_async(&http) {
+        return match state.ingestion_service.submit(exchange).await {
+            Ok(ingestion) => {
+                info!("Queued message: {}", ingestion.message_id);
+                HttpResponse::Accepted()
+                    .insert_header((header::LOCATION, format!("/api/messages/{}/status", ingestion.message_id)))
+                    .insert_header(("Preference-Applied", "respond-async"))
+                    .json(ingestion)
+            }
+            Err(e) => {
+                info!("Error queueing message: {}", e);
+                error_response(&e)
+            }
+        };
+    }
 
     match state.message_service.process_message(exchange).await {
         Ok(processed_exchange) => {
             info!("Successfully processed message: {}", processed_exchange.id);
-            HttpResponse::Ok().json(MessageResponse::from(processed_exchange))
+            let location = format!("/api/messages/{}", processed_exchange.id);
+            let version = processed_exchange.version;
+            match processed_exchange.pattern {
+                ExchangePattern::InOnly => HttpResponse::Accepted()
+                    .insert_header((header::LOCATION, location))
+                    .insert_header(header::ETag(EntityTag::new_strong(version.to_string())))
+                    .json(MessageResponse::from(processed_exchange)),
+                ExchangePattern::InOut => raw_response(
+                    HttpResponse::Ok().insert_header((header::LOCATION, location)),
+                    processed_exchange.into_reply(),
+                    version,
+                ),
+            }
         }
         Err(e) => {
             info!("Error processing message: {}", e);
-            HttpResponse::InternalServerError().body(format!("Error processing message: {}", e))
+            error_response(&e)
         }
     }
 }
 
+/// Honors `If-Match`: processing is refused with 409 once the message has moved on.
+/// `X-Triggered-By` is recorded on the revision the reprocessing produces.
 pub async fn process_message(
     state: web::Data<AppState>,
+    http: HttpRequest,
     req: web::Json<ProcessMessageRequest>,
 ) -> impl Responder {
     info!("Received request to process message ID: {}", req.message_id);
+    let expected_version = match if_match(&http) {
+        Ok(version) => version,
+        Err(e) => return error_response(&e),
+    };
 
-    match uuid::Uuid::parse_str(&req.message_id) {
+    match parse_uuid("message_id", &req.message_id) {
         Ok(uuid) => {
             match state
                 .message_service
-                .get_and_process_message(&uuid, req.additional_data.clone())
+                .get_and_process_message(
+                    &uuid,
+                    req.additional_data.clone(),
+                    expected_version,
+                    triggered_by(&http),
+                )
                 .await
             {
                 Ok(exchange) => {
@@ -84,22 +224,278 @@ pub async fn process_message(
                         "Successfully processed message: {}",
                         exchange.as_ref().unwrap().id
                     );
-                    HttpResponse::Ok().json(MessageResponse::from(exchange.unwrap()))
+                    message_response(exchange.unwrap())
                 }
                 Err(e) => {
                     info!("Error processing message: {}", e);
-                    match e {
-                        DomainError::ProcessorError(msg) if msg.contains("not found") => {
-                            HttpResponse::NotFound().body(msg)
-                        }
-                        _ => HttpResponse::InternalServerError().body(format!("Error: {}", e)),
-                    }
+                    error_response(&e)
                 }
             }
         }
-        Err(_) => {
+        Err(e) => {
             info!("Invalid UUID format: {}", req.message_id);
-            HttpResponse::BadRequest().body("Invalid UUID format")
+            error_response(&e)
         }
     }
 }
+
+pub async fn get_message(state: web::Data<AppState>, path: web::Path<String>) -> HttpResponse {
+    let id = match parse_uuid("id", &path.into_inner()) {
+        Ok(id) => id,
+        Err(e) => return error_response(&e),
+    };
+
+    match state.message_service.get_message(&id).await {
+        Ok(exchange) => message_response(exchange),
+        Err(e) => error_response(&e),
+    }
+}
+
+/// `GET /messages/{id}/status`: how far a message submitted with `Prefer: respond-async` has got.
+/// Statuses live in memory; after a restart they are rebuilt from the message's revisions.
+pub async fn get_message_status(state: web::Data<AppState>, path: web::Path<String>) -> HttpResponse {
+    let id = match parse_uuid("id", &path.into_inner()) {
+        Ok(id) => id,
+        Err(e) => return error_response(&e),
+    };
+
+    match state.ingestion_service.stored_status(&id).await {
+        Ok(ingestion) => HttpResponse::Ok().json(ingestion),
+        Err(e) => error_response(&e),
+    }
+}
+
+pub async fn delete_message(
+    state: web::Data<AppState>,
+    http: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let id = match parse_uuid("id", &path.into_inner()) {
+        Ok(id) => id,
+        Err(e) => return error_response(&e),
+    };
+    info!("Received request to delete message {}", id);
+
+    let expected_version = match if_match(&http) {
+        Ok(version) => version,
+        Err(e) => return error_response(&e),
+    };
+
+    match state.message_service.delete_message(&id, expected_version).await {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(e) => error_response(&e),
+    }
+}
+
+/// `GET /messages?offset=&limit=&source_system=&priority=&success=&created_after=&created_before=&header.<name>=`
+pub async fn list_messages(
+    state: web::Data<AppState>,
+    params: web::Query<HashMap<String, String>>,
+) -> HttpResponse {
+    let query = match message_query(&params) {
+        Ok(query) => query,
+        Err(e) => return error_response(&e),
+    };
+
+    match state.message_service.list_messages(&query).await {
+        Ok(page) => HttpResponse::Ok().json(page.map(MessageResponse::from)),
+        Err(e) => error_response(&e),
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RevisionResponse {
+    version: u64,
+    trigger: String,
+    triggered_by: Option<String>,
+    recorded_at: String,
+    message: MessageResponse,
+    /// Differences from the previous revision; absent on the first one.
+    changes: Option<RevisionDiff>,
+}
+
+/// `GET /messages/{id}/revisions`: every stored revision, oldest first, each diffed against its predecessor.
+pub async fn list_revisions(state: web::Data<AppState>, path: web::Path<String>) -> HttpResponse {
+    let id = match parse_uuid("id", &path.into_inner()) {
+        Ok(id) => id,
+        Err(e) => return error_response(&e),
+    };
+
+    match state.message_service.revisions(&id).await {
+        Ok(revisions) => {
+            let changes: Vec<Option<RevisionDiff>> = revisions
+                .iter()
+                .enumerate()
+                .map(|(i, revision)| i.checked_sub(1).map(|prev| revision.diff(&revisions[prev])))
+                .collect();
+            let response: Vec<RevisionResponse> = revisions
+                .into_iter()
+                .zip(changes)
+                .map(|(revision, changes)| RevisionResponse {
+                    version: revision.version(),
+                    trigger: revision.cause.trigger,
+                    triggered_by: revision.cause.triggered_by,
+                    recorded_at: revision.recorded_at.to_rfc3339(),
+                    message: MessageResponse::from(revision.exchange),
+                    changes,
+                })
+                .collect();
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => error_response(&e),
+    }
+}
+
+/// `GET /messages/{id}/body`: the body as raw content, under the content type it arrived with.
+pub async fn get_message_body(state: web::Data<AppState>, path: web::Path<String>) -> HttpResponse {
+    let id = match parse_uuid("id", &path.into_inner()) {
+        Ok(id) => id,
+        Err(e) => return error_response(&e),
+    };
+
+    match state.message_service.get_message(&id).await {
+        Ok(exchange) => {
+            let version = exchange.version;
+            raw_response(
+                &mut HttpResponse::Ok(),
+                Message {
+                    body: exchange.body,
+                    headers: exchange.headers,
+                },
+                version,
+            )
+        }
+        Err(e) => error_response(&e),
+    }
+}
+
+/// The message body as raw content, typed by its `content_type` header or else by the kind of body.
+fn raw_response(response: &mut HttpResponseBuilder, message: Message, version: u64) -> HttpResponse {
+    let content_type = message.headers.get(CONTENT_TYPE).map(HeaderValue::to_string).unwrap_or_else(|| {
+        match message.body {
+            Body::Json(_) => "application/json",
+            Body::Bytes(_) => "application/octet-stream",
+            _ => "text/plain; charset=utf-8",
+        }
+        .to_string()
+    });
+    match message.body.bytes() {
+        Ok(content) => response
+            .insert_header(header::ETag(EntityTag::new_strong(version.to_string())))
+            .content_type(content_type)
+            .body(content.into_owned()),
+        Err(e) => error_response(&e),
+    }
+}
+
+/// The body and, for JSON requests, the pattern the request asked for.
+fn request_body(req: &HttpRequest, payload: web::Bytes) -> Result<(Body, Option<ExchangePattern>), DomainError> {
+    if raw_content_type(req).is_some() {
+        let body = match String::from_utf8(payload.to_vec()) {
+            Ok(text) if req.content_type().starts_with("text/") => Body::Text(text),
+            _ => Body::Bytes(payload),
+        };
+        return Ok((body, None));
+    }
+    let request = serde_json::from_slice::<MessageRequest>(&payload)
+        .map_err(|e| DomainError::invalid_field("body", e.to_string()))?;
+    let pattern = request.pattern;
+    Ok((request.into_body()?, pattern))
+}
+
+/// The pattern named by an `X-Exchange-Pattern` header; `InOnly` when absent.
+fn exchange_pattern(req: &HttpRequest) -> Result<ExchangePattern, DomainError> {
+    let Some(value) = req.headers().get("X-Exchange-Pattern") else {
+        return Ok(ExchangePattern::default());
+    };
+    value
+        .to_str()
+        .ok()
+        .and_then(ExchangePattern::parse)
+        .ok_or_else(|| DomainError::invalid_field("X-Exchange-Pattern", "must be InOnly or InOut"))
+}
+
+/// The request's content type unless it is JSON (or absent), which carries a `MessageRequest`.
+fn raw_content_type(req: &HttpRequest) -> Option<String> {
+    match req.content_type() {
+        "" | "application/json" => None,
+        _ => req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string),
+    }
+}
+
+fn prefers_async(req: &HttpRequest) -> bool {
+    req.headers()
+        .get_all("Prefer")
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|preference| preference.trim().eq_ignore_ascii_case("respond-async"))
+}
+
+fn triggered_by(req: &HttpRequest) -> Option<String> {
+    req.headers()
+        .get("X-Triggered-By")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+/// A single message with its version as a strong `ETag`.
+fn message_response(exchange: Exchange) -> HttpResponse {
+    HttpResponse::Ok()
+        .insert_header(header::ETag(EntityTag::new_strong(exchange.version.to_string())))
+        .json(MessageResponse::from(exchange))
+}
+
+/// The version named by an `If-Match` header; `None` when absent or `*`.
+fn if_match(req: &HttpRequest) -> Result<Option<u64>, DomainError> {
+    let Some(value) = req.headers().get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+    let invalid = || DomainError::invalid_field("If-Match", "must be an ETag returned by this API");
+    let value = value.to_str().map_err(|_| invalid())?.trim();
+    if value == "*" {
+        return Ok(None);
+    }
+    let tag: EntityTag = value.parse().map_err(|_| invalid())?;
+    tag.tag().parse().map(Some).map_err(|_| invalid())
+}
+
+fn message_query(params: &HashMap<String, String>) -> Result<MessageQuery, DomainError> {
+    let mut query = MessageQuery::new();
+    for (key, value) in params {
+        match key.as_str() {
+            "offset" | "limit" => {}
+            "source_system" => query = query.source_system(value),
+            "priority" => query = query.priority(value),
+            "success" => query = query.success(parse_param(key, value)?),
+            "created_after" => query.created_after = Some(parse_param::<DateTime<Utc>>(key, value)?),
+            "created_before" => query.created_before = Some(parse_param::<DateTime<Utc>>(key, value)?),
+            _ => match key.strip_prefix("header.") {
+                Some(name) if !name.is_empty() => query = query.header(name, value),
+                _ => return Err(DomainError::invalid_field(key, "unknown query parameter")),
+            },
+        }
+    }
+
+    let offset = params.get("offset").map(|v| parse_param("offset", v)).transpose()?;
+    let limit = params.get("limit").map(|v| parse_param::<usize>("limit", v)).transpose()?;
+    if limit.is_some_and(|limit| limit == 0 || limit > MessageQuery::MAX_LIMIT) {
+        return Err(DomainError::invalid_field(
+            "limit",
+            format!("must be between 1 and {}", MessageQuery::MAX_LIMIT),
+        ));
+    }
+    Ok(query.page(
+        offset.unwrap_or(0),
+        limit.unwrap_or(MessageQuery::DEFAULT_LIMIT),
+    ))
+}
+
+fn parse_param<T: FromStr>(name: &str, value: &str) -> Result<T, DomainError> {
+    value
+        .parse()
+        .map_err(|_| DomainError::invalid_field(name, format!("invalid value '{}'", value)))
+}
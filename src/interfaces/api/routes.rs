@@ -0,0 +1,53 @@
+use crate::domain::models::error::DomainError;
+use crate::interfaces::api::error::error_response;
+use crate::interfaces::api::rest::AppState;
+use actix_web::{web, HttpResponse, Responder};
+use tracing::info;
+
+pub async fn list_routes(state: web::Data<AppState>) -> impl Responder {
+    match state.context.routes() {
+        Ok(routes) => HttpResponse::Ok().json(routes),
+        Err(e) => error_response(&e),
+    }
+}
+
+pub async fn get_route(state: web::Data<AppState>, path: web::Path<String>) -> HttpResponse {
+    let route_id = path.into_inner();
+    route_response(&state, &route_id)
+}
+
+pub async fn control_route(
+    state: web::Data<AppState>,
+    path: web::Path<(String, String)>,
+) -> HttpResponse {
+    let (route_id, action) = path.into_inner();
+    info!("Received request to {} route {}", action, route_id);
+
+    let result = match action.as_str() {
+        "start" => state.context.start_route(&route_id),
+        "stop" => state.context.stop_route(&route_id),
+        "suspend" => state.context.suspend_route(&route_id),
+        "resume" => state.context.resume_route(&route_id),
+        _ => {
+            return error_response(&DomainError::invalid_field(
+                "action",
+                format!("unknown route action '{}'", action),
+            ))
+        }
+    };
+
+    match result {
+        Ok(()) => route_response(&state, &route_id),
+        Err(e) => error_response(&e),
+    }
+}
+
+fn route_response(state: &AppState, route_id: &str) -> HttpResponse {
+    match state.context.routes() {
+        Ok(routes) => match routes.into_iter().find(|route| route.id == route_id) {
+            Some(route) => HttpResponse::Ok().json(route),
+            None => error_response(&DomainError::not_found(format!("Route {} not found", route_id))),
+        },
+        Err(e) => error_response(&e),
+    }
+}
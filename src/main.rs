@@ -2,61 +2,202 @@ use std::collections::HashMap;
 use actix_web::{web, App, HttpServer};
 pub use rust_camel::{
     application::{
-        pipeline::ProcessorPipeline,
-        processors::{
-            enricher::EnricherProcessor, filter::FilterProcessor, logging::LoggingProcessor,
-            transform::TransformProcessor,
+        context::CamelContext,
+        error_handler::{ErrorHandler, RedeliveryPolicy},
+        route::from,
+        services::{
+            dead_letter_service::DeadLetterService,
+            ingestion_service::{IngestionConfig, IngestionService},
+            message_service::MessageService, replay_service::ReplayService,
+            retention_service::RetentionService,
         },
-        services::message_service::MessageService,
     },
-    infrastructure::repositories::message_repository::InMemoryMessageRepository,
-    interfaces::api::rest::{create_message, process_message, AppState},
+    infrastructure::components::{
+        direct::DirectComponent, file::FileComponent, seda::SedaComponent, timer::TimerComponent,
+    },
+    infrastructure::repositories::{
+        dead_letter_repository::InMemoryDeadLetterRepository,
+        file_log_repository::{FileLogConfig, FileLogMessageRepository},
+        message_repository::InMemoryMessageRepository,
+        sqlite_message_repository::SqliteMessageRepository,
+    },
+    domain::{
+        models::retention::{RetentionPolicy, RetentionRule},
+        ports::repository::MessageRepository,
+    },
+    interfaces::api::rest::{
+        create_message, delete_message, get_message, get_message_body, get_message_status, list_messages,
+        list_revisions, process_message, AppState,
+    },
+    interfaces::api::dead_letters::{delete_dead_letter, list_dead_letters, replay_dead_letter},
+    interfaces::api::error::{json_config, payload_config},
     interfaces::api::health::{health_check},
+    interfaces::api::metrics::metrics,
+    interfaces::api::replays::{cancel_replay, get_replay, list_replays, start_replay},
+    interfaces::api::routes::{control_route, get_route, list_routes},
 };
 use std::sync::Arc;
+use std::time::Duration;
 
 #[tokio::main]
 async fn main() -> std::io::Result<()> {
     // Initialize tracing
     tracing_subscriber::fmt::init();
 
-    // Create repository
-    let repository = Arc::new(InMemoryMessageRepository::new());
+    // Create repository; MESSAGE_STORE=sqlite or file keeps exchanges across restarts
+    let max_revisions = env_number("RETENTION_MAX_REVISIONS")?.map(|max| max as usize);
+    let repository: Arc<dyn MessageRepository> = match std::env::var("MESSAGE_STORE").as_deref() {
+        Ok("sqlite") => {
+            let path = std::env::var("SQLITE_PATH").unwrap_or_else(|_| "rust-camel.db".to_string());
+            let mut repository = SqliteMessageRepository::open(&path).map_err(std::io::Error::other)?;
+            if let Some(max) = max_revisions {
+                repository = repository.max_revisions(max);
+            }
+            Arc::new(repository)
+        }
+        Ok("file") => {
+            let dir = std::env::var("FILE_STORE_DIR").unwrap_or_else(|_| "data/messages".to_string());
+            let mut config = FileLogConfig::default();
+            if let Some(max) = max_revisions {
+                config = config.max_revisions(max);
+            }
+            let repository = Arc::new(FileLogMessageRepository::open(&dir, config).map_err(std::io::Error::other)?);
+            repository.start_compaction(Duration::from_secs(60));
+            repository
+        }
+        Ok("memory") | Err(_) => {
+            let mut repository = InMemoryMessageRepository::new();
+            if let Some(max) = max_revisions {
+                repository = repository.max_revisions(max);
+            }
+            Arc::new(repository)
+        }
+        Ok(other) => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("Unknown MESSAGE_STORE '{}', expected 'memory', 'sqlite' or 'file'", other),
+            ))
+        }
+    };
 
-    // Create processors
-    let logging_processor = Arc::new(LoggingProcessor::new("DEBUG".to_string()));
-    
+    let dead_letter_repository = Arc::new(InMemoryDeadLetterRepository::new());
+
+    // Define the default route
     let mut metadata = HashMap::new();
     metadata.insert("service_name".to_string(), "rust-camel".to_string());
-    let enricher_processor = Arc::new(EnricherProcessor::with_metadata(metadata));
-    let transform_processor = Arc::new(TransformProcessor::new());
-    let filter_processor = Arc::new(FilterProcessor::new());
+    let route = from("rest:/api/messages")
+        .route_id("messages")
+        .error_handler(
+            ErrorHandler::dead_letter_repository(dead_letter_repository.clone()).redelivery(
+                RedeliveryPolicy::new(3)
+                    .redelivery_delay(Duration::from_millis(200))
+                    .jitter(0.2),
+            ),
+        )
+        .log("DEBUG")
+        .enrich(metadata)
+        .transform(Ok)
+        .filter(|_| true)
+        .build()
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string()))?;
+
+    // Register routes with the runtime and start them
+    let context = Arc::new(CamelContext::new("rust-camel"));
+    context
+        .add_component(Arc::new(DirectComponent::new()))
+        .and_then(|_| context.add_component(Arc::new(SedaComponent::new())))
+        .and_then(|_| context.add_component(Arc::new(TimerComponent::new())))
+        .and_then(|_| context.add_component(Arc::new(FileComponent::new())))
+        .and_then(|_| context.add_route(route))
+        .and_then(|_| context.start())
+        .map_err(|e| std::io::Error::other(e.to_string()))?;
 
-    // Create pipeline
-    let mut pipeline = ProcessorPipeline::new();
-    pipeline.add_processor(logging_processor);
-    pipeline.add_processor(enricher_processor);
-    pipeline.add_processor(transform_processor);
-    pipeline.add_processor(filter_processor);
-    let pipeline = Arc::new(pipeline);
+    // Create message service; messages go through the route, so its lifecycle applies
+    let message_service = Arc::new(MessageService::new(repository.clone(), context.clone(), "messages"));
 
-    // Create message service
-    let message_service = Arc::new(MessageService::new(repository, pipeline));
+    // Expire stored messages according to RETENTION_* settings
+    let retention_service = Arc::new(RetentionService::new(repository.clone(), retention_policy_from_env()?));
+    retention_service.start(Duration::from_secs(env_number("RETENTION_SWEEP_INTERVAL_SECS")?.unwrap_or(60)));
+
+    // Messages submitted with `Prefer: respond-async` are processed by a worker pool
+    let mut ingestion_config = IngestionConfig::default();
+    if let Some(workers) = env_number("INGESTION_WORKERS")? {
+        ingestion_config = ingestion_config.workers(workers as usize);
+    }
+    if let Some(capacity) = env_number("INGESTION_QUEUE_CAPACITY")? {
+        ingestion_config = ingestion_config.queue_capacity(capacity as usize);
+    }
+    let ingestion_service = Arc::new(IngestionService::new(
+        message_service.clone(),
+        dead_letter_repository.clone(),
+        ingestion_config,
+    ));
+    ingestion_service.start();
 
     // Create app state
     let state = web::Data::new(AppState {
         message_service: message_service.clone(),
+        context: context.clone(),
+        dead_letter_service: Arc::new(DeadLetterService::new(dead_letter_repository, context.clone())),
+        retention_service,
+        replay_service: Arc::new(ReplayService::new(repository, context.clone())),
+        ingestion_service,
     });
 
     HttpServer::new(move || {
-        App::new().app_data(state.clone()).service(
-            web::scope("/api")
-                .route("/messages", web::post().to(create_message))
-                .route("/messages/process", web::post().to(process_message)),
-        )
+        App::new()
+            .app_data(state.clone())
+            .app_data(json_config())
+            .app_data(payload_config())
+            .service(
+                web::scope("/api")
+                    .route("/messages", web::post().to(create_message))
+                    .route("/messages", web::get().to(list_messages))
+                    .route("/messages/{id}", web::get().to(get_message))
+                    .route("/messages/{id}", web::delete().to(delete_message))
+                    .route("/messages/{id}/body", web::get().to(get_message_body))
+                    .route("/messages/{id}/revisions", web::get().to(list_revisions))
+                    .route("/messages/{id}/status", web::get().to(get_message_status))
+                    .route("/messages/process", web::post().to(process_message))
+                    .route("/routes", web::get().to(list_routes))
+                    .route("/routes/{id}", web::get().to(get_route))
+                    .route("/routes/{id}/{action}", web::post().to(control_route))
+                    .route("/dead-letters", web::get().to(list_dead_letters))
+                    .route("/dead-letters/{id}", web::delete().to(delete_dead_letter))
+                    .route("/dead-letters/{id}/replay", web::post().to(replay_dead_letter))
+                    .route("/replays", web::post().to(start_replay))
+                    .route("/replays", web::get().to(list_replays))
+                    .route("/replays/{id}", web::get().to(get_replay))
+                    .route("/replays/{id}/cancel", web::post().to(cancel_replay))
+                    .route("/metrics", web::get().to(metrics)),
+            )
             .route("/health", web::get().to(health_check))
     })
     .bind("0.0.0.0:8080")?
     .run()
     .await
 }
+
+/// A single policy-wide rule from RETENTION_MAX_AGE_SECS and RETENTION_MAX_COUNT.
+fn retention_policy_from_env() -> std::io::Result<RetentionPolicy> {
+    let mut rule = RetentionRule::all();
+    if let Some(secs) = env_number("RETENTION_MAX_AGE_SECS")? {
+        rule = rule.max_age(Duration::from_secs(secs));
+    }
+    if let Some(count) = env_number("RETENTION_MAX_COUNT")? {
+        rule = rule.max_count(count as usize);
+    }
+    Ok(RetentionPolicy::new().rule(rule))
+}
+
+fn env_number(name: &str) -> std::io::Result<Option<u64>> {
+    match std::env::var(name) {
+        Ok(value) => value.parse().map(Some).map_err(|_| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("{} must be a whole number, got '{}'", name, value),
+            )
+        }),
+        Err(_) => Ok(None),
+    }
+}
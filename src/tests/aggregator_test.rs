@@ -0,0 +1,356 @@
+use crate::application::{
+    aggregation::JoinBodiesStrategy,
+    processors::aggregator::{
+        correlation_id, header, AggregatorProcessor, AGGREGATED_COMPLETED_BY, AGGREGATED_SIZE,
+    },
+};
+use crate::domain::{
+    models::{aggregation::AggregationGroup, error::DomainError, exchange::Exchange},
+    ports::{aggregation::AggregationRepository, processor::Processor},
+};
+use crate::infrastructure::repositories::aggregation_repository::{
+    FileAggregationRepository, InMemoryAggregationRepository,
+};
+use crate::tests::helpers::temp_dir;
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+struct CollectingProcessor {
+    completed: Mutex<Vec<Exchange>>,
+}
+
+#[async_trait]
+impl Processor for CollectingProcessor {
+    async fn process(&self, exchange: Exchange) -> Result<Exchange, DomainError> {
+        self.completed.lock().unwrap().push(exchange.clone());
+        Ok(exchange)
+    }
+}
+
+fn collector() -> Arc<CollectingProcessor> {
+    Arc::new(CollectingProcessor { completed: Mutex::new(Vec::new()) })
+}
+
+fn order_line(order: &str, body: &str) -> Exchange {
+    let mut exchange = Exchange::new(body.to_string());
+    exchange.set_header("order_id", order);
+    exchange
+}
+
+#[actix_rt::test]
+async fn test_aggregator_completes_by_size_per_correlation_key() {
+    let output = collector();
+    let aggregator = AggregatorProcessor::new(
+        header("order_id"),
+        Arc::new(JoinBodiesStrategy::new("+")),
+        Arc::new(InMemoryAggregationRepository::new()),
+        output.clone(),
+    )
+    .completion_size(2);
+
+    for (order, body) in [("a", "1"), ("b", "x"), ("a", "2"), ("b", "y")] {
+        aggregator.process(order_line(order, body)).await.unwrap();
+    }
+
+    let completed = output.completed.lock().unwrap();
+    assert_eq!(completed.len(), 2);
+    assert_eq!(completed[0].body, "1+2");
+    assert_eq!(completed[0].headers.get(AGGREGATED_SIZE).unwrap(), "2");
+    assert_eq!(completed[0].headers.get(AGGREGATED_COMPLETED_BY).unwrap(), "size");
+    assert_eq!(completed[1].body, "x+y");
+}
+
+#[actix_rt::test]
+async fn test_aggregator_completes_by_predicate() {
+    let output = collector();
+    let aggregator = AggregatorProcessor::new(
+        header("order_id"),
+        Arc::new(JoinBodiesStrategy::new(",")),
+        Arc::new(InMemoryAggregationRepository::new()),
+        output.clone(),
+    )
+    .completion_predicate(|aggregated| aggregated.body.text().is_ok_and(|body| body.ends_with("END")));
+
+    aggregator.process(order_line("a", "1")).await.unwrap();
+    assert!(output.completed.lock().unwrap().is_empty());
+    aggregator.process(order_line("a", "END")).await.unwrap();
+
+    let completed = output.completed.lock().unwrap();
+    assert_eq!(completed[0].body, "1,END");
+    assert_eq!(completed[0].headers.get(AGGREGATED_COMPLETED_BY).unwrap(), "predicate");
+}
+
+#[actix_rt::test]
+async fn test_aggregator_completes_by_timeout_in_background() {
+    let output = collector();
+    let aggregator = Arc::new(
+        AggregatorProcessor::new(
+            header("order_id"),
+            Arc::new(JoinBodiesStrategy::new(",")),
+            Arc::new(InMemoryAggregationRepository::new()),
+            output.clone(),
+        )
+        .completion_timeout(Duration::from_millis(50)),
+    );
+    let handle = aggregator.start().unwrap();
+
+    aggregator.process(order_line("a", "1")).await.unwrap();
+    aggregator.process(order_line("a", "2")).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    handle.abort();
+
+    let completed = output.completed.lock().unwrap();
+    assert_eq!(completed.len(), 1);
+    assert_eq!(completed[0].body, "1,2");
+    assert_eq!(completed[0].headers.get(AGGREGATED_COMPLETED_BY).unwrap(), "timeout");
+}
+
+#[actix_rt::test]
+async fn test_aggregator_requires_correlation_key() {
+    let aggregator = AggregatorProcessor::new(
+        header("order_id"),
+        Arc::new(JoinBodiesStrategy::new(",")),
+        Arc::new(InMemoryAggregationRepository::new()),
+        collector(),
+    );
+
+    let result = aggregator.process(Exchange::new("orphan".to_string())).await;
+
+    assert!(matches!(result, Err(DomainError::Validation { .. })));
+}
+
+#[actix_rt::test]
+async fn test_file_repository_groups_survive_restart() {
+    let path = temp_dir("aggregation");
+    let first = Exchange::new("1".to_string());
+    let correlation = first.metadata.correlation_id.clone().unwrap();
+
+    {
+        let repository = Arc::new(FileAggregationRepository::open(&path).await.unwrap());
+        let aggregator = AggregatorProcessor::new(
+            correlation_id(),
+            Arc::new(JoinBodiesStrategy::new(",")),
+            repository,
+            collector(),
+        )
+        .completion_size(2);
+        aggregator.process(first).await.unwrap();
+    }
+
+    // A new process recovers the pending group and completes it
+    let repository = Arc::new(FileAggregationRepository::open(&path).await.unwrap());
+    assert_eq!(repository.find_all().await.unwrap().len(), 1);
+    let output = collector();
+    let aggregator = AggregatorProcessor::new(
+        correlation_id(),
+        Arc::new(JoinBodiesStrategy::new(",")),
+        repository.clone(),
+        output.clone(),
+    )
+    .completion_size(2);
+    let mut second = Exchange::new("2".to_string());
+    second.metadata.correlation_id = Some(correlation);
+    aggregator.process(second).await.unwrap();
+
+    assert_eq!(output.completed.lock().unwrap()[0].body, "1,2");
+    assert!(repository.find_all().await.unwrap().is_empty());
+    let _ = std::fs::remove_file(&path);
+}
+
+#[actix_rt::test]
+async fn test_file_repository_changes_memory_only_once_persisted() {
+    // Arrange
+    let dir = temp_dir("aggregation");
+    let repository = FileAggregationRepository::open(dir.join("groups.json")).await.unwrap();
+    let group = AggregationGroup::new("order-1", Exchange::new("1".to_string()));
+
+    // Act - the directory is missing, then present, then gone again
+    let unsaved = repository.save(&group).await;
+    let missing = repository.get("order-1").await.unwrap();
+    std::fs::create_dir_all(&dir).unwrap();
+    repository.save(&group).await.unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    let unremoved = repository.remove("order-1").await;
+
+    // Assert
+    assert!(unsaved.is_err());
+    assert!(missing.is_none());
+    assert!(unremoved.is_err());
+    assert!(repository.get("order-1").await.unwrap().is_some());
+}
+
+/// Fails every aggregate whose body mentions "bad", and the first `fail_first` of the rest.
+struct FailingOutput {
+    fail_first: Mutex<usize>,
+    completed: Mutex<Vec<Exchange>>,
+}
+
+#[async_trait]
+impl Processor for FailingOutput {
+    async fn process(&self, exchange: Exchange) -> Result<Exchange, DomainError> {
+        let mut fail_first = self.fail_first.lock().unwrap();
+        if exchange.body.text()?.contains("bad") || *fail_first > 0 {
+            *fail_first = fail_first.saturating_sub(1);
+            return Err(DomainError::transient("output unavailable"));
+        }
+        self.completed.lock().unwrap().push(exchange.clone());
+        Ok(exchange)
+    }
+}
+
+fn failing_output(fail_first: usize) -> Arc<FailingOutput> {
+    Arc::new(FailingOutput {
+        fail_first: Mutex::new(fail_first),
+        completed: Mutex::new(Vec::new()),
+    })
+}
+
+#[actix_rt::test]
+async fn test_aggregator_keeps_group_when_emit_fails() {
+    let output = failing_output(1);
+    let repository = Arc::new(InMemoryAggregationRepository::new());
+    let aggregator = AggregatorProcessor::new(
+        header("order_id"),
+        Arc::new(JoinBodiesStrategy::new("+")),
+        repository.clone(),
+        output.clone(),
+    )
+    .completion_size(2);
+
+    aggregator.process(order_line("a", "1")).await.unwrap();
+    assert!(aggregator.process(order_line("a", "2")).await.is_err());
+    let kept = repository.get("a").await.unwrap().unwrap();
+    assert_eq!((kept.size, kept.exchange.body.text().unwrap().into_owned()), (1, "1".to_string()));
+
+    // Redelivery of the failed exchange completes the group it belonged to
+    aggregator.process(order_line("a", "2")).await.unwrap();
+
+    assert!(repository.get("a").await.unwrap().is_none());
+    let completed = output.completed.lock().unwrap();
+    assert_eq!(completed.len(), 1);
+    assert_eq!(completed[0].body, "1+2");
+}
+
+#[actix_rt::test]
+async fn test_aggregator_completes_other_groups_when_one_emit_fails() {
+    let output = failing_output(0);
+    let repository = Arc::new(InMemoryAggregationRepository::new());
+    let aggregator = AggregatorProcessor::new(
+        header("order_id"),
+        Arc::new(JoinBodiesStrategy::new("+")),
+        repository.clone(),
+        output.clone(),
+    )
+    .completion_size(10);
+    for (order, body) in [("a", "bad"), ("b", "ok"), ("c", "fine")] {
+        aggregator.process(order_line(order, body)).await.unwrap();
+    }
+
+    let result = aggregator.complete_all("shutdown").await;
+
+    assert!(result.unwrap_err().to_string().contains("1 of 3"));
+    assert_eq!(output.completed.lock().unwrap().len(), 2);
+    let remaining = repository.find_all().await.unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].correlation_key, "a");
+}
+
+/// Sends every aggregate back into `aggregator`, re-keyed to `order_id` when given.
+struct FeedbackOutput {
+    aggregator: Mutex<Option<Arc<AggregatorProcessor>>>,
+    order_id: Option<&'static str>,
+}
+
+#[async_trait]
+impl Processor for FeedbackOutput {
+    async fn process(&self, mut exchange: Exchange) -> Result<Exchange, DomainError> {
+        let aggregator = self.aggregator.lock().unwrap().clone().unwrap();
+        if let Some(order_id) = self.order_id {
+            exchange.set_header("order_id", order_id);
+        }
+        aggregator.process(exchange).await
+    }
+}
+
+fn feeding_back(order_id: Option<&'static str>) -> (Arc<AggregatorProcessor>, Arc<InMemoryAggregationRepository>) {
+    let output = Arc::new(FeedbackOutput {
+        aggregator: Mutex::new(None),
+        order_id,
+    });
+    let repository = Arc::new(InMemoryAggregationRepository::new());
+    let aggregator = Arc::new(
+        AggregatorProcessor::new(
+            header("order_id"),
+            Arc::new(JoinBodiesStrategy::new("+")),
+            repository.clone(),
+            output.clone(),
+        )
+        .completion_size(2),
+    );
+    *output.aggregator.lock().unwrap() = Some(aggregator.clone());
+    (aggregator, repository)
+}
+
+#[actix_rt::test]
+async fn test_aggregator_output_may_feed_back_into_the_aggregator() {
+    // Arrange
+    let (rekeying, rekeyed) = feeding_back(Some("summary"));
+    let (same_key, kept) = feeding_back(None);
+    for aggregator in [&rekeying, &same_key] {
+        aggregator.process(order_line("a", "1")).await.unwrap();
+    }
+
+    // Act
+    let limit = Duration::from_secs(1);
+    let rekeyed_result = tokio::time::timeout(limit, rekeying.process(order_line("a", "2"))).await;
+    let same_key_result = tokio::time::timeout(limit, same_key.process(order_line("a", "2"))).await;
+
+    // Assert
+    assert!(rekeyed_result.expect("no deadlock").is_ok());
+    assert_eq!(rekeyed.get("summary").await.unwrap().unwrap().exchange.body, "1+2");
+    assert!(rekeyed.get("a").await.unwrap().is_none());
+    assert!(same_key_result.expect("no deadlock").is_err());
+    assert_eq!(kept.get("a").await.unwrap().unwrap().size, 1);
+}
+
+/// Takes a while over every aggregate whose body mentions "slow".
+struct SlowOutput;
+
+#[async_trait]
+impl Processor for SlowOutput {
+    async fn process(&self, exchange: Exchange) -> Result<Exchange, DomainError> {
+        if exchange.body.text()?.contains("slow") {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+        }
+        Ok(exchange)
+    }
+}
+
+#[actix_rt::test]
+async fn test_aggregator_slow_emit_does_not_hold_back_other_groups() {
+    // Arrange
+    let aggregator = Arc::new(
+        AggregatorProcessor::new(
+            header("order_id"),
+            Arc::new(JoinBodiesStrategy::new("+")),
+            Arc::new(InMemoryAggregationRepository::new()),
+            Arc::new(SlowOutput),
+        )
+        .completion_size(1),
+    );
+    let slow = {
+        let aggregator = aggregator.clone();
+        tokio::spawn(async move { aggregator.process(order_line("a", "slow")).await })
+    };
+    tokio::time::sleep(Duration::from_millis(20)).await;
+
+    // Act
+    let started = std::time::Instant::now();
+    aggregator.process(order_line("b", "fast")).await.unwrap();
+    let elapsed = started.elapsed();
+
+    // Assert
+    assert!(elapsed < Duration::from_millis(200), "took {:?}", elapsed);
+    assert!(slow.await.unwrap().is_ok());
+}
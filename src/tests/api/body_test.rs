@@ -0,0 +1,90 @@
+use crate::tests::helpers::setup_test_app;
+use actix_web::{http::StatusCode, test};
+use serde_json::{json, Value};
+
+#[actix_rt::test]
+async fn test_base64_body_round_trips() {
+    // Arrange
+    let app = setup_test_app().await;
+
+    // Act
+    let req = test::TestRequest::post()
+        .uri("/api/messages")
+        .set_json(json!({ "body": "yv66vg==", "encoding": "base64" }))
+        .to_request();
+    let created: Value = test::call_and_read_body_json(&app, req).await;
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/messages/{}/body", created["id"].as_str().unwrap()))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    let content_type = resp.headers().get("Content-Type").unwrap().clone();
+    let raw = test::read_body(resp).await;
+
+    // Assert
+    assert_eq!(created["encoding"], "base64");
+    assert_eq!(created["body"], "yv66vg==");
+    assert_eq!(content_type, "application/octet-stream");
+    assert_eq!(raw.as_ref(), &[0xca, 0xfe, 0xba, 0xbe]);
+}
+
+#[actix_rt::test]
+async fn test_raw_content_keeps_its_content_type() {
+    // Arrange
+    let app = setup_test_app().await;
+    let png = [0x89, b'P', b'N', b'G', 0x0d, 0x0a];
+
+    // Act
+    let req = test::TestRequest::post()
+        .uri("/api/messages")
+        .insert_header(("Content-Type", "image/png"))
+        .set_payload(png.to_vec())
+        .to_request();
+    let created: Value = test::call_and_read_body_json(&app, req).await;
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/messages/{}/body", created["id"].as_str().unwrap()))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    let content_type = resp.headers().get("Content-Type").unwrap().clone();
+    let raw = test::read_body(resp).await;
+
+    // Assert
+    assert_eq!(created["headers"]["content_type"], "image/png");
+    assert_eq!(content_type, "image/png");
+    assert_eq!(raw.as_ref(), &png);
+}
+
+#[actix_rt::test]
+async fn test_json_body_is_returned_as_json() {
+    // Arrange
+    let app = setup_test_app().await;
+
+    // Act
+    let req = test::TestRequest::post()
+        .uri("/api/messages")
+        .set_json(json!({ "body": { "order": 42 } }))
+        .to_request();
+    let created: Value = test::call_and_read_body_json(&app, req).await;
+
+    // Assert
+    assert_eq!(created["encoding"], "json");
+    assert_eq!(created["body"]["order"], 42);
+}
+
+#[actix_rt::test]
+async fn test_invalid_base64_is_rejected() {
+    // Arrange
+    let app = setup_test_app().await;
+
+    // Act
+    let req = test::TestRequest::post()
+        .uri("/api/messages")
+        .set_json(json!({ "body": "not base64!", "encoding": "base64" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    let status = resp.status();
+    let problem: Value = test::read_body_json(resp).await;
+
+    // Assert
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(problem["errors"][0]["field"], "body");
+}
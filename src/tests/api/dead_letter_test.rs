@@ -0,0 +1,65 @@
+use crate::domain::{
+    models::{dead_letter::DeadLetter, exchange::Exchange},
+    ports::dead_letter::DeadLetterRepository,
+};
+use crate::infrastructure::repositories::dead_letter_repository::InMemoryDeadLetterRepository;
+use crate::tests::helpers::setup_test_app_with_dead_letters;
+use actix_web::{http::StatusCode, test};
+use serde_json::Value;
+use std::sync::Arc;
+
+async fn seeded_repository() -> (Arc<InMemoryDeadLetterRepository>, uuid::Uuid) {
+    let repository = Arc::new(InMemoryDeadLetterRepository::new());
+    let exchange = Exchange::new("failed body".to_string());
+    let id = exchange.id;
+    repository
+        .save(&DeadLetter {
+            exchange,
+            route_id: Some("test-route".to_string()),
+            cause: "boom".to_string(),
+            attempts: 3,
+            failed_at: chrono::Utc::now(),
+        })
+        .await
+        .unwrap();
+    (repository, id)
+}
+
+#[actix_rt::test]
+async fn test_list_and_replay_dead_letters() {
+    // Arrange
+    let (repository, id) = seeded_repository().await;
+    let app = setup_test_app_with_dead_letters(repository.clone()).await;
+    let start = test::TestRequest::post().uri("/api/routes/test-route/start").to_request();
+    test::call_service(&app, start).await;
+
+    // Act
+    let list = test::TestRequest::get().uri("/api/dead-letters").to_request();
+    let listed: Value = test::call_and_read_body_json(&app, list).await;
+    let replay = test::TestRequest::post()
+        .uri(&format!("/api/dead-letters/{}/replay", id))
+        .to_request();
+    let replayed: Value = test::call_and_read_body_json(&app, replay).await;
+
+    // Assert
+    assert_eq!(listed[0]["cause"], "boom");
+    assert_eq!(listed[0]["attempts"], 3);
+    assert_eq!(replayed["id"], id.to_string());
+    assert!(repository.find_all().await.unwrap().is_empty());
+}
+
+#[actix_rt::test]
+async fn test_delete_and_missing_dead_letters() {
+    let (repository, id) = seeded_repository().await;
+    let app = setup_test_app_with_dead_letters(repository.clone()).await;
+
+    let delete = test::TestRequest::delete()
+        .uri(&format!("/api/dead-letters/{}", id))
+        .to_request();
+    assert_eq!(test::call_service(&app, delete).await.status(), StatusCode::NO_CONTENT);
+
+    let replay = test::TestRequest::post()
+        .uri(&format!("/api/dead-letters/{}/replay", id))
+        .to_request();
+    assert_eq!(test::call_service(&app, replay).await.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,82 @@
+use crate::{
+    domain::models::exchange::ExchangePattern, interfaces::api::rest::MessageRequest,
+    tests::helpers::setup_test_app,
+};
+use actix_web::{http::StatusCode, test};
+use serde_json::Value;
+
+#[actix_rt::test]
+async fn test_in_only_message_is_acknowledged_with_accepted() {
+    // Arrange
+    let app = setup_test_app().await;
+
+    // Act
+    let req = test::TestRequest::post()
+        .uri("/api/messages")
+        .set_json(MessageRequest::text("fire and forget"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    let status = resp.status();
+    let location = resp.headers().get("Location").unwrap().to_str().unwrap().to_string();
+    let body: Value = test::read_body_json(resp).await;
+
+    // Assert
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert_eq!(location, format!("/api/messages/{}", body["id"].as_str().unwrap()));
+    assert_eq!(body["body"], "fire and forget");
+}
+
+#[actix_rt::test]
+async fn test_in_out_message_returns_the_reply_body() {
+    // Arrange
+    let app = setup_test_app().await;
+
+    // Act
+    let req = test::TestRequest::post()
+        .uri("/api/messages")
+        .set_json(MessageRequest::text("ping").pattern(ExchangePattern::InOut))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    let status = resp.status();
+    let location = resp.headers().get("Location").unwrap().to_str().unwrap().to_string();
+    let body = test::read_body(resp).await;
+
+    let stored = test::TestRequest::get().uri(&location).to_request();
+    let stored: Value = test::call_and_read_body_json(&app, stored).await;
+
+    // Assert
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "ping");
+    assert_eq!(stored["body"], "ping");
+}
+
+#[actix_rt::test]
+async fn test_pattern_header_applies_to_raw_bodies() {
+    // Arrange
+    let app = setup_test_app().await;
+
+    // Act
+    let req = test::TestRequest::post()
+        .uri("/api/messages")
+        .insert_header(("Content-Type", "text/csv"))
+        .insert_header(("X-Exchange-Pattern", "inout"))
+        .set_payload("a,b\n1,2")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    let status = resp.status();
+    let content_type = resp.headers().get("Content-Type").unwrap().clone();
+    let body = test::read_body(resp).await;
+
+    let invalid = test::TestRequest::post()
+        .uri("/api/messages")
+        .insert_header(("X-Exchange-Pattern", "sometimes"))
+        .set_json(MessageRequest::text("x"))
+        .to_request();
+    let invalid = test::call_service(&app, invalid).await;
+
+    // Assert
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "text/csv");
+    assert_eq!(body, "a,b\n1,2");
+    assert_eq!(invalid.status(), StatusCode::BAD_REQUEST);
+}
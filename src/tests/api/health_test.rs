@@ -15,4 +15,20 @@ async fn test_health_check() {
     // Assert
     assert_eq!(resp["status"], "ok");
     assert!(resp["version"].is_string());
+    assert!(resp["queues"].is_array());
+}
+
+#[actix_rt::test]
+async fn test_metrics_report_retention() {
+    // Arrange
+    let app = setup_test_app().await;
+
+    // Act
+    let req = test::TestRequest::get().uri("/api/metrics").to_request();
+    let resp: Value = test::call_and_read_body_json(&app, req).await;
+
+    // Assert
+    assert_eq!(resp["retention"]["sweeps"], 0);
+    assert_eq!(resp["retention"]["expired_total"], 0);
+    assert!(resp["queues"].is_array());
 }
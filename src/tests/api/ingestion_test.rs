@@ -0,0 +1,63 @@
+use crate::{interfaces::api::rest::MessageRequest, tests::helpers::setup_test_app};
+use actix_web::{http::StatusCode, test};
+use serde_json::Value;
+use std::time::Duration;
+
+#[actix_rt::test]
+async fn test_async_submission_returns_a_status_location_to_poll() {
+    // Arrange
+    let app = setup_test_app().await;
+
+    // Act
+    let req = test::TestRequest::post()
+        .uri("/api/messages")
+        .insert_header(("Prefer", "respond-async"))
+        .set_json(MessageRequest::text("slow work"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    let status = resp.status();
+    let location = resp.headers().get("Location").unwrap().to_str().unwrap().to_string();
+    let applied = resp.headers().get("Preference-Applied").unwrap().clone();
+    let queued: Value = test::read_body_json(resp).await;
+
+    let mut polled = Value::Null;
+    for _ in 0..200 {
+        let poll = test::TestRequest::get().uri(&location).to_request();
+        polled = test::call_and_read_body_json(&app, poll).await;
+        if polled["status"] == "completed" {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    let message_id = queued["message_id"].as_str().unwrap();
+    let message = test::TestRequest::get().uri(&format!("/api/messages/{}", message_id)).to_request();
+    let message: Value = test::call_and_read_body_json(&app, message).await;
+
+    // Assert
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert_eq!(applied, "respond-async");
+    assert_eq!(location, format!("/api/messages/{}/status", message_id));
+    assert_eq!(queued["status"], "queued");
+    assert_eq!(polled["status"], "completed");
+    assert_eq!(message["headers"]["processed_by"], "enricher");
+}
+
+#[actix_rt::test]
+async fn test_status_of_synchronous_message_is_not_found() {
+    // Arrange
+    let app = setup_test_app().await;
+    let req = test::TestRequest::post()
+        .uri("/api/messages")
+        .set_json(MessageRequest::text("now"))
+        .to_request();
+    let created: Value = test::call_and_read_body_json(&app, req).await;
+
+    // Act
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/messages/{}/status", created["id"].as_str().unwrap()))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    // Assert
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
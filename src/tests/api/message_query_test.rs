@@ -0,0 +1,114 @@
+use crate::{
+    interfaces::api::rest::MessageRequest,
+    tests::helpers::setup_test_app,
+};
+use actix_web::{http::StatusCode, test};
+use serde_json::Value;
+
+async fn create<S>(app: &S, body: &str) -> String
+where
+    S: actix_web::dev::Service<
+        actix_http::Request,
+        Response = actix_web::dev::ServiceResponse,
+        Error = actix_web::Error,
+    >,
+{
+    let req = test::TestRequest::post()
+        .uri("/api/messages")
+        .set_json(MessageRequest::text(body))
+        .to_request();
+    let resp: Value = test::call_and_read_body_json(app, req).await;
+    resp["id"].as_str().unwrap().to_string()
+}
+
+#[actix_rt::test]
+async fn test_get_and_delete_message() {
+    // Arrange
+    let app = setup_test_app().await;
+    let id = create(&app, "stored").await;
+
+    // Act
+    let req = test::TestRequest::get().uri(&format!("/api/messages/{}", id)).to_request();
+    let found: Value = test::call_and_read_body_json(&app, req).await;
+
+    let req = test::TestRequest::delete().uri(&format!("/api/messages/{}", id)).to_request();
+    let deleted = test::call_service(&app, req).await;
+
+    let req = test::TestRequest::get().uri(&format!("/api/messages/{}", id)).to_request();
+    let missing = test::call_service(&app, req).await;
+
+    // Assert
+    assert_eq!(found["body"], "stored");
+    assert_eq!(deleted.status(), StatusCode::NO_CONTENT);
+    assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+}
+
+#[actix_rt::test]
+async fn test_list_messages_paginates_newest_first() {
+    // Arrange
+    let app = setup_test_app().await;
+    for body in ["first", "second", "third"] {
+        create(&app, body).await;
+    }
+
+    // Act
+    let req = test::TestRequest::get().uri("/api/messages?limit=2").to_request();
+    let first_page: Value = test::call_and_read_body_json(&app, req).await;
+    let req = test::TestRequest::get().uri("/api/messages?limit=2&offset=2").to_request();
+    let second_page: Value = test::call_and_read_body_json(&app, req).await;
+
+    // Assert
+    assert_eq!(first_page["total"], 3);
+    assert_eq!(first_page["items"].as_array().unwrap().len(), 2);
+    assert_eq!(first_page["items"][0]["body"], "third");
+    assert_eq!(second_page["items"].as_array().unwrap().len(), 1);
+    assert_eq!(second_page["items"][0]["body"], "first");
+}
+
+#[actix_rt::test]
+async fn test_list_messages_filters() {
+    // Arrange
+    let app = setup_test_app().await;
+    create(&app, "one").await;
+    create(&app, "two").await;
+
+    // Act
+    let req = test::TestRequest::get()
+        .uri("/api/messages?header.processed_by=enricher&success=true&source_system=create")
+        .to_request();
+    let matching: Value = test::call_and_read_body_json(&app, req).await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/messages?header.processed_by=someone-else")
+        .to_request();
+    let none: Value = test::call_and_read_body_json(&app, req).await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/messages?created_after=2999-01-01T00:00:00Z")
+        .to_request();
+    let future: Value = test::call_and_read_body_json(&app, req).await;
+
+    // Assert
+    assert_eq!(matching["total"], 2);
+    assert_eq!(none["total"], 0);
+    assert_eq!(future["total"], 0);
+}
+
+#[actix_rt::test]
+async fn test_list_messages_rejects_bad_parameters() {
+    // Arrange
+    let app = setup_test_app().await;
+
+    // Act
+    let req = test::TestRequest::get().uri("/api/messages?limit=0").to_request();
+    let bad_limit = test::call_service(&app, req).await;
+    let req = test::TestRequest::get().uri("/api/messages?success=maybe").to_request();
+    let bad_success = test::call_service(&app, req).await;
+    let req = test::TestRequest::get().uri("/api/messages?colour=red").to_request();
+    let unknown = test::call_service(&app, req).await;
+
+    // Assert
+    assert_eq!(bad_limit.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(bad_success.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(unknown.status(), StatusCode::BAD_REQUEST);
+}
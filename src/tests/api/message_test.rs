@@ -17,9 +17,7 @@ async fn test_create_message() {
     // Act
     let req = test::TestRequest::post()
         .uri("/api/messages")
-        .set_json(&MessageRequest {
-            body: test_message.to_string(),
-        })
+        .set_json(MessageRequest::text(test_message))
         .to_request();
 
     let resp: Value = test::call_and_read_body_json(&app, req).await;
@@ -39,9 +37,7 @@ async fn test_process_message() {
     // First create a message
     let create_req = test::TestRequest::post()
         .uri("/api/messages")
-        .set_json(&MessageRequest {
-            body: "Test message".to_string(),
-        })
+        .set_json(MessageRequest::text("Test message"))
         .to_request();
 
     let create_resp: Value = test::call_and_read_body_json(&app, create_req).await;
@@ -65,6 +61,8 @@ async fn test_process_message() {
     assert!(process_resp["headers"].as_object().unwrap().contains_key("processed_by"),
             "Headers should contain 'processed_by'. Got headers: {:?}",
             process_resp["headers"]);
+    assert!(process_resp["headers"]["processed_at"].is_string(),
+            "Typed headers should still be rendered as strings");
 }
 
 // Add a test to verify enricher behavior specifically
@@ -98,3 +96,31 @@ async fn test_process_nonexistent_message() {
     // Assert
     assert_eq!(resp.status(), StatusCode::NOT_FOUND);
 }
+
+#[actix_rt::test]
+async fn test_suspended_route_rejects_messages_until_resumed() {
+    // Arrange
+    let app = setup_test_app().await;
+    let suspend = test::TestRequest::post().uri("/api/routes/messages/suspend").to_request();
+    assert_eq!(test::call_service(&app, suspend).await.status(), StatusCode::OK);
+
+    // Act
+    let req = test::TestRequest::post()
+        .uri("/api/messages")
+        .set_json(MessageRequest::text("while suspended"))
+        .to_request();
+    let rejected = test::call_service(&app, req).await;
+    let resume = test::TestRequest::post().uri("/api/routes/messages/resume").to_request();
+    test::call_service(&app, resume).await;
+    let req = test::TestRequest::post()
+        .uri("/api/messages")
+        .set_json(MessageRequest::text("after resume"))
+        .to_request();
+    let accepted = test::call_service(&app, req).await;
+
+    // Assert
+    assert_eq!(rejected.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let problem: Value = test::read_body_json(rejected).await;
+    assert!(problem["detail"].as_str().unwrap().contains("messages"));
+    assert!(accepted.status().is_success());
+}
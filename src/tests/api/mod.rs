@@ -1,2 +1,12 @@
+mod body_test;
+mod dead_letter_test;
+mod exchange_pattern_test;
 mod health_test;
-mod message_test;
\ No newline at end of file
+mod ingestion_test;
+mod message_query_test;
+mod message_test;
+mod problem_test;
+mod replay_test;
+mod revision_test;
+mod route_test;
+mod versioning_test;
\ No newline at end of file
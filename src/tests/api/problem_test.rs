@@ -0,0 +1,106 @@
+use crate::{
+    interfaces::api::{
+        error::{MAX_BODY_BYTES, PROBLEM_JSON},
+        rest::{MessageRequest, ProcessMessageRequest},
+    },
+    tests::helpers::setup_test_app,
+};
+use actix_web::{http::StatusCode, test};
+use serde_json::Value;
+
+#[actix_rt::test]
+async fn test_invalid_uuid_is_problem_document() {
+    // Arrange
+    let app = setup_test_app().await;
+
+    // Act
+    let req = test::TestRequest::post()
+        .uri("/api/messages/process")
+        .set_json(&ProcessMessageRequest {
+            message_id: "not-a-uuid".to_string(),
+            additional_data: None,
+        })
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    // Assert
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(resp.headers().get("content-type").unwrap(), PROBLEM_JSON);
+    let problem: Value = test::read_body_json(resp).await;
+    assert_eq!(problem["type"], "urn:rust-camel:problem:validation");
+    assert_eq!(problem["status"], 400);
+    assert_eq!(problem["errors"][0]["field"], "message_id");
+}
+
+#[actix_rt::test]
+async fn test_malformed_json_is_problem_document() {
+    // Arrange
+    let app = setup_test_app().await;
+
+    // Act
+    let req = test::TestRequest::post()
+        .uri("/api/messages")
+        .insert_header(("content-type", "application/json"))
+        .set_payload("{\"body\":")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    // Assert
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(resp.headers().get("content-type").unwrap(), PROBLEM_JSON);
+    let problem: Value = test::read_body_json(resp).await;
+    assert_eq!(problem["errors"][0]["field"], "body");
+}
+
+#[actix_rt::test]
+async fn test_route_conflict_is_problem_document() {
+    // Arrange
+    let app = setup_test_app().await;
+
+    // Act - the test route starts out stopped
+    let req = test::TestRequest::post().uri("/api/routes/test-route/suspend").to_request();
+    let resp = test::call_service(&app, req).await;
+
+    // Assert
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    let problem: Value = test::read_body_json(resp).await;
+    assert_eq!(problem["type"], "urn:rust-camel:problem:conflict");
+    assert_eq!(problem["title"], "Conflicting state");
+    assert!(problem["detail"].as_str().unwrap().contains("test-route"));
+}
+
+#[actix_rt::test]
+async fn test_message_body_limit_matches_json_bodies() {
+    // Arrange
+    let app = setup_test_app().await;
+    let within_limit = "a".repeat(MAX_BODY_BYTES - 1024);
+    let over_limit = "a".repeat(MAX_BODY_BYTES + 1);
+
+    // Act
+    let req = test::TestRequest::post()
+        .uri("/api/messages")
+        .set_json(MessageRequest::text(&within_limit))
+        .to_request();
+    let accepted = test::call_service(&app, req).await;
+    let req = test::TestRequest::post()
+        .uri("/api/messages")
+        .insert_header(("content-type", "text/plain"))
+        .set_payload(over_limit.clone())
+        .to_request();
+    let rejected_raw = test::call_service(&app, req).await;
+    let req = test::TestRequest::post()
+        .uri("/api/messages")
+        .set_json(MessageRequest::text(&over_limit))
+        .to_request();
+    let rejected_json = test::call_service(&app, req).await;
+
+    // Assert
+    assert!(accepted.status().is_success());
+    for rejected in [rejected_raw, rejected_json] {
+        assert_eq!(rejected.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(rejected.headers().get("content-type").unwrap(), PROBLEM_JSON);
+        let problem: Value = test::read_body_json(rejected).await;
+        assert_eq!(problem["type"], "urn:rust-camel:problem:payload-too-large");
+        assert_eq!(problem["status"], 413);
+    }
+}
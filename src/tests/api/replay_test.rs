@@ -0,0 +1,84 @@
+use crate::{
+    interfaces::api::rest::MessageRequest,
+    tests::helpers::setup_test_app,
+};
+use actix_web::{http::StatusCode, test};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+#[actix_rt::test]
+async fn test_replay_job_reports_progress() {
+    // Arrange
+    let app = setup_test_app().await;
+    let req = test::TestRequest::post().uri("/api/routes/test-route/start").to_request();
+    test::call_service(&app, req).await;
+    let req = test::TestRequest::post()
+        .uri("/api/messages")
+        .set_json(MessageRequest::text("replay me"))
+        .to_request();
+    let created: Value = test::call_and_read_body_json(&app, req).await;
+
+    // Act
+    let req = test::TestRequest::post()
+        .uri("/api/replays")
+        .set_json(json!({ "route_id": "test-route", "filter": {}, "rate_limit": 100.0 }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    let status = resp.status();
+    let location = resp.headers().get("Location").unwrap().to_str().unwrap().to_string();
+    let mut job = Value::Null;
+    for _ in 0..100 {
+        let req = test::TestRequest::get().uri(&location).to_request();
+        job = test::call_and_read_body_json(&app, req).await;
+        if job["status"] != "running" {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    // Assert
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert_eq!(job["status"], "completed");
+    assert_eq!(job["total"], 1);
+    assert_eq!(job["succeeded"], 1);
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/messages/{}/revisions", created["id"].as_str().unwrap()))
+        .to_request();
+    let revisions: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(revisions[2]["trigger"], "replay");
+
+    let req = test::TestRequest::post().uri(&format!("{}/cancel", location)).to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+}
+
+#[actix_rt::test]
+async fn test_replay_requires_exactly_one_selection() {
+    // Arrange
+    let app = setup_test_app().await;
+
+    // Act
+    let req = test::TestRequest::post()
+        .uri("/api/replays")
+        .set_json(json!({ "route_id": "test-route", "ids": [], "filter": {} }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    // Assert
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_rt::test]
+async fn test_unknown_replay_is_not_found() {
+    // Arrange
+    let app = setup_test_app().await;
+
+    // Act
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/replays/{}", uuid::Uuid::new_v4()))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    // Assert
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
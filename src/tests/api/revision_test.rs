@@ -0,0 +1,61 @@
+use crate::{
+    interfaces::api::rest::{MessageRequest, ProcessMessageRequest},
+    tests::helpers::setup_test_app,
+};
+use actix_web::{http::StatusCode, test};
+use serde_json::Value;
+
+#[actix_rt::test]
+async fn test_reprocessing_adds_revision() {
+    // Arrange
+    let app = setup_test_app().await;
+    let req = test::TestRequest::post()
+        .uri("/api/messages")
+        .set_json(MessageRequest::text("original"))
+        .to_request();
+    let created: Value = test::call_and_read_body_json(&app, req).await;
+    let id = created["id"].as_str().unwrap().to_string();
+
+    // Act
+    let req = test::TestRequest::post()
+        .uri("/api/messages/process")
+        .insert_header(("X-Triggered-By", "ops"))
+        .set_json(&ProcessMessageRequest {
+            message_id: id.clone(),
+            additional_data: Some("retry".to_string()),
+        })
+        .to_request();
+    test::call_service(&app, req).await;
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/messages/{}/revisions", id))
+        .to_request();
+    let revisions: Value = test::call_and_read_body_json(&app, req).await;
+
+    // Assert
+    assert_eq!(revisions.as_array().unwrap().len(), 3);
+    assert_eq!(revisions[0]["trigger"], "received");
+    assert_eq!(revisions[0]["message"]["body"], "original");
+    assert!(revisions[0]["message"]["headers"].get("processed_by").is_none());
+    assert!(revisions[0]["changes"].is_null());
+    assert_eq!(revisions[1]["trigger"], "create");
+    assert_eq!(revisions[2]["version"], 3);
+    assert_eq!(revisions[2]["trigger"], "reprocess");
+    assert_eq!(revisions[2]["triggered_by"], "ops");
+    assert_eq!(revisions[2]["message"]["body"], "original");
+    assert!(revisions[2]["changes"]["headers"].is_object());
+}
+
+#[actix_rt::test]
+async fn test_revisions_of_unknown_message_is_not_found() {
+    // Arrange
+    let app = setup_test_app().await;
+
+    // Act
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/messages/{}/revisions", uuid::Uuid::new_v4()))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    // Assert
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
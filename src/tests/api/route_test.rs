@@ -0,0 +1,55 @@
+use crate::tests::helpers::setup_test_app;
+use actix_web::{http::StatusCode, test};
+use serde_json::Value;
+
+#[actix_rt::test]
+async fn test_list_routes() {
+    // Arrange
+    let app = setup_test_app().await;
+
+    // Act
+    let req = test::TestRequest::get().uri("/api/routes").to_request();
+    let resp: Value = test::call_and_read_body_json(&app, req).await;
+
+    // Assert
+    let route = resp.as_array().unwrap().iter().find(|route| route["id"] == "test-route").unwrap();
+    assert_eq!(route["from_uri"], "direct:test");
+    assert_eq!(route["status"], "Stopped");
+}
+
+#[actix_rt::test]
+async fn test_route_lifecycle_actions() {
+    let app = setup_test_app().await;
+
+    for (action, expected) in [("start", "Started"), ("suspend", "Suspended"), ("resume", "Started"), ("stop", "Stopped")] {
+        let req = test::TestRequest::post()
+            .uri(&format!("/api/routes/test-route/{}", action))
+            .to_request();
+        let resp: Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["status"], expected, "after {}", action);
+    }
+}
+
+#[actix_rt::test]
+async fn test_invalid_route_transition_is_conflict() {
+    let app = setup_test_app().await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/routes/test-route/resume")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+}
+
+#[actix_rt::test]
+async fn test_unknown_route_is_not_found() {
+    let app = setup_test_app().await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/routes/missing/start")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,70 @@
+use crate::{
+    interfaces::api::rest::{MessageRequest, ProcessMessageRequest},
+    tests::helpers::setup_test_app,
+};
+use actix_web::{http::StatusCode, test};
+use serde_json::Value;
+
+#[actix_rt::test]
+async fn test_if_match_guards_processing() {
+    // Arrange
+    let app = setup_test_app().await;
+    let req = test::TestRequest::post()
+        .uri("/api/messages")
+        .set_json(MessageRequest::text("versioned"))
+        .to_request();
+    let created = test::call_service(&app, req).await;
+    let etag = created.headers().get("etag").unwrap().to_str().unwrap().to_string();
+    let body: Value = test::read_body_json(created).await;
+    let id = body["id"].as_str().unwrap().to_string();
+    let process = |etag: &str| {
+        test::TestRequest::post()
+            .uri("/api/messages/process")
+            .insert_header(("If-Match", etag.to_string()))
+            .set_json(&ProcessMessageRequest {
+                message_id: id.clone(),
+                additional_data: None,
+            })
+            .to_request()
+    };
+
+    // Act
+    let first = test::call_service(&app, process(&etag)).await;
+    let first_status = first.status();
+    let new_etag = first.headers().get("etag").unwrap().to_str().unwrap().to_string();
+    let stale = test::call_service(&app, process(&etag)).await;
+
+    // Assert
+    assert_eq!(etag, "\"2\"");
+    assert_eq!(first_status, StatusCode::OK);
+    assert_eq!(new_etag, "\"3\"");
+    assert_eq!(stale.status(), StatusCode::CONFLICT);
+}
+
+#[actix_rt::test]
+async fn test_if_match_guards_delete() {
+    // Arrange
+    let app = setup_test_app().await;
+    let req = test::TestRequest::post()
+        .uri("/api/messages")
+        .set_json(MessageRequest::text("versioned"))
+        .to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    let uri = format!("/api/messages/{}", body["id"].as_str().unwrap());
+
+    // Act
+    let req = test::TestRequest::get().uri(&uri).to_request();
+    let fetched = test::call_service(&app, req).await;
+    let etag = fetched.headers().get("etag").unwrap().to_str().unwrap().to_string();
+    let req = test::TestRequest::delete().uri(&uri).insert_header(("If-Match", "\"7\"")).to_request();
+    let stale = test::call_service(&app, req).await;
+    let req = test::TestRequest::delete().uri(&uri).insert_header(("If-Match", "not-an-etag")).to_request();
+    let malformed = test::call_service(&app, req).await;
+    let req = test::TestRequest::delete().uri(&uri).insert_header(("If-Match", etag)).to_request();
+    let current = test::call_service(&app, req).await;
+
+    // Assert
+    assert_eq!(stale.status(), StatusCode::CONFLICT);
+    assert_eq!(malformed.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(current.status(), StatusCode::NO_CONTENT);
+}
@@ -0,0 +1,110 @@
+use crate::application::{
+    aggregation::JsonArrayStrategy,
+    processors::{
+        splitter::{SplitExpression, SplitterProcessor},
+        transform::TransformProcessor,
+    },
+};
+use crate::domain::{
+    models::{
+        body::{Body, BodyEncoding},
+        error::ErrorKind,
+        exchange::Exchange,
+    },
+    ports::{processor::Processor, repository::MessageRepository},
+};
+use crate::infrastructure::repositories::sqlite_message_repository::SqliteMessageRepository;
+use bytes::Bytes;
+use serde_json::json;
+use std::sync::Arc;
+
+#[test]
+fn test_body_converts_lazily_between_representations() {
+    let json = Body::from(json!({"id": 7}));
+    let text = Body::from(r#"{"id": 7}"#);
+    let binary = Body::from(vec![0xff, 0x00]);
+
+    assert_eq!(json.text().unwrap(), r#"{"id":7}"#);
+    assert_eq!(text.json().unwrap()["id"], 7);
+    assert_eq!(binary.bytes().unwrap().as_ref(), &[0xff, 0x00]);
+    assert_eq!(binary.text().unwrap_err().kind(), ErrorKind::Validation);
+    assert_eq!(binary.to_string(), "<2 bytes>");
+}
+
+#[test]
+fn test_body_serialization_keeps_plain_text_compatible() {
+    let exchange = Exchange::new(vec![0xca, 0xfe]);
+    let stored = serde_json::to_value(&exchange).unwrap();
+    let legacy: Body = serde_json::from_value(json!("stored before bodies were typed")).unwrap();
+
+    assert_eq!(stored["body"], json!({"base64": "yv4="}));
+    assert_eq!(serde_json::from_value::<Exchange>(stored).unwrap().body, exchange.body);
+    assert_eq!(legacy, Body::Text("stored before bodies were typed".to_string()));
+    assert_eq!(Body::decode("yv4=", BodyEncoding::Base64).unwrap(), exchange.body);
+}
+
+#[actix_rt::test]
+async fn test_stream_body_is_buffered_once() {
+    // Arrange
+    let chunks = vec![Ok(Bytes::from_static(b"hello ")), Ok(Bytes::from_static(b"world"))];
+    let mut body = Body::from_stream(futures::stream::iter(chunks));
+    let mut shared = body.clone();
+
+    // Act
+    let unread = body.text().unwrap_err();
+    body.buffer().await.unwrap();
+
+    // Assert
+    assert_eq!(unread.kind(), ErrorKind::Validation);
+    assert!(serde_json::to_string(&shared).is_err());
+    assert_eq!(body, "hello world");
+    assert_eq!(shared.buffer().await.unwrap_err().kind(), ErrorKind::Validation);
+}
+
+#[actix_rt::test]
+async fn test_text_transformer_reads_stream_and_rejects_binary() {
+    // Arrange
+    let upper = TransformProcessor::with_transformer(|body| Ok(body.to_uppercase()));
+    let stream = Body::from_stream(futures::stream::iter(vec![Ok(Bytes::from_static(b"abc"))]));
+
+    // Act
+    let streamed = upper.process(Exchange::new(stream)).await.unwrap();
+    let binary = upper.process(Exchange::new(vec![0xff])).await.unwrap_err();
+
+    // Assert
+    assert_eq!(streamed.body, "ABC");
+    assert_eq!(binary.kind(), ErrorKind::Validation);
+}
+
+#[actix_rt::test]
+async fn test_json_array_splits_into_json_parts() {
+    // Arrange
+    let identity: Arc<dyn Processor> = Arc::new(TransformProcessor::new());
+    let splitter = SplitterProcessor::new(SplitExpression::JsonArray, identity)
+        .aggregate(Arc::new(JsonArrayStrategy));
+
+    // Act
+    let processed = splitter
+        .process(Exchange::new(json!([{"id": 1}, {"id": 2}])))
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(processed.body, Body::Json(json!([{"id": 1}, {"id": 2}])));
+}
+
+#[actix_rt::test]
+async fn test_sqlite_repository_round_trips_typed_bodies() {
+    // Arrange
+    let repository = SqliteMessageRepository::in_memory().unwrap();
+    let binary = Exchange::new(vec![0x89, b'P', b'N', b'G']);
+    let json = Exchange::new(json!({"total": 12.5}));
+
+    // Act
+    repository.save(&binary).await.unwrap();
+    repository.save(&json).await.unwrap();
+
+    // Assert
+    assert_eq!(repository.find_by_id(&binary.id).await.unwrap().unwrap().body, binary.body);
+    assert_eq!(repository.find_by_id(&json.id).await.unwrap().unwrap().body, json.body);
+}
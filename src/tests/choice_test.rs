@@ -0,0 +1,67 @@
+use crate::application::{
+    pipeline::ProcessorPipeline,
+    processors::{choice::ChoiceProcessor, transform::TransformProcessor},
+    route::from,
+};
+use crate::domain::{models::exchange::Exchange, ports::processor::Processor};
+use std::sync::Arc;
+
+fn upper() -> Arc<dyn Processor> {
+    Arc::new(TransformProcessor::with_transformer(|body| Ok(body.to_uppercase())))
+}
+
+fn suffix(tag: &'static str) -> Arc<dyn Processor> {
+    Arc::new(TransformProcessor::with_transformer(move |body| Ok(format!("{}-{}", body, tag))))
+}
+
+fn choice() -> ChoiceProcessor {
+    let mut gold = ProcessorPipeline::new();
+    gold.add_processor(upper());
+    gold.add_processor(suffix("gold"));
+
+    ChoiceProcessor::new()
+        .when(|exchange| exchange.body.text().is_ok_and(|body| body.starts_with("gold")), Arc::new(gold))
+        .when_named("silver", |exchange| exchange.body.text().is_ok_and(|body| body.starts_with("silver")), suffix("silver"))
+        .otherwise(suffix("other"))
+}
+
+#[actix_rt::test]
+async fn test_choice_dispatches_to_first_matching_branch() {
+    let processor = choice();
+
+    let gold = processor.process(Exchange::new("gold order".to_string())).await.unwrap();
+    let silver = processor.process(Exchange::new("silver order".to_string())).await.unwrap();
+    let other = processor.process(Exchange::new("bronze order".to_string())).await.unwrap();
+
+    assert_eq!(gold.body, "GOLD ORDER-gold");
+    assert_eq!(silver.body, "silver order-silver");
+    assert_eq!(other.body, "bronze order-other");
+}
+
+#[actix_rt::test]
+async fn test_choice_records_branch_in_history() {
+    let processed = choice()
+        .process(Exchange::new("silver order".to_string()))
+        .await
+        .unwrap();
+
+    let step = processed.processing_history.last().unwrap();
+    assert_eq!(step.processor_name, "choice");
+    assert_eq!(step.notes.as_deref(), Some("branch=silver"));
+}
+
+#[actix_rt::test]
+async fn test_choice_without_otherwise_passes_through() {
+    let route = from("direct:choice")
+        .choice(ChoiceProcessor::new().when(|exchange| exchange.body == "match", upper()))
+        .build()
+        .unwrap();
+
+    let processed = route.process(Exchange::new("no match".to_string())).await.unwrap();
+
+    assert_eq!(processed.body, "no match");
+    assert_eq!(
+        processed.processing_history.last().unwrap().notes.as_deref(),
+        Some("branch=none")
+    );
+}
@@ -0,0 +1,266 @@
+use crate::application::{
+    context::{CamelContext, RouteStatus},
+    route::from,
+};
+use crate::domain::{
+    models::{
+        endpoint::EndpointUri,
+        error::{DomainError, ErrorKind},
+        exchange::Exchange,
+    },
+    ports::{
+        component::Component,
+        endpoint::{Consumer, Endpoint, Producer},
+        processor::Processor,
+    },
+};
+use crate::infrastructure::components::direct::DirectComponent;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Barrier};
+
+#[actix_rt::test]
+async fn test_context_processes_only_started_routes() {
+    // Arrange
+    let context = CamelContext::new("test");
+    context
+        .add_route(
+            from("direct:upper")
+                .route_id("upper")
+                .transform(|body| Ok(body.to_uppercase()))
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+
+    // Act & Assert
+    assert!(context.process("upper", Exchange::new("a".to_string())).await.is_err());
+
+    context.start().unwrap();
+    let processed = context.process("upper", Exchange::new("a".to_string())).await.unwrap();
+    assert_eq!(processed.body, "A");
+
+    context.suspend_route("upper").unwrap();
+    assert_eq!(context.route_status("upper").unwrap(), Some(RouteStatus::Suspended));
+    assert!(context.process("upper", Exchange::new("a".to_string())).await.is_err());
+
+    context.stop().unwrap();
+    assert_eq!(context.route_status("upper").unwrap(), Some(RouteStatus::Stopped));
+}
+
+#[test]
+fn test_context_rejects_duplicate_route_ids() {
+    let context = CamelContext::new("test");
+    context.add_route(from("direct:a").route_id("dup").build().unwrap()).unwrap();
+
+    assert!(context.add_route(from("direct:b").route_id("dup").build().unwrap()).is_err());
+}
+
+#[test]
+fn test_context_only_removes_stopped_routes() {
+    let context = CamelContext::new("test");
+    context.add_route(from("direct:a").route_id("a").build().unwrap()).unwrap();
+    context.start_route("a").unwrap();
+
+    assert!(context.remove_route("a").is_err());
+    context.stop_route("a").unwrap();
+    assert!(context.remove_route("a").is_ok());
+    assert!(context.route("a").unwrap().is_none());
+}
+
+/// A component whose consumers fail to stop the first time.
+struct StubbornComponent {
+    stops: Arc<AtomicUsize>,
+}
+
+struct StubbornEndpoint {
+    uri: EndpointUri,
+    stops: Arc<AtomicUsize>,
+}
+
+struct StubbornConsumer {
+    stops: Arc<AtomicUsize>,
+}
+
+impl Component for StubbornComponent {
+    fn scheme(&self) -> &str {
+        "stubborn"
+    }
+
+    fn create_endpoint(&self, uri: &EndpointUri) -> Result<Arc<dyn Endpoint>, DomainError> {
+        Ok(Arc::new(StubbornEndpoint {
+            uri: uri.clone(),
+            stops: self.stops.clone(),
+        }))
+    }
+}
+
+impl Endpoint for StubbornEndpoint {
+    fn uri(&self) -> &EndpointUri {
+        &self.uri
+    }
+
+    fn create_producer(&self) -> Result<Arc<dyn Producer>, DomainError> {
+        Err(DomainError::validation("stubborn endpoints only consume"))
+    }
+
+    fn create_consumer(&self, _processor: Arc<dyn Processor>) -> Result<Box<dyn Consumer>, DomainError> {
+        Ok(Box::new(StubbornConsumer {
+            stops: self.stops.clone(),
+        }))
+    }
+}
+
+impl Consumer for StubbornConsumer {
+    fn start(&mut self) -> Result<(), DomainError> {
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), DomainError> {
+        match self.stops.fetch_add(1, Ordering::SeqCst) {
+            0 => Err(DomainError::transient("still busy")),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[actix_rt::test]
+async fn test_failed_start_unbinds_senders() {
+    // Arrange
+    let context = CamelContext::new("test");
+    context.add_component(Arc::new(DirectComponent::new())).unwrap();
+    context
+        .add_route(from("direct:in").route_id("half").to("direct:out").to("missing:x").build().unwrap())
+        .unwrap();
+
+    // Act
+    let started = context.start_route("half");
+
+    // Assert - an unbound sender only records the destination instead of calling direct:out
+    assert!(started.is_err());
+    assert_eq!(context.route_status("half").unwrap(), Some(RouteStatus::Stopped));
+    let route = context.route("half").unwrap().unwrap();
+    assert!(route.process(Exchange::new("a")).await.is_ok());
+}
+
+#[test]
+fn test_failed_stop_keeps_the_route_started() {
+    // Arrange
+    let context = CamelContext::new("test");
+    let stops = Arc::new(AtomicUsize::new(0));
+    context
+        .add_component(Arc::new(StubbornComponent { stops: stops.clone() }))
+        .unwrap();
+    context.add_route(from("stubborn:a").route_id("a").build().unwrap()).unwrap();
+    context.start_route("a").unwrap();
+
+    // Act
+    let first = context.stop_route("a");
+    let status = context.route_status("a").unwrap();
+    let second = context.stop_route("a");
+
+    // Assert
+    assert!(first.is_err());
+    assert_eq!(status, Some(RouteStatus::Started));
+    assert!(second.is_ok());
+    assert_eq!(stops.load(Ordering::SeqCst), 2);
+    assert_eq!(context.route_status("a").unwrap(), Some(RouteStatus::Stopped));
+}
+
+
+/// A component whose consumers start only once the test has seen them starting.
+struct GatedComponent {
+    entered: Arc<Barrier>,
+    released: Arc<Barrier>,
+}
+
+struct GatedEndpoint {
+    uri: EndpointUri,
+    entered: Arc<Barrier>,
+    released: Arc<Barrier>,
+}
+
+struct GatedConsumer {
+    entered: Arc<Barrier>,
+    released: Arc<Barrier>,
+}
+
+impl Component for GatedComponent {
+    fn scheme(&self) -> &str {
+        "gated"
+    }
+
+    fn create_endpoint(&self, uri: &EndpointUri) -> Result<Arc<dyn Endpoint>, DomainError> {
+        Ok(Arc::new(GatedEndpoint {
+            uri: uri.clone(),
+            entered: self.entered.clone(),
+            released: self.released.clone(),
+        }))
+    }
+}
+
+impl Endpoint for GatedEndpoint {
+    fn uri(&self) -> &EndpointUri {
+        &self.uri
+    }
+
+    fn create_producer(&self) -> Result<Arc<dyn Producer>, DomainError> {
+        Err(DomainError::validation("gated endpoints only consume"))
+    }
+
+    fn create_consumer(&self, _processor: Arc<dyn Processor>) -> Result<Box<dyn Consumer>, DomainError> {
+        Ok(Box::new(GatedConsumer {
+            entered: self.entered.clone(),
+            released: self.released.clone(),
+        }))
+    }
+}
+
+impl Consumer for GatedConsumer {
+    fn start(&mut self) -> Result<(), DomainError> {
+        self.entered.wait();
+        self.released.wait();
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), DomainError> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_starting_route_does_not_block_other_routes() {
+    // Arrange
+    let context = Arc::new(CamelContext::new("test"));
+    let entered = Arc::new(Barrier::new(2));
+    let released = Arc::new(Barrier::new(2));
+    context
+        .add_component(Arc::new(GatedComponent {
+            entered: entered.clone(),
+            released: released.clone(),
+        }))
+        .unwrap();
+    context.add_component(Arc::new(DirectComponent::new())).unwrap();
+    context.add_route(from("gated:a").route_id("slow").build().unwrap()).unwrap();
+    context.add_route(from("direct:b").route_id("fast").build().unwrap()).unwrap();
+
+    // Act
+    let starting = {
+        let context = context.clone();
+        std::thread::spawn(move || context.start_route("slow"))
+    };
+    entered.wait();
+    let status = context.route_status("slow").unwrap();
+    let again = context.start_route("slow");
+    let removed = context.remove_route("slow");
+    let other = context.start_route("fast");
+    released.wait();
+    let started = starting.join().unwrap();
+
+    // Assert
+    assert_eq!(status, Some(RouteStatus::Starting));
+    assert_eq!(again.unwrap_err().kind(), ErrorKind::Conflict);
+    assert_eq!(removed.unwrap_err().kind(), ErrorKind::Conflict);
+    assert!(other.is_ok());
+    assert!(started.is_ok());
+    assert_eq!(context.route_status("slow").unwrap(), Some(RouteStatus::Started));
+}
@@ -0,0 +1,109 @@
+use crate::domain::models::{
+    body::{Body, CONTENT_TYPE},
+    conversion::{ConversionContext, TypeConverterRegistry},
+    error::{DomainError, ErrorKind},
+    exchange::Exchange,
+};
+use bytes::Bytes;
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Order {
+    id: u32,
+    item: String,
+}
+
+fn field_message(error: &DomainError) -> String {
+    error.fields()[0].message.clone()
+}
+
+#[test]
+fn test_header_as_converts_scalars() {
+    let mut exchange = Exchange::new("headers");
+    exchange.set_header("count", "42");
+    exchange.set_header("urgent", "TRUE");
+    exchange.set_header("due", "2024-05-01T12:00:00Z");
+
+    assert_eq!(exchange.header_as::<i64>("count").unwrap(), Some(42));
+    assert_eq!(exchange.header_as::<bool>("urgent").unwrap(), Some(true));
+    assert_eq!(
+        exchange.header_as::<DateTime<Utc>>("due").unwrap(),
+        Some(Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap())
+    );
+    assert_eq!(exchange.header_as::<i64>("missing").unwrap(), None);
+}
+
+#[test]
+fn test_failed_conversion_names_field_and_types() {
+    let mut exchange = Exchange::new(vec![0xff]);
+    exchange.set_header("count", "many");
+
+    let header = exchange.header_as::<i64>("count").unwrap_err();
+    let body = exchange.body_as::<String>().unwrap_err();
+    let missing = exchange.body_as::<Order>().unwrap_err();
+
+    assert_eq!(header.kind(), ErrorKind::Validation);
+    assert_eq!(header.fields()[0].field, "count");
+    assert!(field_message(&header).starts_with("cannot convert String to i64: 'many'"));
+    assert!(field_message(&body).starts_with("cannot convert Bytes to String: invalid UTF-8"));
+    assert_eq!(
+        field_message(&missing),
+        "cannot convert Bytes to Order: no type converter registered"
+    );
+}
+
+#[test]
+fn test_bytes_to_string_uses_charset() {
+    let mut exchange = Exchange::new(vec![b'c', b'a', b'f', 0xe9]);
+    exchange.set_header(CONTENT_TYPE, "text/plain; charset=ISO-8859-1");
+
+    assert_eq!(exchange.body_as::<String>().unwrap(), "café");
+    assert!(TypeConverterRegistry::new()
+        .convert::<String, Bytes>("body", &"café".to_string(), &ConversionContext::default().charset("us-ascii"))
+        .is_err());
+}
+
+#[test]
+fn test_json_and_typed_struct_convert_through_serde() {
+    // Arrange
+    let registry = TypeConverterRegistry::new();
+    registry.register_json::<Order>().unwrap();
+    let context = ConversionContext::default();
+    let bytes = Body::from(br#"{"id": 7, "item": "book"}"#.to_vec());
+
+    // Act
+    let from_bytes: Order = registry.convert_body(&bytes, &context).unwrap();
+    let to_json: Value = registry.convert("order", &from_bytes, &context).unwrap();
+    let from_text: Value = registry.convert_body(&Body::from(r#"[1, 2]"#), &context).unwrap();
+
+    // Assert
+    assert_eq!(from_bytes, Order { id: 7, item: "book".to_string() });
+    assert_eq!(to_json, json!({"id": 7, "item": "book"}));
+    assert_eq!(from_text, json!([1, 2]));
+}
+
+#[test]
+fn test_custom_converters_can_be_registered() {
+    let registry = TypeConverterRegistry::empty();
+    registry
+        .register::<String, Order, _>(|text, _| {
+            let (id, item) = text.split_once(':').ok_or("expected id:item")?;
+            Ok(Order {
+                id: id.parse().map_err(|_| "bad id")?,
+                item: item.to_string(),
+            })
+        })
+        .unwrap();
+
+    let order: Order = registry
+        .convert_body(&Body::from("3:pen"), &ConversionContext::default())
+        .unwrap();
+    let error = registry
+        .convert_body::<Order>(&Body::from("pen"), &ConversionContext::default())
+        .unwrap_err();
+
+    assert_eq!(order, Order { id: 3, item: "pen".to_string() });
+    assert_eq!(field_message(&error), "cannot convert String to Order: expected id:item");
+}
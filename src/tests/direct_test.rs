@@ -0,0 +1,90 @@
+use crate::application::{context::CamelContext, route::from};
+use crate::domain::models::{error::ErrorKind, exchange::Exchange};
+use crate::infrastructure::components::direct::DirectComponent;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn context() -> CamelContext {
+    let context = CamelContext::new("test");
+    context.add_component(Arc::new(DirectComponent::new())).unwrap();
+    context
+}
+
+#[actix_rt::test]
+async fn test_direct_runs_a_shared_sub_route_inline() {
+    // Arrange
+    let context = context();
+    let mut metadata = HashMap::new();
+    metadata.insert("audited".to_string(), "yes".to_string());
+    context
+        .add_route(from("direct:audit").route_id("audit").enrich(metadata).build().unwrap())
+        .unwrap();
+    for id in ["orders", "invoices"] {
+        context
+            .add_route(
+                from(&format!("rest:/{}", id))
+                    .route_id(id)
+                    .to("direct:audit")
+                    .transform(|body| Ok(body.to_uppercase()))
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap();
+    }
+    context.start().unwrap();
+    let exchange = Exchange::new("order");
+    let id = exchange.id;
+
+    // Act
+    let orders = context.process("orders", exchange).await.unwrap();
+    let invoices = context.process("invoices", Exchange::new("invoice")).await.unwrap();
+
+    // Assert
+    assert_eq!(orders.id, id);
+    assert_eq!(orders.body, "ORDER");
+    assert_eq!(orders.headers["audited"], "yes");
+    assert_eq!(invoices.headers["audited"], "yes");
+    let steps: Vec<&str> = orders.processing_history.iter().map(|step| step.processor_name.as_str()).collect();
+    assert_eq!(steps.first(), Some(&"enricher"), "the sub-route runs before the caller continues");
+    assert!(steps.contains(&"to:direct:audit"));
+}
+
+#[actix_rt::test]
+async fn test_direct_without_a_consumer_fails_clearly() {
+    // Arrange
+    let context = context();
+    context
+        .add_route(from("direct:audit").route_id("audit").log("AUDIT").build().unwrap())
+        .unwrap();
+    context
+        .add_route(from("rest:/orders").route_id("orders").to("direct:audit").build().unwrap())
+        .unwrap();
+    context.start_route("orders").unwrap();
+
+    // Act
+    let error = context.process("orders", Exchange::new("order")).await.unwrap_err();
+
+    // Assert
+    assert_eq!(error.kind(), ErrorKind::NotFound);
+    assert!(error.to_string().contains("No consumer on endpoint direct:audit"));
+
+    context.start_route("audit").unwrap();
+    assert!(context.process("orders", Exchange::new("order")).await.is_ok());
+    context.stop_route("audit").unwrap();
+    assert!(context.process("orders", Exchange::new("order")).await.is_err());
+}
+
+#[test]
+fn test_direct_allows_one_consumer_per_name() {
+    let context = context();
+    for id in ["first", "second"] {
+        context
+            .add_route(from("direct:shared").route_id(id).log(id).build().unwrap())
+            .unwrap();
+    }
+
+    context.start_route("first").unwrap();
+    let error = context.start_route("second").unwrap_err();
+
+    assert_eq!(error.kind(), ErrorKind::Conflict);
+}
@@ -0,0 +1,160 @@
+use crate::application::{context::CamelContext, registry::ComponentRegistry, route::from};
+use crate::domain::{
+    models::{endpoint::EndpointUri, error::DomainError, exchange::Exchange},
+    ports::{
+        component::Component,
+        endpoint::{Consumer, Endpoint, Producer},
+        processor::Processor,
+    },
+};
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+struct MockComponent {
+    created: Arc<AtomicUsize>,
+    consuming: Arc<AtomicBool>,
+}
+
+struct MockEndpoint {
+    uri: EndpointUri,
+    consuming: Arc<AtomicBool>,
+}
+
+struct MockProducer;
+
+struct MockConsumer {
+    consuming: Arc<AtomicBool>,
+}
+
+impl Component for MockComponent {
+    fn scheme(&self) -> &str {
+        "mock"
+    }
+
+    fn create_endpoint(&self, uri: &EndpointUri) -> Result<Arc<dyn Endpoint>, DomainError> {
+        self.created.fetch_add(1, Ordering::SeqCst);
+        Ok(Arc::new(MockEndpoint {
+            uri: uri.clone(),
+            consuming: self.consuming.clone(),
+        }))
+    }
+}
+
+impl Endpoint for MockEndpoint {
+    fn uri(&self) -> &EndpointUri {
+        &self.uri
+    }
+
+    fn create_producer(&self) -> Result<Arc<dyn Producer>, DomainError> {
+        Ok(Arc::new(MockProducer))
+    }
+
+    fn create_consumer(&self, _processor: Arc<dyn Processor>) -> Result<Box<dyn Consumer>, DomainError> {
+        Ok(Box::new(MockConsumer {
+            consuming: self.consuming.clone(),
+        }))
+    }
+}
+
+#[async_trait]
+impl Producer for MockProducer {
+    async fn send(&self, mut exchange: Exchange) -> Result<Exchange, DomainError> {
+        exchange.set_header("mock_received", "true");
+        Ok(exchange)
+    }
+}
+
+impl Consumer for MockConsumer {
+    fn start(&mut self) -> Result<(), DomainError> {
+        self.consuming.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), DomainError> {
+        self.consuming.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+fn mock_component() -> (Arc<MockComponent>, Arc<AtomicUsize>, Arc<AtomicBool>) {
+    let created = Arc::new(AtomicUsize::new(0));
+    let consuming = Arc::new(AtomicBool::new(false));
+    let component = Arc::new(MockComponent {
+        created: created.clone(),
+        consuming: consuming.clone(),
+    });
+    (component, created, consuming)
+}
+
+#[test]
+fn test_endpoint_uri_parses_scheme_path_and_options() {
+    let uri = EndpointUri::parse("seda:orders?size=100&blockWhenFull=true").unwrap();
+
+    assert_eq!(uri.scheme, "seda");
+    assert_eq!(uri.path, "orders");
+    assert_eq!(uri.option::<usize>("size").unwrap(), Some(100));
+    assert!(uri.option_or("blockWhenFull", false).unwrap());
+    assert_eq!(uri.option_or("concurrentConsumers", 1u32).unwrap(), 1);
+    assert_eq!(uri.to_string(), "seda:orders?blockWhenFull=true&size=100");
+
+    let file = EndpointUri::parse("file:/data/in").unwrap();
+    assert_eq!(file.path, "/data/in");
+}
+
+#[test]
+fn test_endpoint_uri_rejects_invalid_input() {
+    assert!(EndpointUri::parse("no-scheme").is_err());
+    assert!(EndpointUri::parse("seda:").is_err());
+    assert!(EndpointUri::parse(":orders").is_err());
+
+    let uri = EndpointUri::parse("seda:orders?size=lots").unwrap();
+    assert!(matches!(uri.option::<usize>("size"), Err(DomainError::Validation { .. })));
+}
+
+#[test]
+fn test_registry_caches_endpoints_by_normalized_uri() {
+    let registry = ComponentRegistry::new();
+    let (component, created, _) = mock_component();
+    registry.register(component).unwrap();
+
+    registry.endpoint("mock:a?x=1&y=2").unwrap();
+    registry.endpoint("mock:a?y=2&x=1").unwrap();
+    registry.endpoint("mock:b").unwrap();
+
+    assert_eq!(created.load(Ordering::SeqCst), 2);
+    assert!(registry.endpoint("unknown:a").is_err());
+}
+
+#[actix_rt::test]
+async fn test_context_binds_consumers_and_producers_on_start() {
+    // Arrange
+    let context = CamelContext::new("test");
+    let (component, _, consuming) = mock_component();
+    context.add_component(component).unwrap();
+    context
+        .add_route(from("mock:in").route_id("mock-route").to("mock:out").build().unwrap())
+        .unwrap();
+
+    // Act
+    context.start().unwrap();
+    let processed = context.process("mock-route", Exchange::new("hi".to_string())).await.unwrap();
+
+    // Assert
+    assert!(consuming.load(Ordering::SeqCst));
+    assert_eq!(processed.headers.get("mock_received").unwrap(), "true");
+
+    context.stop().unwrap();
+    assert!(!consuming.load(Ordering::SeqCst));
+}
+
+#[test]
+fn test_context_fails_to_start_route_with_unknown_producer_scheme() {
+    let context = CamelContext::new("test");
+    context
+        .add_route(from("rest:/in").route_id("r").to("nowhere:out").build().unwrap())
+        .unwrap();
+
+    assert!(context.start_route("r").is_err());
+    assert_eq!(context.route_status("r").unwrap(), Some(crate::application::context::RouteStatus::Stopped));
+}
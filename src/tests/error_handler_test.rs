@@ -0,0 +1,151 @@
+use crate::application::{
+    context::CamelContext,
+    error_handler::{ErrorHandler, RedeliveryPolicy},
+    route::from,
+    services::dead_letter_service::DeadLetterService,
+};
+use crate::domain::{
+    models::{error::DomainError, exchange::Exchange},
+    ports::{dead_letter::DeadLetterRepository, processor::Processor},
+};
+use crate::infrastructure::repositories::dead_letter_repository::InMemoryDeadLetterRepository;
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Fails the first `failures` calls with the given error, then succeeds.
+struct FlakyProcessor {
+    failures: u32,
+    calls: AtomicU32,
+    error: fn() -> DomainError,
+}
+
+impl FlakyProcessor {
+    fn new(failures: u32, error: fn() -> DomainError) -> Arc<Self> {
+        Arc::new(Self { failures, calls: AtomicU32::new(0), error })
+    }
+}
+
+#[async_trait]
+impl Processor for FlakyProcessor {
+    async fn process(&self, mut exchange: Exchange) -> Result<Exchange, DomainError> {
+        if self.calls.fetch_add(1, Ordering::SeqCst) < self.failures {
+            return Err((self.error)());
+        }
+        exchange.set_header("flaky", "passed");
+        Ok(exchange)
+    }
+}
+
+fn transient() -> DomainError {
+    DomainError::transient("downstream unavailable")
+}
+
+fn invalid() -> DomainError {
+    DomainError::validation("bad payload")
+}
+
+fn fast_policy(max: u32) -> RedeliveryPolicy {
+    RedeliveryPolicy::new(max).redelivery_delay(Duration::from_millis(1))
+}
+
+#[test]
+fn test_redelivery_policy_backoff_is_capped() {
+    let policy = RedeliveryPolicy::new(5)
+        .redelivery_delay(Duration::from_millis(100))
+        .backoff_multiplier(2.0)
+        .maximum_redelivery_delay(Duration::from_millis(300));
+
+    assert_eq!(policy.delay_for(1), Duration::from_millis(100));
+    assert_eq!(policy.delay_for(2), Duration::from_millis(200));
+    assert_eq!(policy.delay_for(3), Duration::from_millis(300));
+
+    let jittered = policy.jitter(0.5).delay_for(1);
+    assert!(jittered >= Duration::from_millis(50) && jittered <= Duration::from_millis(150));
+}
+
+#[actix_rt::test]
+async fn test_redelivery_recovers_and_counts_retries() {
+    let flaky = FlakyProcessor::new(2, transient);
+    let route = from("direct:retry")
+        .error_handler(ErrorHandler::default_error_handler(fast_policy(3)))
+        .process(flaky.clone())
+        .build()
+        .unwrap();
+
+    let processed = route.process(Exchange::new("hi".to_string())).await.unwrap();
+
+    assert_eq!(flaky.calls.load(Ordering::SeqCst), 3);
+    assert_eq!(processed.metadata.retry_count, 2);
+    assert_eq!(processed.headers.get("flaky").unwrap(), "passed");
+    let failed_attempts = processed.processing_history.iter().filter(|step| !step.success).count();
+    assert_eq!(failed_attempts, 2);
+}
+
+#[actix_rt::test]
+async fn test_exhausted_exchange_is_dead_lettered_with_cause() {
+    let dead_letters = Arc::new(InMemoryDeadLetterRepository::new());
+    let route = from("direct:dlq")
+        .route_id("dlq-route")
+        .error_handler(ErrorHandler::dead_letter_repository(dead_letters.clone()).redelivery(fast_policy(2)))
+        .process(FlakyProcessor::new(10, transient))
+        .build()
+        .unwrap();
+
+    let result = route.process(Exchange::new("hi".to_string())).await;
+
+    assert!(result.is_err());
+    let all = dead_letters.find_all().await.unwrap();
+    assert_eq!(all.len(), 1);
+    assert_eq!(all[0].attempts, 3);
+    assert_eq!(all[0].route_id.as_deref(), Some("dlq-route"));
+    assert!(all[0].cause.contains("downstream unavailable"));
+    assert_eq!(all[0].exchange.metadata.retry_count, 2);
+}
+
+#[actix_rt::test]
+async fn test_non_retryable_errors_skip_redelivery() {
+    let dead_letters = Arc::new(InMemoryDeadLetterRepository::new());
+    let flaky = FlakyProcessor::new(10, invalid);
+    let route = from("direct:invalid")
+        .error_handler(ErrorHandler::dead_letter_repository(dead_letters.clone()).redelivery(fast_policy(5)))
+        .process(flaky.clone())
+        .build()
+        .unwrap();
+
+    assert!(route.process(Exchange::new("hi".to_string())).await.is_err());
+    assert_eq!(flaky.calls.load(Ordering::SeqCst), 1);
+    assert_eq!(dead_letters.find_all().await.unwrap()[0].attempts, 1);
+}
+
+#[actix_rt::test]
+async fn test_dead_letter_replay_removes_entry_on_success() {
+    let dead_letters = Arc::new(InMemoryDeadLetterRepository::new());
+    let context = Arc::new(CamelContext::new("test"));
+    // Fails the original delivery and its single redelivery, then recovers on replay
+    context
+        .add_route(
+            from("direct:replay")
+                .route_id("replay-route")
+                .error_handler(ErrorHandler::dead_letter_repository(dead_letters.clone()).redelivery(fast_policy(1)))
+                .process(FlakyProcessor::new(2, transient))
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+    context.start().unwrap();
+    let service = DeadLetterService::new(dead_letters.clone(), context.clone());
+
+    let exchange = Exchange::new("retry me".to_string());
+    let id = exchange.id;
+    assert!(context.process("replay-route", exchange).await.is_err());
+    assert_eq!(service.list().await.unwrap().len(), 1);
+
+    let replayed = service.replay(&id).await.unwrap();
+
+    assert_eq!(replayed.id, id);
+    assert_eq!(replayed.properties.get("replayed_from_dead_letter").unwrap(), "true");
+    assert!(!replayed.properties.contains_key("dead_letter_cause"));
+    assert!(service.list().await.unwrap().is_empty());
+}
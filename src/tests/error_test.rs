@@ -0,0 +1,97 @@
+use crate::application::{processors::filter::FilterProcessor, route::from};
+use crate::domain::{
+    models::{
+        error::{DomainError, ErrorKind},
+        exchange::Exchange,
+    },
+    ports::processor::Processor,
+};
+use crate::interfaces::api::error::{status_for, ProblemDetails};
+use actix_web::http::StatusCode;
+use std::error::Error;
+use std::time::Duration;
+
+#[test]
+fn test_error_kinds_drive_retryability() {
+    assert!(DomainError::transient("broker down").is_retryable());
+    assert!(DomainError::timeout("slow").is_retryable());
+    assert!(!DomainError::fatal("corrupt").is_retryable());
+    assert!(!DomainError::validation("bad").is_retryable());
+    assert!(!DomainError::not_found("missing").is_retryable());
+    assert!(!DomainError::filtered("dropped").is_retryable());
+    assert!(!DomainError::conflict("stale").is_retryable());
+    assert!(DomainError::rate_limited("slow down", Some(Duration::from_secs(2))).is_retryable());
+}
+
+#[test]
+fn test_error_source_chaining() {
+    let io = std::io::Error::new(std::io::ErrorKind::ConnectionRefused, "refused");
+    let error = DomainError::transient("Failed to reach broker").with_source(io);
+
+    assert_eq!(error.kind(), ErrorKind::Transient);
+    assert_eq!(error.message(), "Failed to reach broker");
+    assert_eq!(error.source().unwrap().to_string(), "refused");
+}
+
+#[test]
+fn test_validation_error_carries_field_details() {
+    let error = DomainError::invalid_field("message_id", "must be a UUID");
+
+    assert_eq!(error.kind(), ErrorKind::Validation);
+    assert_eq!(error.fields()[0].field, "message_id");
+    assert_eq!(error.fields()[0].message, "must be a UUID");
+}
+
+#[test]
+fn test_status_codes_follow_error_kind() {
+    assert_eq!(status_for(ErrorKind::NotFound), StatusCode::NOT_FOUND);
+    assert_eq!(status_for(ErrorKind::Validation), StatusCode::BAD_REQUEST);
+    assert_eq!(status_for(ErrorKind::Filtered), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(status_for(ErrorKind::Conflict), StatusCode::CONFLICT);
+    assert_eq!(status_for(ErrorKind::RateLimited), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(status_for(ErrorKind::Transient), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(status_for(ErrorKind::Timeout), StatusCode::GATEWAY_TIMEOUT);
+    assert_eq!(status_for(ErrorKind::Fatal), StatusCode::INTERNAL_SERVER_ERROR);
+}
+
+#[actix_rt::test]
+async fn test_filter_rejection_is_filtered_kind() {
+    let filter = FilterProcessor::with_predicate(|_| false);
+
+    let error = filter.process(Exchange::new("x".to_string())).await.unwrap_err();
+
+    assert_eq!(error.kind(), ErrorKind::Filtered);
+}
+
+#[actix_rt::test]
+async fn test_suspended_route_reports_transient_error() {
+    let context = crate::application::context::CamelContext::new("test");
+    context.add_route(from("direct:a").route_id("a").build().unwrap()).unwrap();
+    context.start().unwrap();
+    context.suspend_route("a").unwrap();
+
+    let error = context.process("a", Exchange::new("x".to_string())).await.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Transient);
+
+    let missing = context.process("missing", Exchange::new("x".to_string())).await.unwrap_err();
+    assert_eq!(missing.kind(), ErrorKind::NotFound);
+}
+
+#[actix_rt::test]
+async fn test_pipeline_errors_name_failing_processor() {
+    let route = from("direct:a").log("TEST").filter(|_| false).build().unwrap();
+    let exchange = Exchange::new("x".to_string());
+    let exchange_id = exchange.id;
+
+    let error = route.process(exchange).await.unwrap_err();
+
+    assert_eq!(error.kind(), ErrorKind::Filtered);
+    assert_eq!(error.exchange_id(), Some(exchange_id));
+    assert_eq!(error.processor(), Some("FilterProcessor"));
+
+    let problem = ProblemDetails::from_error(&error);
+    assert_eq!(problem.status, 422);
+    assert_eq!(problem.exchange_id, Some(exchange_id.to_string()));
+    assert_eq!(problem.processor.as_deref(), Some("FilterProcessor"));
+    assert!(problem.detail.contains(&exchange_id.to_string()));
+}
@@ -0,0 +1,76 @@
+use crate::application::processors::send::SendProcessor;
+use crate::domain::{
+    models::{
+        error::DomainError,
+        exchange::{Exchange, ExchangePattern},
+    },
+    ports::{endpoint::Producer, processor::Processor, repository::MessageRepository},
+};
+use crate::infrastructure::repositories::sqlite_message_repository::SqliteMessageRepository;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+struct EchoProducer;
+
+#[async_trait]
+impl Producer for EchoProducer {
+    async fn send(&self, mut exchange: Exchange) -> Result<Exchange, DomainError> {
+        let reply = format!("echo: {}", exchange.body);
+        exchange.set_out(reply);
+        exchange.out_mut().headers.insert("echoed".to_string(), true.into());
+        Ok(exchange)
+    }
+}
+
+async fn send(pattern: ExchangePattern) -> Exchange {
+    let processor = SendProcessor::new("echo:in");
+    processor.bind(Arc::new(EchoProducer)).unwrap();
+    let mut exchange = Exchange::new("ping");
+    exchange.pattern = pattern;
+    processor.process(exchange).await.unwrap()
+}
+
+#[test]
+fn test_exchange_pattern_parses_names_ignoring_case() {
+    assert_eq!(ExchangePattern::parse("InOut"), Some(ExchangePattern::InOut));
+    assert_eq!(ExchangePattern::parse("inonly"), Some(ExchangePattern::InOnly));
+    assert_eq!(ExchangePattern::parse("request-reply"), None);
+    assert_eq!(ExchangePattern::default(), ExchangePattern::InOnly);
+}
+
+#[actix_rt::test]
+async fn test_in_out_exchange_keeps_the_producer_reply() {
+    let exchange = send(ExchangePattern::InOut).await;
+
+    assert_eq!(exchange.body, "ping");
+    let reply = exchange.into_reply();
+    assert_eq!(reply.body, "echo: ping");
+    assert_eq!(reply.headers["echoed"], "true");
+}
+
+#[actix_rt::test]
+async fn test_in_only_exchange_drops_the_producer_reply() {
+    let exchange = send(ExchangePattern::InOnly).await;
+
+    assert!(exchange.out.is_none());
+    assert_eq!(exchange.into_reply().body, "ping");
+}
+
+#[actix_rt::test]
+async fn test_sqlite_repository_stores_the_out_message() {
+    // Arrange
+    let repository = SqliteMessageRepository::in_memory().unwrap();
+    let replied = send(ExchangePattern::InOut).await;
+    let unreplied = Exchange::new("no reply");
+
+    // Act
+    repository.save(&replied).await.unwrap();
+    repository.save(&unreplied).await.unwrap();
+    let replied_loaded = repository.find_by_id(&replied.id).await.unwrap().unwrap();
+    let unreplied_loaded = repository.find_by_id(&unreplied.id).await.unwrap().unwrap();
+
+    // Assert
+    assert_eq!(replied_loaded.pattern, ExchangePattern::InOut);
+    assert_eq!(replied_loaded.out, replied.out);
+    assert!(unreplied_loaded.out.is_none());
+}
@@ -0,0 +1,218 @@
+use crate::domain::{
+    models::{exchange::Exchange, query::MessageQuery},
+    ports::repository::MessageRepository,
+};
+use crate::infrastructure::repositories::file_log_repository::{
+    FileLogConfig, FileLogMessageRepository, FsyncPolicy,
+};
+use crate::tests::helpers::temp_dir;
+use std::io::Write;
+
+fn config() -> FileLogConfig {
+    FileLogConfig::default().fsync(FsyncPolicy::Always)
+}
+
+#[actix_rt::test]
+async fn test_file_log_recovers_index_on_reopen() {
+    // Arrange
+    let dir = temp_dir("log");
+    let kept = Exchange::new("kept".to_string());
+    let mut updated = Exchange::new("v1".to_string());
+    let deleted = Exchange::new("deleted".to_string());
+    {
+        let repository = FileLogMessageRepository::open(&dir, config()).unwrap();
+        repository.save(&kept).await.unwrap();
+        repository.save(&updated).await.unwrap();
+        updated.body = "v2".into();
+        updated.version = 1;
+        repository.save(&updated).await.unwrap();
+        repository.save(&deleted).await.unwrap();
+        repository.delete(&deleted.id).await.unwrap();
+    }
+
+    // Act
+    let repository = FileLogMessageRepository::open(&dir, config()).unwrap();
+
+    // Assert
+    assert_eq!(repository.find_by_id(&kept.id).await.unwrap().unwrap().body, "kept");
+    let loaded = repository.find_by_id(&updated.id).await.unwrap().unwrap();
+    assert_eq!((loaded.body.text().unwrap().as_ref(), loaded.version), ("v2", 2));
+    assert!(repository.find_by_id(&deleted.id).await.unwrap().is_none());
+    assert_eq!(repository.query(&MessageQuery::new()).await.unwrap().total, 2);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[actix_rt::test]
+async fn test_file_log_truncates_torn_tail() {
+    // Arrange
+    let dir = temp_dir("log");
+    let exchange = Exchange::new("intact".to_string());
+    FileLogMessageRepository::open(&dir, config())
+        .unwrap()
+        .save(&exchange)
+        .await
+        .unwrap();
+    let segment = std::fs::read_dir(&dir).unwrap().next().unwrap().unwrap().path();
+    let intact_len = std::fs::metadata(&segment).unwrap().len();
+    let mut file = std::fs::OpenOptions::new().append(true).open(&segment).unwrap();
+    file.write_all(&[42, 0, 0, 0, 1, 2]).unwrap();
+
+    // Act
+    let repository = FileLogMessageRepository::open(&dir, config()).unwrap();
+    let later = Exchange::new("after recovery".to_string());
+    repository.save(&later).await.unwrap();
+    drop(repository);
+    let reopened = FileLogMessageRepository::open(&dir, config()).unwrap();
+
+    // Assert
+    assert!(std::fs::metadata(&segment).unwrap().len() > intact_len);
+    assert_eq!(reopened.find_by_id(&exchange.id).await.unwrap().unwrap().body, "intact");
+    assert_eq!(reopened.find_by_id(&later.id).await.unwrap().unwrap().body, "after recovery");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[actix_rt::test]
+async fn test_file_log_treats_oversized_record_length_as_torn_tail() {
+    // Arrange
+    let dir = temp_dir("log");
+    let exchange = Exchange::new("intact".to_string());
+    FileLogMessageRepository::open(&dir, config())
+        .unwrap()
+        .save(&exchange)
+        .await
+        .unwrap();
+    let segment = std::fs::read_dir(&dir).unwrap().next().unwrap().unwrap().path();
+    let intact_len = std::fs::metadata(&segment).unwrap().len();
+    let mut file = std::fs::OpenOptions::new().append(true).open(&segment).unwrap();
+    file.write_all(&u32::MAX.to_le_bytes()).unwrap();
+    file.write_all(&[1, 2, 3, 4, 5, 6]).unwrap();
+
+    // Act
+    let repository = FileLogMessageRepository::open(&dir, config()).unwrap();
+
+    // Assert
+    assert_eq!(std::fs::metadata(&segment).unwrap().len(), intact_len);
+    assert_eq!(repository.find_by_id(&exchange.id).await.unwrap().unwrap().body, "intact");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[actix_rt::test]
+async fn test_file_log_refuses_to_open_with_a_damaged_sealed_segment() {
+    // Arrange - a tiny segment size rolls after every record
+    let dir = temp_dir("log");
+    let first = Exchange::new("first".to_string());
+    let second = Exchange::new("second".to_string());
+    {
+        let repository = FileLogMessageRepository::open(&dir, config().max_segment_bytes(1)).unwrap();
+        repository.save(&first).await.unwrap();
+        repository.save(&second).await.unwrap();
+    }
+    let mut segments: Vec<_> = std::fs::read_dir(&dir).unwrap().map(|entry| entry.unwrap().path()).collect();
+    segments.sort();
+    let sealed = &segments[0];
+    let mut content = std::fs::read(sealed).unwrap();
+    let last = content.len() - 2;
+    content[last] ^= 0xFF;
+    std::fs::write(sealed, &content).unwrap();
+
+    // Act
+    let result = FileLogMessageRepository::open(&dir, config());
+
+    // Assert
+    assert!(result.is_err());
+    assert_eq!(std::fs::read(sealed).unwrap(), content, "nothing is truncated");
+    assert!(std::fs::metadata(&segments[1]).unwrap().len() > 0);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[actix_rt::test]
+async fn test_file_log_compaction_reclaims_dead_records() {
+    // Arrange
+    let dir = temp_dir("log");
+    let repository = FileLogMessageRepository::open(&dir, config().max_segment_bytes(512)).unwrap();
+    let mut exchange = Exchange::new("0".to_string());
+    for i in 0..10 {
+        exchange.body = i.to_string().into();
+        repository.save(&exchange).await.unwrap();
+        exchange.version += 1;
+    }
+    let removed = Exchange::new("removed".to_string());
+    repository.save(&removed).await.unwrap();
+    repository.delete(&removed.id).await.unwrap();
+    let before = repository.stats().await.unwrap();
+
+    // Act
+    let after = repository.compact().await.unwrap();
+    drop(repository);
+    let reopened = FileLogMessageRepository::open(&dir, config()).unwrap();
+
+    // Assert
+    assert!(before.segments > 1);
+    assert!(before.dead_bytes > 0);
+    assert_eq!(after.segments, 1);
+    assert_eq!(after.live_records, 1);
+    assert_eq!(after.dead_bytes, 0);
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+    assert_eq!(reopened.find_by_id(&exchange.id).await.unwrap().unwrap().body, "9");
+    assert!(reopened.find_by_id(&removed.id).await.unwrap().is_none());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[actix_rt::test]
+async fn test_file_log_deletes_stay_deleted_when_an_old_segment_survives_compaction() {
+    // Arrange
+    let dir = temp_dir("log");
+    let repository = FileLogMessageRepository::open(&dir, config()).unwrap();
+    let kept = Exchange::new("kept".to_string());
+    let removed = Exchange::new("removed".to_string());
+    repository.save(&kept).await.unwrap();
+    repository.save(&removed).await.unwrap();
+    repository.delete(&removed.id).await.unwrap();
+    let old_segment = std::fs::read_dir(&dir).unwrap().next().unwrap().unwrap().path();
+    let old_content = std::fs::read(&old_segment).unwrap();
+
+    // Act - put the old segment back, as if removing it had failed
+    repository.compact().await.unwrap();
+    drop(repository);
+    std::fs::write(&old_segment, &old_content).unwrap();
+    let reopened = FileLogMessageRepository::open(&dir, config()).unwrap();
+
+    // Assert
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 2);
+    assert!(reopened.find_by_id(&removed.id).await.unwrap().is_none());
+    assert_eq!(reopened.find_by_id(&kept.id).await.unwrap().unwrap().body, "kept");
+    assert_eq!(reopened.revisions(&kept.id).await.unwrap().len(), 1);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[actix_rt::test]
+async fn test_file_log_queries_run_alongside_compaction() {
+    // Arrange
+    let dir = temp_dir("log");
+    let repository = std::sync::Arc::new(FileLogMessageRepository::open(&dir, config()).unwrap());
+    for i in 0..20 {
+        let exchange = Exchange::new(i.to_string());
+        repository.save(&exchange).await.unwrap();
+        repository.delete(&exchange.id).await.unwrap();
+        repository.save(&Exchange::new(i.to_string())).await.unwrap();
+    }
+
+    // Act
+    let compactions = {
+        let repository = repository.clone();
+        tokio::spawn(async move {
+            for _ in 0..10 {
+                repository.compact().await.unwrap();
+            }
+        })
+    };
+    let mut totals = Vec::new();
+    for _ in 0..10 {
+        totals.push(repository.query(&MessageQuery::new()).await.unwrap().total);
+    }
+    compactions.await.unwrap();
+
+    // Assert
+    assert!(totals.iter().all(|total| *total == 20));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
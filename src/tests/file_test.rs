@@ -0,0 +1,310 @@
+use crate::application::{context::CamelContext, route::from};
+use crate::domain::{
+    models::{
+        endpoint::EndpointUri,
+        error::{DomainError, ErrorKind},
+        exchange::Exchange,
+        header::HeaderValue,
+    },
+    ports::{component::Component, endpoint::Producer, processor::Processor},
+};
+use crate::infrastructure::components::file::{
+    FileComponent, FILE_LENGTH, FILE_NAME, FILE_NAME_ONLY, FILE_NAME_PRODUCED,
+};
+use crate::tests::helpers::{temp_dir, wait_until};
+use async_trait::async_trait;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+fn write(path: &Path, content: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+/// Keeps every exchange it is given, failing those whose file name contains "bad".
+#[derive(Default)]
+struct Recorder {
+    exchanges: Mutex<Vec<Exchange>>,
+}
+
+impl Recorder {
+    fn count(&self) -> usize {
+        self.exchanges.lock().unwrap().len()
+    }
+
+    fn names(&self) -> Vec<String> {
+        self.exchanges
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|e| e.headers[FILE_NAME].to_string())
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Processor for Recorder {
+    async fn process(&self, exchange: Exchange) -> Result<Exchange, DomainError> {
+        self.exchanges.lock().unwrap().push(exchange.clone());
+        if exchange.headers[FILE_NAME].to_string().contains("bad") {
+            return Err(DomainError::validation("bad file"));
+        }
+        Ok(exchange)
+    }
+}
+
+fn consume(dir: &Path, options: &str, recorder: Arc<Recorder>) -> CamelContext {
+    let context = CamelContext::new("test");
+    context.add_component(Arc::new(FileComponent::new())).unwrap();
+    let uri = format!("file:{}?{}", dir.display(), options);
+    context
+        .add_route(from(&uri).route_id("file-route").process(recorder).build().unwrap())
+        .unwrap();
+    context.start().unwrap();
+    context
+}
+
+fn producer(dir: &Path, options: &str) -> Arc<dyn Producer> {
+    let uri = format!("file:{}?{}", dir.display(), options);
+    FileComponent::new()
+        .create_endpoint(&EndpointUri::parse(&uri).unwrap())
+        .unwrap()
+        .create_producer()
+        .unwrap()
+}
+
+#[actix_rt::test]
+async fn test_file_consumer_filters_and_moves_consumed_files() {
+    // Arrange
+    let dir = temp_dir("file");
+    write(&dir.join("orders.csv"), "id,qty\n1,2");
+    write(&dir.join("skip-me.csv"), "ignored");
+    write(&dir.join("notes.txt"), "ignored");
+    write(&dir.join("nested/deep.csv"), "not recursive");
+    let recorder = Arc::new(Recorder::default());
+
+    // Act
+    let _context = consume(&dir, "include=*.csv&exclude=skip*&delay=20", recorder.clone());
+    wait_until(|| recorder.count() >= 1).await;
+    wait_until(|| dir.join(".done/orders.csv").exists()).await;
+    tokio::time::sleep(Duration::from_millis(60)).await;
+
+    // Assert
+    assert_eq!(recorder.names(), ["orders.csv"]);
+    let exchange = recorder.exchanges.lock().unwrap()[0].clone();
+    assert_eq!(exchange.body, "id,qty\n1,2");
+    assert_eq!(exchange.headers[FILE_NAME_ONLY], "orders.csv");
+    assert_eq!(exchange.headers[FILE_LENGTH], HeaderValue::Integer(10));
+    assert_eq!(exchange.metadata.source_system, "file");
+    assert!(!dir.join("orders.csv").exists());
+    assert!(!dir.join(".orders.csv.lock").exists());
+    assert!(dir.join("skip-me.csv").exists());
+    assert!(dir.join("notes.txt").exists());
+    assert!(dir.join("nested/deep.csv").exists());
+}
+
+#[actix_rt::test]
+async fn test_file_consumer_deletes_done_files_and_moves_failed_ones() {
+    // Arrange
+    let dir = temp_dir("file");
+    write(&dir.join("good.txt"), "good");
+    write(&dir.join("sub/bad.txt"), "bad");
+    let recorder = Arc::new(Recorder::default());
+
+    // Act
+    let _context = consume(&dir, "recursive=true&delete=true&moveFailed=.error&delay=20", recorder.clone());
+    wait_until(|| recorder.count() >= 2).await;
+    wait_until(|| dir.join(".error/sub/bad.txt").exists() && !dir.join("good.txt").exists()).await;
+
+    // Assert
+    let mut names = recorder.names();
+    names.sort();
+    assert_eq!(names, ["good.txt", "sub/bad.txt"]);
+    assert!(!dir.join("sub/bad.txt").exists());
+    assert!(!dir.join(".done").exists());
+}
+
+#[actix_rt::test]
+async fn test_file_consumer_does_not_read_back_files_moved_inside_a_recursive_directory() {
+    // Arrange
+    let dir = temp_dir("file");
+    write(&dir.join("good.txt"), "good");
+    write(&dir.join("bad.txt"), "bad");
+    let recorder = Arc::new(Recorder::default());
+
+    // Act
+    let _context = consume(&dir, "recursive=true&move=done&moveFailed=failed&delay=20", recorder.clone());
+    wait_until(|| dir.join("done/good.txt").exists() && dir.join("failed/bad.txt").exists()).await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // Assert
+    let mut names = recorder.names();
+    names.sort();
+    assert_eq!(names, ["bad.txt", "good.txt"]);
+    assert!(!dir.join("done/done").exists());
+    assert!(!dir.join("failed/failed").exists());
+}
+
+#[actix_rt::test]
+async fn test_file_consumer_with_noop_reads_each_version_once() {
+    // Arrange
+    let dir = temp_dir("file");
+    write(&dir.join("config.json"), "{}");
+    let recorder = Arc::new(Recorder::default());
+
+    // Act
+    let _context = consume(&dir, "noop=true&delay=10", recorder.clone());
+    wait_until(|| recorder.count() >= 1).await;
+    tokio::time::sleep(Duration::from_millis(60)).await;
+    let unchanged = recorder.count();
+    write(&dir.join("config.json"), "{\"changed\":true}");
+    wait_until(|| recorder.count() >= 2).await;
+
+    // Assert
+    assert_eq!(unchanged, 1);
+    assert!(dir.join("config.json").exists());
+    assert_eq!(recorder.exchanges.lock().unwrap()[1].body, "{\"changed\":true}");
+}
+
+#[actix_rt::test]
+async fn test_file_consumer_skips_files_locked_by_another_consumer() {
+    // Arrange
+    let dir = temp_dir("file");
+    write(&dir.join("claimed.txt"), "claimed");
+    write(&dir.join(".claimed.txt.lock"), "");
+    let recorder = Arc::new(Recorder::default());
+
+    // Act
+    let _context = consume(&dir, "delay=10", recorder.clone());
+    tokio::time::sleep(Duration::from_millis(60)).await;
+    let while_locked = recorder.count();
+    fs::remove_file(dir.join(".claimed.txt.lock")).unwrap();
+    wait_until(|| recorder.count() >= 1).await;
+
+    // Assert
+    assert_eq!(while_locked, 0);
+}
+
+#[actix_rt::test]
+async fn test_file_consumer_takes_over_stale_lock_markers() {
+    // Arrange: a marker left behind by a consumer that crashed an hour ago
+    let dir = temp_dir("file");
+    write(&dir.join("abandoned.txt"), "abandoned");
+    write(&dir.join("fresh.txt"), "fresh");
+    write(&dir.join(".abandoned.txt.lock"), "");
+    write(&dir.join(".fresh.txt.lock"), "");
+    let an_hour_ago = std::time::SystemTime::now() - Duration::from_secs(3600);
+    fs::File::options()
+        .write(true)
+        .open(dir.join(".abandoned.txt.lock"))
+        .unwrap()
+        .set_modified(an_hour_ago)
+        .unwrap();
+    let recorder = Arc::new(Recorder::default());
+
+    // Act
+    let _context = consume(&dir, "delay=10", recorder.clone());
+    wait_until(|| recorder.count() >= 1).await;
+    tokio::time::sleep(Duration::from_millis(60)).await;
+
+    // Assert
+    assert_eq!(recorder.names(), ["abandoned.txt"]);
+    assert!(!dir.join(".abandoned.txt.lock").exists());
+    assert!(dir.join("fresh.txt").exists());
+}
+
+#[actix_rt::test]
+async fn test_file_consumer_with_changed_lock_waits_for_a_stable_file() {
+    // Arrange
+    let dir = temp_dir("file");
+    write(&dir.join("upload.bin"), "partial");
+    let recorder = Arc::new(Recorder::default());
+
+    // Act
+    let _context = consume(&dir, "readLock=changed&delay=100", recorder.clone());
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    let first_poll = recorder.count();
+    wait_until(|| recorder.count() >= 1).await;
+
+    // Assert
+    assert_eq!(first_poll, 0);
+}
+
+#[actix_rt::test]
+async fn test_file_producer_renders_file_name_and_leaves_no_temp_files() {
+    // Arrange
+    let dir = temp_dir("file");
+    let producer = producer(&dir, "fileName=${header.kind}/${date:%Y}-${id}.txt");
+    let mut exchange = Exchange::new("hello");
+    exchange.set_header("kind", "greetings");
+
+    // Act
+    let written = producer.send(exchange.clone()).await.unwrap();
+
+    // Assert
+    let target = dir
+        .join("greetings")
+        .join(format!("{}-{}.txt", chrono::Utc::now().format("%Y"), exchange.id));
+    assert_eq!(fs::read_to_string(&target).unwrap(), "hello");
+    assert_eq!(written.headers[FILE_NAME_PRODUCED], target.display().to_string());
+    assert_eq!(fs::read_dir(dir.join("greetings")).unwrap().count(), 1);
+}
+
+#[actix_rt::test]
+async fn test_file_producer_overwrites_appends_or_fails_on_existing_files() {
+    // Arrange
+    let dir = temp_dir("file");
+    let mut exchange = Exchange::new("line\n");
+    exchange.set_header(FILE_NAME, "out/data.txt");
+
+    // Act
+    for options in ["fileExist=overwrite", "fileExist=overwrite&tempFile=false"] {
+        producer(&dir, options).send(exchange.clone()).await.unwrap();
+    }
+    let overwritten = fs::read_to_string(dir.join("out/data.txt")).unwrap();
+    producer(&dir, "fileExist=append").send(exchange.clone()).await.unwrap();
+    let appended = fs::read_to_string(dir.join("out/data.txt")).unwrap();
+    let failed = producer(&dir, "fileExist=fail").send(exchange.clone()).await;
+    let failed_in_place = producer(&dir, "fileExist=fail&tempFile=false").send(exchange).await;
+
+    // Assert
+    assert_eq!(overwritten, "line\n");
+    assert_eq!(appended, "line\nline\n");
+    assert_eq!(failed.unwrap_err().kind(), ErrorKind::Conflict);
+    assert_eq!(failed_in_place.unwrap_err().kind(), ErrorKind::Conflict);
+    assert_eq!(fs::read_dir(dir.join("out")).unwrap().count(), 1);
+}
+
+#[actix_rt::test]
+async fn test_file_producer_rejects_names_outside_its_directory() {
+    // Arrange
+    let dir = temp_dir("file");
+    let mut exchange = Exchange::new("escape");
+    exchange.set_header(FILE_NAME, "../escaped.txt");
+
+    // Act
+    let result = producer(&dir, "").send(exchange).await;
+
+    // Assert
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::Validation);
+    assert!(!dir.with_file_name("escaped.txt").exists());
+}
+
+#[test]
+fn test_file_endpoint_rejects_invalid_options() {
+    let component = FileComponent::new();
+    let create = |options: &str| component.create_endpoint(&EndpointUri::parse(&format!("file:data?{}", options)).unwrap());
+
+    assert!(create("include=[a").is_err());
+    assert!(create("noop=true&delete=true").is_err());
+    assert!(create("delete=true&move=done").is_err());
+    assert!(create("noop=true&idempotent=false").is_err());
+    assert!(create("readLock=sometimes").is_err());
+    assert!(create("readLockTimeout=-1").is_err());
+    assert!(create("fileExist=skip").is_err());
+    assert!(create("fileName=${unknown}").is_err());
+    assert!(create("fileName=${id").is_err());
+    assert!(create("include=*.csv,*.txt&fileName=${file:name.noext}.${file:ext}").is_ok());
+}
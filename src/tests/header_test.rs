@@ -0,0 +1,112 @@
+use crate::application::processors::transform::TransformProcessor;
+use crate::domain::{
+    models::{exchange::Exchange, header::HeaderValue, query::MessageQuery},
+    ports::{processor::Processor, repository::MessageRepository},
+};
+use crate::infrastructure::repositories::sqlite_message_repository::SqliteMessageRepository;
+use bytes::Bytes;
+use chrono::{DateTime, TimeZone, Utc};
+use serde_json::json;
+use std::collections::HashMap;
+
+fn typed_headers() -> Exchange {
+    let mut exchange = Exchange::new("typed");
+    exchange.set_header("name", "order");
+    exchange.set_header("attempts", 3_i64);
+    exchange.set_header("ratio", 0.5);
+    exchange.set_header("urgent", true);
+    exchange.set_header("due", Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap());
+    exchange.set_header("signature", Bytes::from_static(&[0xca, 0xfe]));
+    exchange.set_header("tags", vec![HeaderValue::from("a"), HeaderValue::from(2_i64)]);
+    exchange
+}
+
+#[test]
+fn test_header_values_serialize_as_json_values() {
+    let exchange = typed_headers();
+
+    let json = serde_json::to_value(&exchange.headers).unwrap();
+    let restored: HashMap<String, HeaderValue> = serde_json::from_value(json.clone()).unwrap();
+
+    assert_eq!(
+        json,
+        json!({
+            "name": "order",
+            "attempts": 3,
+            "ratio": 0.5,
+            "urgent": true,
+            "due": {"timestamp": "2024-05-01T12:00:00Z"},
+            "signature": {"base64": "yv4="},
+            "tags": ["a", 2]
+        })
+    );
+    assert_eq!(restored, exchange.headers);
+}
+
+#[test]
+fn test_non_finite_floats_survive_a_round_trip() {
+    let mut exchange = Exchange::new("");
+    exchange.set_header("nan", f64::NAN);
+    exchange.set_header("up", f64::INFINITY);
+    exchange.set_header("down", f64::NEG_INFINITY);
+
+    let json = serde_json::to_string(&exchange.headers).unwrap();
+    let restored: HashMap<String, HeaderValue> = serde_json::from_str(&json).unwrap();
+
+    assert!(restored["nan"].as_f64().unwrap().is_nan());
+    assert_eq!(restored["up"], HeaderValue::Float(f64::INFINITY));
+    assert_eq!(restored["down"], HeaderValue::Float(f64::NEG_INFINITY));
+    assert_eq!(serde_json::to_value(&restored["up"]).unwrap(), json!({"float": "inf"}));
+}
+
+#[test]
+fn test_string_headers_from_before_typing_still_load() {
+    let legacy = json!({"processed_by": "enricher", "split_index": "1"});
+
+    let headers: HashMap<String, HeaderValue> = serde_json::from_value(legacy).unwrap();
+
+    assert_eq!(headers["split_index"], HeaderValue::String("1".to_string()));
+    assert_eq!(headers["split_index"], "1");
+}
+
+#[test]
+fn test_header_values_render_and_convert_like_strings() {
+    let exchange = typed_headers();
+
+    assert_eq!(exchange.headers["due"].to_string(), "2024-05-01T12:00:00+00:00");
+    assert_eq!(exchange.headers["tags"].to_string(), "a,2");
+    assert_eq!(exchange.header_as::<f64>("attempts").unwrap(), Some(3.0));
+    assert_eq!(exchange.header_as::<String>("urgent").unwrap(), Some("true".to_string()));
+    assert_eq!(
+        exchange.header_as::<DateTime<Utc>>("due").unwrap(),
+        exchange.headers["due"].as_timestamp()
+    );
+    assert!(MessageQuery::new().header("attempts", "3").matches(&exchange));
+}
+
+#[actix_rt::test]
+async fn test_transform_sets_typed_headers() {
+    let processed = TransformProcessor::new().process(Exchange::new("x")).await.unwrap();
+
+    assert_eq!(processed.headers["transformed"], HeaderValue::Bool(true));
+    assert!(processed.headers["transformed_at"].as_timestamp().is_some());
+}
+
+#[actix_rt::test]
+async fn test_sqlite_repository_keeps_typed_headers_queryable() {
+    // Arrange
+    let repository = SqliteMessageRepository::in_memory().unwrap();
+    let exchange = typed_headers();
+
+    // Act
+    repository.save(&exchange).await.unwrap();
+    let loaded = repository.find_by_id(&exchange.id).await.unwrap().unwrap();
+    let found = repository
+        .query(&MessageQuery::new().header("attempts", "3").header("urgent", "true"))
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(loaded.headers, exchange.headers);
+    assert_eq!(found.total, 1);
+}
@@ -1,14 +1,38 @@
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 use actix_web::{test, web, App};
 use crate::{
     application::{
+        context::CamelContext,
         pipeline::ProcessorPipeline,
+        route::from,
         processors::logging::LoggingProcessor,
-        services::message_service::MessageService,
+        services::{
+            dead_letter_service::DeadLetterService,
+            ingestion_service::{IngestionConfig, IngestionService},
+            message_service::MessageService, replay_service::ReplayService,
+            retention_service::RetentionService,
+        },
     },
-    infrastructure::repositories::message_repository::InMemoryMessageRepository,
-    interfaces::api::rest::{AppState, create_message, process_message},
+    domain::{models::retention::RetentionPolicy, ports::dead_letter::DeadLetterRepository},
+    infrastructure::components::{
+        direct::DirectComponent, file::FileComponent, seda::SedaComponent, timer::TimerComponent,
+    },
+    infrastructure::repositories::{
+        dead_letter_repository::InMemoryDeadLetterRepository,
+        message_repository::InMemoryMessageRepository,
+    },
+    interfaces::api::rest::{
+        AppState, create_message, delete_message, get_message, get_message_body, get_message_status,
+        list_messages, list_revisions, process_message,
+    },
+    interfaces::api::dead_letters::{delete_dead_letter, list_dead_letters, replay_dead_letter},
+    interfaces::api::error::{json_config, payload_config},
     interfaces::api::health::health_check,
+    interfaces::api::metrics::metrics,
+    interfaces::api::replays::{cancel_replay, get_replay, list_replays, start_replay},
+    interfaces::api::routes::{control_route, get_route, list_routes},
 };
 use crate::application::processors::enricher::EnricherProcessor;
 
@@ -17,6 +41,16 @@ pub async fn setup_test_app() -> impl actix_web::dev::Service<
     actix_http::Request,
     Response = actix_web::dev::ServiceResponse,
     Error = actix_web::Error,
+> {
+    setup_test_app_with_dead_letters(Arc::new(InMemoryDeadLetterRepository::new())).await
+}
+
+pub async fn setup_test_app_with_dead_letters(
+    dead_letters: Arc<dyn DeadLetterRepository>,
+) -> impl actix_web::dev::Service<
+    actix_http::Request,
+    Response = actix_web::dev::ServiceResponse,
+    Error = actix_web::Error,
 > {
     // Create test dependencies
     let repository = Arc::new(InMemoryMessageRepository::new());
@@ -30,20 +64,78 @@ pub async fn setup_test_app() -> impl actix_web::dev::Service<
     pipeline.add_processor(enricher_processor);  // Make sure enricher is in the pipeline
     let pipeline = Arc::new(pipeline);
 
-    let message_service = Arc::new(MessageService::new(repository, pipeline));
+    let context = Arc::new(CamelContext::new("test"));
+    context.add_component(Arc::new(DirectComponent::new())).unwrap();
+    context.add_component(Arc::new(SedaComponent::new())).unwrap();
+    context.add_component(Arc::new(TimerComponent::new())).unwrap();
+    context.add_component(Arc::new(FileComponent::new())).unwrap();
+    context
+        .add_route(from("direct:test").route_id("test-route").log("TEST").build().unwrap())
+        .unwrap();
+    context
+        .add_route(from("rest:/api/messages").route_id("messages").process(pipeline).build().unwrap())
+        .unwrap();
+    context.start_route("messages").unwrap();
+    let message_service = Arc::new(MessageService::new(repository.clone(), context.clone(), "messages"));
+    let ingestion_service = Arc::new(IngestionService::new(
+        message_service.clone(),
+        dead_letters.clone(),
+        IngestionConfig::default().workers(2),
+    ));
+    ingestion_service.start();
     let state = web::Data::new(AppState {
         message_service: message_service.clone(),
+        context: context.clone(),
+        dead_letter_service: Arc::new(DeadLetterService::new(dead_letters, context.clone())),
+        retention_service: Arc::new(RetentionService::new(repository.clone(), RetentionPolicy::new())),
+        replay_service: Arc::new(ReplayService::new(repository, context)),
+        ingestion_service,
     });
 
     // Create test app
     test::init_service(
         App::new()
             .app_data(state)
+            .app_data(json_config())
+            .app_data(payload_config())
             .service(
                 web::scope("/api")
                     .route("/messages", web::post().to(create_message))
+                    .route("/messages", web::get().to(list_messages))
+                    .route("/messages/{id}", web::get().to(get_message))
+                    .route("/messages/{id}", web::delete().to(delete_message))
+                    .route("/messages/{id}/body", web::get().to(get_message_body))
+                    .route("/messages/{id}/revisions", web::get().to(list_revisions))
+                    .route("/messages/{id}/status", web::get().to(get_message_status))
                     .route("/messages/process", web::post().to(process_message))
+                    .route("/routes", web::get().to(list_routes))
+                    .route("/routes/{id}", web::get().to(get_route))
+                    .route("/routes/{id}/{action}", web::post().to(control_route))
+                    .route("/dead-letters", web::get().to(list_dead_letters))
+                    .route("/dead-letters/{id}", web::delete().to(delete_dead_letter))
+                    .route("/dead-letters/{id}/replay", web::post().to(replay_dead_letter))
+                    .route("/replays", web::post().to(start_replay))
+                    .route("/replays", web::get().to(list_replays))
+                    .route("/replays/{id}", web::get().to(get_replay))
+                    .route("/replays/{id}/cancel", web::post().to(cancel_replay))
+                    .route("/metrics", web::get().to(metrics))
             )
             .route("/health", web::get().to(health_check))
     ).await
+}
+
+/// A fresh path under the system temp directory; nothing is created there yet.
+pub fn temp_dir(prefix: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("rust-camel-{}-{}", prefix, uuid::Uuid::new_v4()))
+}
+
+/// Polls `condition` every 10 ms, panicking if it still fails after two seconds.
+pub async fn wait_until(condition: impl Fn() -> bool) {
+    for _ in 0..200 {
+        if condition() {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("condition not met in time");
 }
\ No newline at end of file
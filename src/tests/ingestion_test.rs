@@ -0,0 +1,202 @@
+use crate::application::{
+    context::CamelContext,
+    error_handler::ErrorHandler,
+    pipeline::ProcessorPipeline,
+    processors::enricher::EnricherProcessor,
+    route::from,
+    services::{
+        ingestion_service::{IngestionConfig, IngestionService},
+        message_service::MessageService,
+    },
+};
+use crate::domain::{
+    models::{
+        error::{DomainError, ErrorKind},
+        exchange::Exchange,
+        ingestion::{Ingestion, IngestionStatus},
+        query::MessageQuery,
+    },
+    ports::{dead_letter::DeadLetterRepository, processor::Processor, repository::MessageRepository},
+};
+use crate::infrastructure::repositories::{
+    dead_letter_repository::InMemoryDeadLetterRepository, message_repository::InMemoryMessageRepository,
+};
+use crate::tests::helpers::wait_until;
+use async_trait::async_trait;
+use std::sync::Arc;
+use uuid::Uuid;
+
+struct FailingProcessor;
+
+#[async_trait]
+impl Processor for FailingProcessor {
+    async fn process(&self, _exchange: Exchange) -> Result<Exchange, DomainError> {
+        Err(DomainError::fatal("downstream rejected the message"))
+    }
+}
+
+struct Setup {
+    repository: Arc<InMemoryMessageRepository>,
+    dead_letters: Arc<InMemoryDeadLetterRepository>,
+    service: Arc<IngestionService>,
+}
+
+/// `pipeline` gets the dead letter repository the service checks failures against.
+fn setup(
+    pipeline: impl FnOnce(Arc<InMemoryDeadLetterRepository>) -> ProcessorPipeline,
+    config: IngestionConfig,
+) -> Setup {
+    setup_with(Arc::new(InMemoryMessageRepository::new()), pipeline, config)
+}
+
+fn setup_with(
+    repository: Arc<InMemoryMessageRepository>,
+    pipeline: impl FnOnce(Arc<InMemoryDeadLetterRepository>) -> ProcessorPipeline,
+    config: IngestionConfig,
+) -> Setup {
+    let dead_letters = Arc::new(InMemoryDeadLetterRepository::new());
+    let pipeline = Arc::new(pipeline(dead_letters.clone()));
+    let context = Arc::new(CamelContext::new("test"));
+    context
+        .add_route(from("rest:/api/messages").route_id("messages").process(pipeline).build().unwrap())
+        .unwrap();
+    context.start().unwrap();
+    let message_service = Arc::new(MessageService::new(repository.clone(), context, "messages"));
+    let service = Arc::new(IngestionService::new(message_service, dead_letters.clone(), config));
+    Setup {
+        repository,
+        dead_letters,
+        service,
+    }
+}
+
+async fn wait_until_finished(service: &IngestionService, id: &Uuid) -> Ingestion {
+    // Recovered messages only get a status once recovery reaches them
+    wait_until(|| service.status(id).is_ok_and(|ingestion| ingestion.status.is_finished())).await;
+    service.status(id).unwrap()
+}
+
+#[actix_rt::test]
+async fn test_queued_message_is_stored_then_processed_by_a_worker() {
+    // Arrange
+    let setup = setup(
+        |_| ProcessorPipeline::with_processors(vec![Arc::new(EnricherProcessor::new())]),
+        IngestionConfig::default().workers(2),
+    );
+    setup.service.start();
+
+    // Act
+    let queued = setup.service.submit(Exchange::new("later")).await.unwrap();
+    let finished = wait_until_finished(&setup.service, &queued.message_id).await;
+
+    // Assert
+    assert_eq!(queued.status, IngestionStatus::Queued);
+    assert_eq!(finished.status, IngestionStatus::Completed);
+    assert!(finished.started_at.is_some() && finished.finished_at.is_some());
+    let revisions = setup.repository.revisions(&queued.message_id).await.unwrap();
+    let triggers: Vec<&str> = revisions.iter().map(|revision| revision.cause.trigger.as_str()).collect();
+    assert_eq!(triggers, ["queued", "create"]);
+    assert!(revisions[1].exchange.headers.contains_key("processed_by"));
+}
+
+#[actix_rt::test]
+async fn test_failures_are_reported_as_failed_or_dead_lettered() {
+    // Arrange
+    let failing = setup(
+        |_| ProcessorPipeline::with_processors(vec![Arc::new(FailingProcessor)]),
+        IngestionConfig::default(),
+    );
+    let dead_lettering = setup(
+        |dead_letters| {
+            ProcessorPipeline::with_processors(vec![Arc::new(FailingProcessor)])
+                .with_error_handler(Arc::new(ErrorHandler::dead_letter_repository(dead_letters)))
+        },
+        IngestionConfig::default(),
+    );
+    failing.service.start();
+    dead_lettering.service.start();
+
+    // Act
+    let failed = failing.service.submit(Exchange::new("a")).await.unwrap();
+    let dead = dead_lettering.service.submit(Exchange::new("b")).await.unwrap();
+    let failed = wait_until_finished(&failing.service, &failed.message_id).await;
+    let dead = wait_until_finished(&dead_lettering.service, &dead.message_id).await;
+
+    // Assert
+    assert_eq!(failed.status, IngestionStatus::Failed);
+    assert!(failed.error.unwrap().contains("downstream rejected the message"));
+    assert_eq!(dead.status, IngestionStatus::DeadLettered);
+    assert!(dead_lettering.dead_letters.find_by_id(&dead.message_id).await.unwrap().is_some());
+}
+
+#[actix_rt::test]
+async fn test_full_queue_refuses_without_storing() {
+    // Arrange: no workers, so the queue only fills up
+    let setup = setup(|_| ProcessorPipeline::new(), IngestionConfig::default().queue_capacity(1));
+
+    // Act
+    let first = setup.service.submit(Exchange::new("first")).await;
+    let second = setup.service.submit(Exchange::new("second")).await;
+
+    // Assert
+    assert!(first.is_ok());
+    let error = second.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::RateLimited);
+    assert!(error.retry_after().is_some());
+    assert_eq!(setup.service.queue_depth(), 1);
+    assert_eq!(setup.repository.query(&MessageQuery::new()).await.unwrap().total, 1);
+}
+
+#[actix_rt::test]
+async fn test_messages_queued_before_a_restart_are_processed_on_start() {
+    // Arrange - a service that stored messages but stopped before processing them
+    let enricher = || ProcessorPipeline::with_processors(vec![Arc::new(EnricherProcessor::new())]);
+    let before = setup(|_| enricher(), IngestionConfig::default());
+    let first = before.service.submit(Exchange::new("first")).await.unwrap();
+    let second = before.service.submit(Exchange::new("second")).await.unwrap();
+
+    // Act
+    let after = setup_with(before.repository.clone(), |_| enricher(), IngestionConfig::default());
+    after.service.start();
+    let first = wait_until_finished(&after.service, &first.message_id).await;
+    let second = wait_until_finished(&after.service, &second.message_id).await;
+
+    // Assert
+    assert_eq!(first.status, IngestionStatus::Completed);
+    assert_eq!(second.status, IngestionStatus::Completed);
+    let revisions = after.repository.revisions(&first.message_id).await.unwrap();
+    let triggers: Vec<&str> = revisions.iter().map(|revision| revision.cause.trigger.as_str()).collect();
+    assert_eq!(triggers, ["queued", "create"]);
+}
+
+#[actix_rt::test]
+async fn test_failed_messages_are_not_requeued_and_keep_their_status_after_a_restart() {
+    // Arrange - a run whose pipeline rejected every message
+    let before = setup(
+        |_| ProcessorPipeline::with_processors(vec![Arc::new(FailingProcessor)]),
+        IngestionConfig::default(),
+    );
+    before.service.start();
+    let failed = before.service.submit(Exchange::new("poison")).await.unwrap();
+    wait_until_finished(&before.service, &failed.message_id).await;
+
+    // Act
+    let after = setup_with(
+        before.repository.clone(),
+        |_| ProcessorPipeline::with_processors(vec![Arc::new(EnricherProcessor::new())]),
+        IngestionConfig::default(),
+    );
+    after.service.start();
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    let status = after.service.stored_status(&failed.message_id).await.unwrap();
+
+    // Assert
+    assert!(after.service.status(&failed.message_id).is_err(), "nothing was requeued");
+    assert_eq!(status.status, IngestionStatus::Failed);
+    assert!(status.error.unwrap().contains("downstream rejected the message"));
+    let revisions = after.repository.revisions(&failed.message_id).await.unwrap();
+    let triggers: Vec<&str> = revisions.iter().map(|revision| revision.cause.trigger.as_str()).collect();
+    assert_eq!(triggers, ["queued", "failed"]);
+    let failures = after.repository.query(&MessageQuery::new().success(false)).await.unwrap();
+    assert_eq!(failures.total, 1);
+}
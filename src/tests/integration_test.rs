@@ -1,11 +1,22 @@
 use actix_web::{test, web};
 use crate::{
     application::{
+        context::CamelContext,
         pipeline::ProcessorPipeline,
+        route::from,
         processors::logging::LoggingProcessor,
-        services::message_service::MessageService,
+        services::{
+            dead_letter_service::DeadLetterService,
+            ingestion_service::{IngestionConfig, IngestionService},
+            message_service::MessageService, replay_service::ReplayService,
+            retention_service::RetentionService,
+        },
+    },
+    domain::models::retention::RetentionPolicy,
+    infrastructure::repositories::{
+        dead_letter_repository::InMemoryDeadLetterRepository,
+        message_repository::InMemoryMessageRepository,
     },
-    infrastructure::repositories::message_repository::InMemoryMessageRepository,
     interfaces::api::rest::{AppState, MessageRequest, ProcessMessageRequest, create_message, process_message},
     interfaces::api::health::health_check,
 };
@@ -24,9 +35,20 @@ async fn setup_integration_test_app() -> impl actix_web::dev::Service<
     pipeline.add_processor(logging_processor);
     let pipeline = Arc::new(pipeline);
 
-    let message_service = Arc::new(MessageService::new(repository, pipeline));
+    let context = Arc::new(CamelContext::new("test"));
+    context
+        .add_route(from("rest:/api/messages").route_id("messages").process(pipeline).build().unwrap())
+        .unwrap();
+    context.start().unwrap();
+    let message_service = Arc::new(MessageService::new(repository.clone(), context.clone(), "messages"));
+    let dead_letters = Arc::new(InMemoryDeadLetterRepository::new());
     let state = web::Data::new(AppState {
         message_service: message_service.clone(),
+        context: context.clone(),
+        dead_letter_service: Arc::new(DeadLetterService::new(dead_letters.clone(), context.clone())),
+        retention_service: Arc::new(RetentionService::new(repository.clone(), RetentionPolicy::new())),
+        replay_service: Arc::new(ReplayService::new(repository, context)),
+        ingestion_service: Arc::new(IngestionService::new(message_service, dead_letters, IngestionConfig::default())),
     });
 
     test::init_service(
@@ -50,9 +72,7 @@ async fn test_full_message_flow() {
     // Act - Create message
     let create_req = test::TestRequest::post()
         .uri("/api/messages")
-        .set_json(&MessageRequest {
-            body: test_message.to_string(),
-        })
+        .set_json(MessageRequest::text(test_message))
         .to_request();
 
     let create_resp: Value = test::call_and_read_body_json(&app, create_req).await;
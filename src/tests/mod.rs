@@ -1,3 +1,26 @@
+mod aggregator_test;
 mod api;
+mod body_test;
+mod choice_test;
+mod context_test;
+mod conversion_test;
+mod direct_test;
+mod endpoint_test;
+mod error_handler_test;
+mod error_test;
+mod exchange_pattern_test;
+mod file_log_repository_test;
+mod file_test;
+mod header_test;
 mod helpers;
-mod integration_test;
\ No newline at end of file
+mod ingestion_test;
+mod integration_test;
+mod replay_test;
+mod retention_test;
+mod revision_test;
+mod route_test;
+mod seda_test;
+mod splitter_test;
+mod sqlite_repository_test;
+mod timer_test;
+mod versioning_test;
\ No newline at end of file
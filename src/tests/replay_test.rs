@@ -0,0 +1,202 @@
+use crate::application::{
+    context::CamelContext,
+    route::from,
+    services::{message_service::MessageService, replay_service::ReplayService},
+};
+use crate::domain::{
+    models::{
+        body::Body,
+        error::{DomainError, ErrorKind},
+        exchange::Exchange,
+        query::MessageQuery,
+        replay::{ReplayJob, ReplayRequest, ReplaySelection, ReplayStatus},
+    },
+    ports::repository::MessageRepository,
+};
+use crate::infrastructure::repositories::message_repository::InMemoryMessageRepository;
+use crate::interfaces::api::replays::ReplayFilter;
+use crate::tests::helpers::wait_until;
+use bytes::Bytes;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+fn setup() -> (Arc<InMemoryMessageRepository>, Arc<ReplayService>) {
+    let repository = Arc::new(InMemoryMessageRepository::new());
+    let context = Arc::new(CamelContext::new("test"));
+    context
+        .add_route(from("direct:replay").route_id("replay-route").log("REPLAY").build().unwrap())
+        .unwrap();
+    context.start().unwrap();
+    let service = Arc::new(ReplayService::new(repository.clone(), context));
+    (repository, service)
+}
+
+async fn store(repository: &InMemoryMessageRepository, failed: bool) -> Exchange {
+    let mut exchange = Exchange::new("stored".to_string());
+    exchange.add_processing_step("validator", 1, !failed, None);
+    repository.save(&exchange).await.unwrap();
+    exchange
+}
+
+async fn wait_until_finished(service: &ReplayService, id: &Uuid) -> ReplayJob {
+    wait_until(|| service.job(id).unwrap().status.is_finished()).await;
+    service.job(id).unwrap()
+}
+
+#[actix_rt::test]
+async fn test_replay_by_ids_records_replay_revisions() {
+    // Arrange
+    let (repository, service) = setup();
+    let first = store(&repository, false).await;
+    let second = store(&repository, false).await;
+    let missing = Uuid::new_v4();
+    let request = ReplayRequest::new(
+        ReplaySelection::Ids(vec![first.id, second.id, first.id, missing]),
+        "replay-route",
+    )
+    .triggered_by(Some("ops".to_string()));
+
+    // Act
+    let started = service.start(request).await.unwrap();
+    let job = wait_until_finished(&service, &started.id).await;
+
+    // Assert
+    assert_eq!(started.total, 3);
+    assert_eq!(job.status, ReplayStatus::Completed);
+    assert_eq!((job.processed, job.succeeded, job.failed), (3, 2, 1));
+    assert_eq!(job.failures[0].message_id, missing);
+    let revisions = repository.revisions(&first.id).await.unwrap();
+    assert_eq!(revisions.len(), 2);
+    assert_eq!(revisions[1].cause.trigger, "replay");
+    assert_eq!(revisions[1].cause.triggered_by.as_deref(), Some("ops"));
+}
+
+#[actix_rt::test]
+async fn test_replay_by_filter_selects_failed_messages() {
+    // Arrange
+    let (repository, service) = setup();
+    let failed = store(&repository, true).await;
+    let succeeded = store(&repository, false).await;
+    let request = ReplayRequest::new(
+        ReplaySelection::Query(MessageQuery::new().success(false)),
+        "replay-route",
+    );
+
+    // Act
+    let started = service.start(request).await.unwrap();
+    let job = wait_until_finished(&service, &started.id).await;
+
+    // Assert
+    assert_eq!((job.total, job.succeeded), (1, 1));
+    assert_eq!(repository.revisions(&failed.id).await.unwrap().len(), 2);
+    assert_eq!(repository.revisions(&succeeded.id).await.unwrap().len(), 1);
+}
+
+#[actix_rt::test]
+async fn test_rate_limited_replay_can_be_cancelled() {
+    // Arrange
+    let (repository, service) = setup();
+    let mut ids = Vec::new();
+    for _ in 0..5 {
+        ids.push(store(&repository, false).await.id);
+    }
+    let request = ReplayRequest::new(ReplaySelection::Ids(ids), "replay-route").rate_limit(10.0);
+
+    // Act
+    let started = service.start(request).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    let cancelled = service.cancel(&started.id).unwrap();
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let job = service.job(&started.id).unwrap();
+
+    // Assert
+    assert_eq!(cancelled.status, ReplayStatus::Cancelled);
+    assert!(job.processed >= 1 && job.processed < 5);
+    assert_eq!(job.processed, cancelled.processed);
+    assert_eq!(service.cancel(&started.id).unwrap_err().kind(), ErrorKind::Conflict);
+}
+
+#[actix_rt::test]
+async fn test_replay_rejects_unknown_route_and_invalid_rate() {
+    // Arrange
+    let (_, service) = setup();
+
+    // Act
+    let unknown_route = service
+        .start(ReplayRequest::new(ReplaySelection::Ids(vec![]), "missing"))
+        .await
+        .unwrap_err();
+    let invalid_rate = service
+        .start(ReplayRequest::new(ReplaySelection::Ids(vec![]), "replay-route").rate_limit(0.0))
+        .await
+        .unwrap_err();
+
+    // Assert
+    assert_eq!(unknown_route.kind(), ErrorKind::NotFound);
+    assert_eq!(invalid_rate.kind(), ErrorKind::Validation);
+}
+
+
+#[actix_rt::test]
+async fn test_failed_only_replays_messages_whose_latest_run_failed() {
+    // Arrange
+    let repository = Arc::new(InMemoryMessageRepository::new());
+    let context = Arc::new(CamelContext::new("test"));
+    context
+        .add_route(
+            from("direct:checked")
+                .route_id("checked")
+                .transform(|body| match body.as_str() {
+                    "bad" => Err(DomainError::validation("rejected")),
+                    _ => Ok(body),
+                })
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+    context
+        .add_route(
+            from("direct:streaming")
+                .route_id("streaming")
+                .transform_body(|_| Ok(Body::from_stream(futures::stream::iter(vec![Ok(Bytes::from_static(b"fixed"))]))))
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+    context.start().unwrap();
+    let messages = MessageService::new(repository.clone(), context.clone(), "checked");
+    let service = Arc::new(ReplayService::new(repository.clone(), context));
+    messages.process_message(Exchange::new("good".to_string())).await.unwrap();
+    let error = messages.process_message(Exchange::new("bad".to_string())).await.unwrap_err();
+    let failed_only = || {
+        ReplaySelection::Query(MessageQuery::from(ReplayFilter {
+            failed_only: true,
+            ..Default::default()
+        }))
+    };
+
+    // Act
+    let retried = service.start(ReplayRequest::new(failed_only(), "checked")).await.unwrap();
+    let retried = wait_until_finished(&service, &retried.id).await;
+    let fixed = service.start(ReplayRequest::new(failed_only(), "streaming")).await.unwrap();
+    let fixed = wait_until_finished(&service, &fixed.id).await;
+    let remaining = service.start(ReplayRequest::new(failed_only(), "streaming")).await.unwrap();
+
+    // Assert
+    assert_eq!(error.kind(), ErrorKind::Validation);
+    assert_eq!((retried.total, retried.succeeded, retried.failed), (1, 0, 1));
+    assert_eq!((fixed.total, fixed.succeeded, fixed.failed), (1, 1, 0));
+    assert_eq!(remaining.total, 0);
+    let id = retried.failures[0].message_id;
+    let triggers: Vec<String> = repository
+        .revisions(&id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|revision| revision.cause.trigger)
+        .collect();
+    assert_eq!(triggers, vec!["received", "failed", "failed", "replay"]);
+    let stored = repository.find_by_id(&id).await.unwrap().unwrap();
+    assert_eq!(stored.body.text().unwrap(), "fixed");
+}
@@ -0,0 +1,106 @@
+use crate::application::services::retention_service::RetentionService;
+use crate::domain::{
+    models::{
+        exchange::Exchange,
+        query::MessageQuery,
+        retention::{RetentionPolicy, RetentionRule},
+    },
+    ports::repository::MessageRepository,
+};
+use crate::infrastructure::repositories::message_repository::InMemoryMessageRepository;
+use chrono::Utc;
+use std::sync::Arc;
+use std::time::Duration;
+
+async fn store(repository: &InMemoryMessageRepository, source: &str, age_secs: i64) -> Exchange {
+    let mut exchange = Exchange::new(format!("{} {}", source, age_secs));
+    exchange.metadata.source_system = source.to_string();
+    exchange.created_at = Utc::now() - chrono::Duration::seconds(age_secs);
+    repository.save(&exchange).await.unwrap();
+    exchange
+}
+
+#[actix_rt::test]
+async fn test_sweep_expires_by_age_and_count() {
+    // Arrange
+    let repository = Arc::new(InMemoryMessageRepository::new());
+    let expired = store(&repository, "orders", 7200).await;
+    let oldest_kept = store(&repository, "orders", 300).await;
+    store(&repository, "orders", 200).await;
+    store(&repository, "orders", 100).await;
+    let policy = RetentionPolicy::new().rule(
+        RetentionRule::all()
+            .max_age(Duration::from_secs(3600))
+            .max_count(2),
+    );
+    let service = RetentionService::new(repository.clone(), policy);
+
+    // Act
+    let report = service.sweep().await.unwrap();
+
+    // Assert
+    assert_eq!(report.expired_by_age, 1);
+    assert_eq!(report.expired_by_count, 1);
+    assert!(repository.find_by_id(&expired.id).await.unwrap().is_none());
+    assert!(repository.find_by_id(&oldest_kept.id).await.unwrap().is_none());
+    assert_eq!(repository.query(&MessageQuery::new()).await.unwrap().total, 2);
+}
+
+#[actix_rt::test]
+async fn test_rules_only_touch_selected_exchanges() {
+    // Arrange
+    let repository = Arc::new(InMemoryMessageRepository::new());
+    let audit = store(&repository, "audit", 7200).await;
+    let mut urgent = Exchange::new("urgent".to_string());
+    urgent.metadata.priority = "high".to_string();
+    urgent.created_at = Utc::now() - chrono::Duration::seconds(7200);
+    repository.save(&urgent).await.unwrap();
+    store(&repository, "clicks", 7200).await;
+    let policy = RetentionPolicy::new()
+        .rule(RetentionRule::for_source_system("clicks").max_age(Duration::from_secs(60)))
+        .rule(RetentionRule::for_priority("low").max_count(0));
+    let service = RetentionService::new(repository.clone(), policy);
+
+    // Act
+    let report = service.sweep().await.unwrap();
+
+    // Assert
+    assert_eq!(report.total(), 1);
+    assert!(repository.find_by_id(&audit.id).await.unwrap().is_some());
+    assert!(repository.find_by_id(&urgent.id).await.unwrap().is_some());
+}
+
+#[actix_rt::test]
+async fn test_sweeps_are_counted_in_metrics() {
+    // Arrange
+    let repository = Arc::new(InMemoryMessageRepository::new());
+    for age in [10, 20, 30] {
+        store(&repository, "orders", age).await;
+    }
+    let service = RetentionService::new(
+        repository,
+        RetentionPolicy::new().rule(RetentionRule::all().max_count(1)),
+    );
+
+    // Act
+    service.sweep().await.unwrap();
+    service.sweep().await.unwrap();
+    let metrics = service.metrics().unwrap();
+
+    // Assert
+    assert_eq!(metrics.sweeps, 2);
+    assert_eq!(metrics.expired_total, 2);
+    assert_eq!(metrics.expired_by_count, 2);
+    assert_eq!(metrics.last_sweep_expired, 0);
+    assert!(metrics.last_sweep_at.is_some());
+}
+
+#[actix_rt::test]
+async fn test_empty_policy_does_not_start_sweeper() {
+    let service = Arc::new(RetentionService::new(
+        Arc::new(InMemoryMessageRepository::new()),
+        RetentionPolicy::new().rule(RetentionRule::for_source_system("orders")),
+    ));
+
+    assert!(service.start(Duration::from_millis(10)).is_none());
+}
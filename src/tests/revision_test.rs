@@ -0,0 +1,150 @@
+use crate::domain::{
+    models::{exchange::Exchange, query::MessageQuery, revision::RevisionCause},
+    ports::repository::MessageRepository,
+};
+use crate::infrastructure::repositories::{
+    file_log_repository::{FileLogConfig, FileLogMessageRepository},
+    message_repository::InMemoryMessageRepository,
+    sqlite_message_repository::SqliteMessageRepository,
+};
+use crate::tests::helpers::temp_dir;
+
+async fn assert_keeps_revision_chain(repository: &dyn MessageRepository) {
+    let mut exchange = Exchange::new("original".to_string());
+    repository
+        .save_revision(&exchange, &RevisionCause::new("create"))
+        .await
+        .unwrap();
+    exchange.version = 1;
+    exchange.body = "reprocessed".into();
+    exchange.set_header("processed_by", "enricher");
+    repository
+        .save_revision(
+            &exchange,
+            &RevisionCause::new("reprocess").triggered_by(Some("ops".to_string())),
+        )
+        .await
+        .unwrap();
+
+    let revisions = repository.revisions(&exchange.id).await.unwrap();
+
+    assert_eq!(revisions.len(), 2);
+    assert_eq!(revisions[0].version(), 1);
+    assert_eq!(revisions[0].exchange.body, "original");
+    assert_eq!(revisions[0].cause.trigger, "create");
+    assert_eq!(revisions[1].version(), 2);
+    assert_eq!(revisions[1].cause.triggered_by.as_deref(), Some("ops"));
+
+    let diff = revisions[1].diff(&revisions[0]);
+    assert_eq!(diff.body.unwrap().to.as_deref(), Some("reprocessed"));
+    assert_eq!(diff.headers["processed_by"].from, None);
+    assert!(diff.properties.is_empty());
+
+    // Only the latest revision's trigger counts
+    let reprocessed = repository.query(&MessageQuery::new().trigger("reprocess")).await.unwrap();
+    let created = repository.query(&MessageQuery::new().trigger("create")).await.unwrap();
+    assert_eq!(reprocessed.total, 1);
+    assert_eq!(created.total, 0);
+
+    repository.delete(&exchange.id).await.unwrap();
+    assert!(repository.revisions(&exchange.id).await.unwrap().is_empty());
+}
+
+#[actix_rt::test]
+async fn test_in_memory_repository_keeps_revisions() {
+    assert_keeps_revision_chain(&InMemoryMessageRepository::new()).await;
+}
+
+#[actix_rt::test]
+async fn test_sqlite_repository_keeps_revisions() {
+    assert_keeps_revision_chain(&SqliteMessageRepository::in_memory().unwrap()).await;
+}
+
+#[actix_rt::test]
+async fn test_file_log_repository_keeps_revisions() {
+    let dir = temp_dir("log");
+    assert_keeps_revision_chain(&FileLogMessageRepository::open(&dir, FileLogConfig::default()).unwrap()).await;
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[actix_rt::test]
+async fn test_file_log_revisions_survive_compaction_and_reopen() {
+    // Arrange
+    let dir = temp_dir("log");
+    let mut exchange = Exchange::new("v1".to_string());
+    {
+        let repository = FileLogMessageRepository::open(&dir, FileLogConfig::default()).unwrap();
+        repository.save(&exchange).await.unwrap();
+        exchange.version = 1;
+        exchange.body = "v2".into();
+        repository.save(&exchange).await.unwrap();
+        repository.compact().await.unwrap();
+    }
+
+    // Act - a stale pre-compaction segment next to the compacted one must not duplicate revisions
+    let compacted = std::fs::read_dir(&dir).unwrap().next().unwrap().unwrap().path();
+    std::fs::copy(&compacted, dir.join("segment-0000000000.log")).unwrap();
+    let repository = FileLogMessageRepository::open(&dir, FileLogConfig::default()).unwrap();
+    let revisions = repository.revisions(&exchange.id).await.unwrap();
+
+    // Assert
+    let bodies: Vec<String> = revisions.iter().map(|r| r.exchange.body.to_string()).collect();
+    assert_eq!(bodies, ["v1", "v2"]);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Saves v1 to v5 into a repository capped at three revisions.
+async fn assert_caps_revision_chain(repository: &dyn MessageRepository) {
+    let mut exchange = Exchange::new("v1".to_string());
+    for version in 1..=5u64 {
+        exchange.body = format!("v{}", version).into();
+        repository.save(&exchange).await.unwrap();
+        exchange.version = version;
+    }
+
+    let revisions = repository.revisions(&exchange.id).await.unwrap();
+    let versions: Vec<u64> = revisions.iter().map(|revision| revision.version()).collect();
+    assert_eq!(versions, [1, 4, 5]);
+    assert_eq!(revisions[1].diff(&revisions[0]).from_version, 1);
+    let current = repository.find_by_id(&exchange.id).await.unwrap().unwrap();
+    assert_eq!(current.body, "v5");
+}
+
+#[actix_rt::test]
+async fn test_in_memory_repository_caps_revisions() {
+    assert_caps_revision_chain(&InMemoryMessageRepository::new().max_revisions(3)).await;
+}
+
+#[actix_rt::test]
+async fn test_sqlite_repository_caps_revisions() {
+    assert_caps_revision_chain(&SqliteMessageRepository::in_memory().unwrap().max_revisions(3)).await;
+}
+
+#[actix_rt::test]
+async fn test_file_log_repository_caps_revisions_across_reopen_and_compaction() {
+    // Arrange
+    let dir = temp_dir("log");
+    let capped = FileLogConfig::default().max_revisions(3);
+    let id = {
+        let repository = FileLogMessageRepository::open(&dir, capped.clone()).unwrap();
+        assert_caps_revision_chain(&repository).await;
+        let stats = repository.stats().await.unwrap();
+        assert!(stats.dead_bytes > 0);
+        repository.query(&MessageQuery::new()).await.unwrap().items[0].id
+    };
+
+    // Act - the dropped revisions are still on disk until compaction
+    let reopened = FileLogMessageRepository::open(&dir, capped.clone()).unwrap();
+    let before_compaction = reopened.revisions(&id).await.unwrap();
+    let stats = reopened.compact().await.unwrap();
+    drop(reopened);
+    let compacted = FileLogMessageRepository::open(&dir, FileLogConfig::default()).unwrap();
+    let after_compaction = compacted.revisions(&id).await.unwrap();
+
+    // Assert
+    assert_eq!(before_compaction.len(), 3);
+    assert_eq!(stats.dead_bytes, 0);
+    let versions: Vec<u64> = after_compaction.iter().map(|revision| revision.version()).collect();
+    assert_eq!(versions, [1, 4, 5]);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
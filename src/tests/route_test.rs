@@ -0,0 +1,55 @@
+use crate::application::route::from;
+use crate::domain::models::{error::DomainError, exchange::Exchange};
+use std::collections::HashMap;
+
+#[actix_rt::test]
+async fn test_route_builder_runs_steps_in_order() {
+    // Arrange
+    let mut metadata = HashMap::new();
+    metadata.insert("team".to_string(), "integration".to_string());
+    let route = from("direct:orders")
+        .route_id("orders")
+        .log("TEST")
+        .enrich(metadata)
+        .transform(|body| Ok(body.to_uppercase()))
+        .to("log:out")
+        .build()
+        .unwrap();
+
+    // Act
+    let processed = route.process(Exchange::new("order".to_string())).await.unwrap();
+
+    // Assert
+    assert_eq!(route.id(), "orders");
+    assert_eq!(route.from_uri(), "direct:orders");
+    assert_eq!(route.to_uris(), ["log:out".to_string()]);
+    assert_eq!(processed.body, "ORDER");
+    assert_eq!(processed.headers.get("team").unwrap(), "integration");
+    assert_eq!(processed.properties.get("to_endpoint").unwrap(), "log:out");
+    assert_eq!(processed.processing_history.last().unwrap().processor_name, "to:log:out");
+}
+
+#[actix_rt::test]
+async fn test_route_builder_filter_rejects_exchange() {
+    let route = from("direct:filtered")
+        .filter(|exchange| exchange.body.text().is_ok_and(|body| body.starts_with("keep")))
+        .build()
+        .unwrap();
+
+    assert!(route.process(Exchange::new("keep me".to_string())).await.is_ok());
+    assert!(route.process(Exchange::new("drop me".to_string())).await.is_err());
+}
+
+#[test]
+fn test_route_builder_requires_from_uri() {
+    let result = from("  ").log("TEST").build();
+
+    assert!(matches!(result, Err(DomainError::Validation { .. })));
+}
+
+#[test]
+fn test_route_builder_generates_route_id() {
+    let route = from("direct:anonymous").build().unwrap();
+
+    assert!(route.id().starts_with("route-"));
+}
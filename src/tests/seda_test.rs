@@ -0,0 +1,169 @@
+use crate::application::{context::CamelContext, route::from};
+use crate::domain::{
+    models::{
+        endpoint::EndpointUri,
+        error::{DomainError, ErrorKind},
+        exchange::{Exchange, ExchangePattern},
+    },
+    ports::{component::Component, endpoint::Producer, processor::Processor},
+};
+use crate::infrastructure::components::seda::SedaComponent;
+use crate::tests::helpers::wait_until;
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Counts exchanges and the most it ever saw in flight at once.
+#[derive(Default)]
+struct SlowProcessor {
+    in_flight: AtomicUsize,
+    max_in_flight: AtomicUsize,
+    processed: AtomicUsize,
+}
+
+#[async_trait]
+impl Processor for SlowProcessor {
+    async fn process(&self, exchange: Exchange) -> Result<Exchange, DomainError> {
+        let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+        self.processed.fetch_add(1, Ordering::SeqCst);
+        Ok(exchange)
+    }
+}
+
+fn producer(component: &SedaComponent, uri: &str) -> Arc<dyn Producer> {
+    component
+        .create_endpoint(&EndpointUri::parse(uri).unwrap())
+        .unwrap()
+        .create_producer()
+        .unwrap()
+}
+
+#[actix_rt::test]
+async fn test_seda_decouples_routes_with_concurrent_consumers() {
+    // Arrange
+    let context = CamelContext::new("test");
+    context.add_component(Arc::new(SedaComponent::new())).unwrap();
+    let consumer = Arc::new(SlowProcessor::default());
+    context
+        .add_route(
+            from("seda:orders?concurrentConsumers=3")
+                .route_id("consume")
+                .process(consumer.clone())
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+    context
+        .add_route(from("direct:in").route_id("produce").to("seda:orders").build().unwrap())
+        .unwrap();
+    context.start().unwrap();
+
+    // Act
+    for i in 0..6 {
+        let sent = context.process("produce", Exchange::new(i.to_string())).await.unwrap();
+        assert!(sent.out.is_none());
+    }
+    let queued = context.queue_stats().unwrap();
+    wait_until(|| consumer.processed.load(Ordering::SeqCst) >= 6).await;
+
+    // Assert
+    assert_eq!(queued.len(), 1);
+    assert_eq!(queued[0].endpoint, "seda:orders");
+    assert_eq!(queued[0].consumers, 3);
+    assert_eq!(consumer.max_in_flight.load(Ordering::SeqCst), 3);
+    assert_eq!(context.queue_stats().unwrap()[0].depth, 0);
+
+    context.stop().unwrap();
+    assert_eq!(context.queue_stats().unwrap()[0].consumers, 0);
+}
+
+#[actix_rt::test]
+async fn test_in_out_producer_waits_for_the_consuming_route() {
+    // Arrange
+    let context = CamelContext::new("test");
+    context.add_component(Arc::new(SedaComponent::new())).unwrap();
+    context
+        .add_route(
+            from("seda:upper")
+                .route_id("upper")
+                .transform(|body| Ok(body.to_uppercase()))
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+    context
+        .add_route(from("direct:in").route_id("ask").to("seda:upper").build().unwrap())
+        .unwrap();
+    context.start().unwrap();
+    let mut exchange = Exchange::new("hello");
+    exchange.pattern = ExchangePattern::InOut;
+
+    // Act
+    let replied = context.process("ask", exchange).await.unwrap();
+
+    // Assert
+    assert_eq!(replied.body, "hello");
+    assert_eq!(replied.into_reply().body, "HELLO");
+}
+
+#[actix_rt::test]
+async fn test_full_queue_blocks_drops_or_fails_as_configured() {
+    // Arrange: no consumers, so each queue of one fills after the first exchange
+    let component = SedaComponent::new();
+    let block = producer(&component, "seda:block?size=1");
+    let drop = producer(&component, "seda:drop?size=1&whenFull=drop");
+    let fail = producer(&component, "seda:fail?size=1&whenFull=fail");
+    for producer in [&block, &drop, &fail] {
+        producer.send(Exchange::new("first")).await.unwrap();
+    }
+
+    // Act
+    let blocked = tokio::time::timeout(Duration::from_millis(50), block.send(Exchange::new("second"))).await;
+    let dropped = drop.send(Exchange::new("second")).await;
+    let failed = fail.send(Exchange::new("second")).await;
+
+    // Assert
+    assert!(blocked.is_err(), "a blocking producer waits for room");
+    assert!(dropped.is_ok());
+    assert_eq!(failed.unwrap_err().kind(), ErrorKind::Transient);
+    let stats = component.queue_stats();
+    let dropped_queue = stats.iter().find(|queue| queue.endpoint == "seda:drop").unwrap();
+    assert_eq!(dropped_queue.dropped, 1);
+    assert!(stats.iter().all(|queue| queue.depth == 1 && queue.capacity == 1));
+}
+
+#[actix_rt::test]
+async fn test_blocked_sends_and_unanswered_replies_time_out() {
+    // Arrange: no consumers, so the queue of one fills and nothing ever replies
+    let component = SedaComponent::new();
+    let block = producer(&component, "seda:slow?size=1&offerTimeout=50&timeout=50");
+    let mut exchange = Exchange::new("first");
+    exchange.pattern = ExchangePattern::InOut;
+
+    // Act
+    let unanswered = block.send(exchange).await;
+    let blocked = block.send(Exchange::new("second")).await;
+
+    // Assert
+    assert_eq!(unanswered.unwrap_err().kind(), ErrorKind::Timeout);
+    assert_eq!(blocked.unwrap_err().kind(), ErrorKind::Timeout);
+}
+
+#[test]
+fn test_seda_rejects_invalid_options() {
+    let component = SedaComponent::new();
+    let create = |uri: &str| component.create_endpoint(&EndpointUri::parse(uri).unwrap());
+
+    assert!(create("seda:a?size=0").is_err());
+    assert!(create("seda:a?concurrentConsumers=0").is_err());
+    assert!(create("seda:a?whenFull=sometimes").is_err());
+    assert!(create("seda:b?timeout=soon").is_err());
+    assert!(create("seda:b?offerTimeout=0&timeout=0").is_ok());
+    assert!(create("seda:a?size=5").is_ok());
+    assert!(create("seda:a").is_ok());
+    assert!(create("seda:a?size=6").is_err(), "a queue keeps the size it was created with");
+}
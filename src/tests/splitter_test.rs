@@ -0,0 +1,143 @@
+use crate::application::{
+    aggregation::{JoinBodiesStrategy, JsonArrayStrategy},
+    processors::{
+        splitter::{SplitExpression, SplitterProcessor, SPLIT_COMPLETE, SPLIT_INDEX, SPLIT_SIZE},
+        transform::TransformProcessor,
+    },
+    route::from,
+};
+use crate::domain::{
+    models::{error::DomainError, exchange::Exchange},
+    ports::processor::Processor,
+};
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+
+/// Records every part it sees so tests can inspect split headers.
+struct RecordingProcessor {
+    seen: Mutex<Vec<Exchange>>,
+}
+
+#[async_trait]
+impl Processor for RecordingProcessor {
+    async fn process(&self, exchange: Exchange) -> Result<Exchange, DomainError> {
+        self.seen.lock().unwrap().push(exchange.clone());
+        Ok(exchange)
+    }
+}
+
+fn upper() -> Arc<dyn Processor> {
+    Arc::new(TransformProcessor::with_transformer(|body| Ok(body.to_uppercase())))
+}
+
+#[actix_rt::test]
+async fn test_splitter_sets_split_headers() {
+    let recorder = Arc::new(RecordingProcessor { seen: Mutex::new(Vec::new()) });
+    let splitter = SplitterProcessor::new(SplitExpression::Lines, recorder.clone());
+
+    splitter.process(Exchange::new("a\nb\n\nc".to_string())).await.unwrap();
+
+    let seen = recorder.seen.lock().unwrap();
+    assert_eq!(seen.len(), 3);
+    assert_eq!(seen[1].body, "b");
+    assert_eq!(seen[1].headers.get(SPLIT_INDEX).unwrap(), "1");
+    assert_eq!(seen[1].headers.get(SPLIT_SIZE).unwrap(), "3");
+    assert_eq!(seen[1].headers.get(SPLIT_COMPLETE).unwrap(), "false");
+    assert_eq!(seen[2].headers.get(SPLIT_COMPLETE).unwrap(), "true");
+}
+
+#[actix_rt::test]
+async fn test_streaming_splitter_only_sizes_last_part() {
+    let recorder = Arc::new(RecordingProcessor { seen: Mutex::new(Vec::new()) });
+    let splitter = SplitterProcessor::new(SplitExpression::Delimiter(",".to_string()), recorder.clone())
+        .streaming(true);
+
+    splitter.process(Exchange::new("x,y,z".to_string())).await.unwrap();
+
+    let seen = recorder.seen.lock().unwrap();
+    assert!(!seen[0].headers.contains_key(SPLIT_SIZE));
+    assert_eq!(seen[2].headers.get(SPLIT_SIZE).unwrap(), "3");
+    assert_eq!(seen[2].headers.get(SPLIT_COMPLETE).unwrap(), "true");
+}
+
+#[actix_rt::test]
+async fn test_splitter_aggregates_json_array_in_order_when_parallel() {
+    let route = from("direct:split")
+        .split(
+            SplitterProcessor::new(SplitExpression::JsonArray, upper())
+                .parallel(4)
+                .aggregate(Arc::new(JsonArrayStrategy)),
+        )
+        .build()
+        .unwrap();
+
+    let exchange = Exchange::new(r#"["a","b","c","d"]"#.to_string());
+    let id = exchange.id;
+    let processed = route.process(exchange).await.unwrap();
+
+    assert_eq!(processed.id, id);
+    assert_eq!(processed.body, r#"["A","B","C","D"]"#);
+    assert_eq!(processed.processing_history.last().unwrap().processor_name, "splitter");
+}
+
+#[actix_rt::test]
+async fn test_splitter_csv_skips_header_and_rejoins() {
+    let splitter = SplitterProcessor::new(SplitExpression::Csv { skip_header: true }, upper())
+        .aggregate(Arc::new(JoinBodiesStrategy::new("\n")));
+
+    let processed = splitter
+        .process(Exchange::new("id,name\n1,ann\n2,bob".to_string()))
+        .await
+        .unwrap();
+
+    assert_eq!(processed.body, "1,ANN\n2,BOB");
+}
+
+#[actix_rt::test]
+async fn test_splitter_csv_keeps_quoted_line_breaks_in_their_record() {
+    let splitter = SplitterProcessor::new(SplitExpression::Csv { skip_header: true }, upper())
+        .aggregate(Arc::new(JoinBodiesStrategy::new("|")));
+
+    let processed = splitter
+        .process(Exchange::new("id,note\r\n1,\"two\nlines, \"\"quoted\"\"\"\r\n2,plain\r\n".to_string()))
+        .await
+        .unwrap();
+
+    assert_eq!(processed.body, "1,\"TWO\nLINES, \"\"QUOTED\"\"\"|2,PLAIN");
+}
+
+#[actix_rt::test]
+async fn test_splitter_error_handling() {
+    let failing: Arc<dyn Processor> = Arc::new(TransformProcessor::with_transformer(|body| {
+        if body == "bad" {
+            Err(DomainError::fatal("bad part"))
+        } else {
+            Ok(body)
+        }
+    }));
+
+    let strict = SplitterProcessor::new(SplitExpression::Lines, failing.clone());
+    assert!(strict.process(Exchange::new("ok\nbad".to_string())).await.is_err());
+
+    let lenient = SplitterProcessor::new(SplitExpression::Lines, failing)
+        .stop_on_error(false)
+        .aggregate(Arc::new(JoinBodiesStrategy::new("|")));
+    let processed = lenient
+        .process(Exchange::new("ok\nbad\nfine".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(processed.body, "ok|fine");
+    assert_eq!(
+        processed.processing_history.last().unwrap().notes.as_deref(),
+        Some("processed=2 failed=1")
+    );
+}
+
+#[actix_rt::test]
+async fn test_splitter_rejects_non_array_json() {
+    let splitter = SplitterProcessor::new(SplitExpression::JsonArray, upper());
+
+    let result = splitter.process(Exchange::new(r#"{"a":1}"#.to_string())).await;
+
+    assert!(matches!(result, Err(DomainError::Validation { .. })));
+}
@@ -0,0 +1,83 @@
+use crate::domain::{
+    models::{exchange::Exchange, query::MessageQuery},
+    ports::repository::MessageRepository,
+};
+use crate::infrastructure::repositories::sqlite_message_repository::SqliteMessageRepository;
+use crate::tests::helpers::temp_dir;
+
+fn exchange(body: &str) -> Exchange {
+    let mut exchange = Exchange::new(body.to_string());
+    exchange.set_header("tenant", "acme");
+    exchange.set_property("route", "orders");
+    exchange.add_processing_step("log", 3, true, Some("ok".to_string()));
+    exchange
+}
+
+#[actix_rt::test]
+async fn test_sqlite_round_trips_full_exchange() {
+    // Arrange
+    let repository = SqliteMessageRepository::in_memory().unwrap();
+    let original = exchange("payload");
+
+    // Act
+    repository.save(&original).await.unwrap();
+    let loaded = repository.find_by_id(&original.id).await.unwrap().unwrap();
+
+    // Assert
+    assert_eq!(loaded.body, "payload");
+    assert_eq!(loaded.headers, original.headers);
+    assert_eq!(loaded.properties, original.properties);
+    assert_eq!(loaded.created_at, original.created_at);
+    assert_eq!(loaded.processing_history.len(), 1);
+    assert_eq!(loaded.processing_history[0].notes.as_deref(), Some("ok"));
+    assert_eq!(loaded.metadata.correlation_id, original.metadata.correlation_id);
+}
+
+#[actix_rt::test]
+async fn test_sqlite_query_filters_and_delete() {
+    // Arrange
+    let repository = SqliteMessageRepository::in_memory().unwrap();
+    let first = exchange("first");
+    let mut second = exchange("second");
+    second.set_header("tenant", "globex");
+    second.metadata.priority = "high".to_string();
+    second.add_processing_step("filter", 0, false, None);
+    repository.save(&first).await.unwrap();
+    repository.save(&second).await.unwrap();
+
+    // Act
+    let acme = repository.query(&MessageQuery::new().header("tenant", "acme")).await.unwrap();
+    let high = repository.query(&MessageQuery::new().priority("high")).await.unwrap();
+    let failed = repository.query(&MessageQuery::new().success(false)).await.unwrap();
+    let paged = repository.query(&MessageQuery::new().page(1, 1)).await.unwrap();
+    repository.delete(&second.id).await.unwrap();
+    let remaining = repository.query(&MessageQuery::new()).await.unwrap();
+
+    // Assert
+    assert_eq!(acme.total, 1);
+    assert_eq!(acme.items[0].id, first.id);
+    assert_eq!(high.items[0].id, second.id);
+    assert_eq!(failed.items[0].id, second.id);
+    assert_eq!(paged.total, 2);
+    assert_eq!(paged.items[0].id, first.id);
+    assert_eq!(remaining.total, 1);
+}
+
+#[actix_rt::test]
+async fn test_sqlite_store_survives_reopen() {
+    // Arrange
+    let path = temp_dir("sqlite");
+    let original = exchange("durable");
+    SqliteMessageRepository::open(&path).unwrap().save(&original).await.unwrap();
+
+    // Act - reopening must not re-run migrations or lose data
+    let reopened = SqliteMessageRepository::open(&path).unwrap();
+    let loaded = reopened.find_by_id(&original.id).await.unwrap();
+
+    // Assert
+    assert_eq!(loaded.unwrap().body, "durable");
+    drop(reopened);
+    for suffix in ["", "-wal", "-shm"] {
+        let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+    }
+}
@@ -0,0 +1,165 @@
+use crate::application::{context::CamelContext, route::from};
+use crate::domain::{
+    models::{endpoint::EndpointUri, error::DomainError, exchange::Exchange, header::HeaderValue},
+    ports::{component::Component, processor::Processor},
+};
+use crate::infrastructure::components::timer::{
+    TimerComponent, TimerSchedule, TIMER_COUNTER, TIMER_FIRED_AT, TIMER_NAME, TIMER_SCHEDULED_AT,
+};
+use crate::tests::helpers::wait_until;
+use async_trait::async_trait;
+use chrono::{TimeZone, Utc};
+use chrono_tz::Europe::Berlin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Keeps every exchange it is given, taking `delay` over each.
+#[derive(Default)]
+struct Recorder {
+    delay: Duration,
+    exchanges: Mutex<Vec<Exchange>>,
+    in_flight: AtomicUsize,
+    max_in_flight: AtomicUsize,
+}
+
+impl Recorder {
+    fn slow(delay: Duration) -> Arc<Self> {
+        Arc::new(Self {
+            delay,
+            ..Self::default()
+        })
+    }
+
+    fn count(&self) -> usize {
+        self.exchanges.lock().unwrap().len()
+    }
+}
+
+#[async_trait]
+impl Processor for Recorder {
+    async fn process(&self, exchange: Exchange) -> Result<Exchange, DomainError> {
+        let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+        tokio::time::sleep(self.delay).await;
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+        self.exchanges.lock().unwrap().push(exchange.clone());
+        Ok(exchange)
+    }
+}
+
+fn start(uri: &str, recorder: Arc<Recorder>) -> CamelContext {
+    let context = CamelContext::new("test");
+    context.add_component(Arc::new(TimerComponent::new())).unwrap();
+    context
+        .add_route(from(uri).route_id("timer-route").process(recorder).build().unwrap())
+        .unwrap();
+    context.start().unwrap();
+    context
+}
+
+#[actix_rt::test]
+async fn test_timer_fires_after_delay_until_repeat_count() {
+    // Arrange
+    let recorder = Arc::new(Recorder::default());
+    let started = Utc::now();
+
+    // Act
+    let _context = start("timer:tick?period=20&delay=30&repeatCount=3", recorder.clone());
+    wait_until(|| recorder.count() >= 3).await;
+    tokio::time::sleep(Duration::from_millis(60)).await;
+
+    // Assert
+    let exchanges = recorder.exchanges.lock().unwrap();
+    assert_eq!(exchanges.len(), 3);
+    let counters: Vec<Option<i64>> = exchanges.iter().map(|e| e.headers[TIMER_COUNTER].as_i64()).collect();
+    assert_eq!(counters, [Some(1), Some(2), Some(3)]);
+    assert_eq!(exchanges[0].headers[TIMER_NAME], "tick");
+    let scheduled: Vec<_> = exchanges
+        .iter()
+        .map(|e| e.headers[TIMER_SCHEDULED_AT].as_timestamp().unwrap())
+        .collect();
+    assert!(scheduled[0] >= started + Duration::from_millis(30));
+    assert_eq!(scheduled[1] - scheduled[0], chrono::Duration::milliseconds(20));
+    assert!(exchanges[0].headers[TIMER_FIRED_AT].as_timestamp().unwrap() >= scheduled[0]);
+    assert!(exchanges[0].body == "");
+}
+
+#[actix_rt::test]
+async fn test_timer_skips_firings_while_the_previous_one_runs() {
+    // Arrange
+    let serial = Recorder::slow(Duration::from_millis(40));
+    let overlapping = Recorder::slow(Duration::from_millis(40));
+
+    // Act
+    let _serial = start("timer:serial?period=10&repeatCount=3", serial.clone());
+    let _overlapping = start("timer:overlap?period=10&repeatCount=3&allowOverlap=true", overlapping.clone());
+    wait_until(|| serial.count() >= 3).await;
+    wait_until(|| overlapping.count() >= 3).await;
+
+    // Assert
+    assert_eq!(serial.max_in_flight.load(Ordering::SeqCst), 1);
+    assert!(overlapping.max_in_flight.load(Ordering::SeqCst) > 1);
+    let serial = serial.exchanges.lock().unwrap();
+    let gap = serial[1].headers[TIMER_SCHEDULED_AT].as_timestamp().unwrap()
+        - serial[0].headers[TIMER_SCHEDULED_AT].as_timestamp().unwrap();
+    assert!(gap >= chrono::Duration::milliseconds(40), "missed firings are skipped, not caught up");
+}
+
+#[actix_rt::test]
+async fn test_stopped_timer_no_longer_fires() {
+    // Arrange
+    let recorder = Arc::new(Recorder::default());
+    let context = start("timer:stoppable?period=10", recorder.clone());
+    wait_until(|| recorder.count() >= 2).await;
+
+    // Act
+    context.stop().unwrap();
+    // A firing already being processed still completes
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    let fired = recorder.count();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    // Assert
+    assert_eq!(recorder.count(), fired);
+}
+
+#[test]
+fn test_cron_schedule_follows_its_time_zone() {
+    let schedule = TimerSchedule::cron("0+0+2+*+*+*", Berlin).unwrap();
+
+    // 02:00 in Berlin is 01:00 UTC in winter and 00:00 UTC in summer
+    let winter = schedule.next_after(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap());
+    let summer = schedule.next_after(Utc.with_ymd_and_hms(2024, 6, 30, 23, 0, 0).unwrap());
+
+    assert_eq!(winter, Some(Utc.with_ymd_and_hms(2024, 1, 1, 1, 0, 0).unwrap()));
+    assert_eq!(summer, Some(Utc.with_ymd_and_hms(2024, 7, 1, 0, 0, 0).unwrap()));
+}
+
+#[actix_rt::test]
+async fn test_cron_timer_sets_firing_headers() {
+    // Arrange
+    let recorder = Arc::new(Recorder::default());
+
+    // Act
+    let _context = start("timer:every-second?cron=*+*+*+*+*+*&timeZone=Europe/Berlin&repeatCount=1", recorder.clone());
+    wait_until(|| recorder.count() >= 1).await;
+
+    // Assert
+    let exchanges = recorder.exchanges.lock().unwrap();
+    let scheduled = exchanges[0].headers[TIMER_SCHEDULED_AT].as_timestamp().unwrap();
+    assert_eq!(scheduled.timestamp_subsec_nanos(), 0);
+    assert_eq!(exchanges[0].headers[TIMER_COUNTER], HeaderValue::Integer(1));
+}
+
+#[test]
+fn test_timer_rejects_invalid_options_and_producers() {
+    let component = TimerComponent::new();
+    let create = |uri: &str| component.create_endpoint(&EndpointUri::parse(uri).unwrap());
+
+    assert!(create("timer:a?period=0").is_err());
+    assert!(create("timer:a?period=10&cron=*+*+*+*+*+*").is_err());
+    assert!(create("timer:a?cron=every+day").is_err());
+    assert!(create("timer:a?cron=0+0+2+*+*+*&timeZone=Mars/Olympus").is_err());
+    assert!(create("timer:a").unwrap().create_producer().is_err());
+}
@@ -0,0 +1,50 @@
+use crate::domain::{
+    models::{error::ErrorKind, exchange::Exchange},
+    ports::repository::MessageRepository,
+};
+use crate::infrastructure::repositories::{
+    file_log_repository::{FileLogConfig, FileLogMessageRepository},
+    message_repository::InMemoryMessageRepository,
+    sqlite_message_repository::SqliteMessageRepository,
+};
+use crate::tests::helpers::temp_dir;
+
+async fn assert_rejects_stale_saves(repository: &dyn MessageRepository) {
+    let exchange = Exchange::new("v0".to_string());
+    repository.save(&exchange).await.unwrap();
+
+    let mut first = repository.find_by_id(&exchange.id).await.unwrap().unwrap();
+    let mut second = first.clone();
+    assert_eq!(first.version, 1);
+
+    first.body = "first".into();
+    repository.save(&first).await.unwrap();
+    second.body = "second".into();
+    let error = repository.save(&second).await.unwrap_err();
+
+    assert_eq!(error.kind(), ErrorKind::Conflict);
+    let stored = repository.find_by_id(&exchange.id).await.unwrap().unwrap();
+    assert_eq!((stored.body.text().unwrap().as_ref(), stored.version), ("first", 2));
+
+    // A message deleted in the meantime cannot be resurrected by a stale copy
+    repository.delete(&exchange.id).await.unwrap();
+    let error = repository.save(&stored).await.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Conflict);
+}
+
+#[actix_rt::test]
+async fn test_in_memory_repository_rejects_stale_saves() {
+    assert_rejects_stale_saves(&InMemoryMessageRepository::new()).await;
+}
+
+#[actix_rt::test]
+async fn test_sqlite_repository_rejects_stale_saves() {
+    assert_rejects_stale_saves(&SqliteMessageRepository::in_memory().unwrap()).await;
+}
+
+#[actix_rt::test]
+async fn test_file_log_repository_rejects_stale_saves() {
+    let dir = temp_dir("log");
+    assert_rejects_stale_saves(&FileLogMessageRepository::open(&dir, FileLogConfig::default()).unwrap()).await;
+    std::fs::remove_dir_all(&dir).unwrap();
+}